@@ -0,0 +1,152 @@
+use anyhow::Result;
+use nalgebra::Point3;
+use std::f32::consts::PI;
+
+use crate::mesh::ProfilePoint;
+
+/// Largest angle a rounded corner spans between grid columns, in radians
+const CORNER_STEP: f32 = PI / 18.0;
+
+/// Outline of a non-circular aperture
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApertureShape {
+    /// Rectangle, optionally with rounded corners
+    Rectangle,
+    /// Ellipse with its axes along X and Y
+    Ellipse,
+}
+
+impl std::fmt::Display for ApertureShape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApertureShape::Rectangle => write!(f, "rectangular"),
+            ApertureShape::Ellipse => write!(f, "elliptical"),
+        }
+    }
+}
+
+/// Rectangular or elliptical aperture for filters, fold mirrors and
+/// elliptical flats.
+///
+/// The ring's inner and outer diameters are the X dimensions of the
+/// aperture and the frame; the frame wall is the same width all round, so
+/// the frame's Y dimension is `inner_y` plus the same two walls.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApertureSpec {
+    pub shape: ApertureShape,
+    /// Aperture size along Y
+    pub inner_y: f32,
+    /// Corner radius of a rectangular aperture; the frame's outer corners
+    /// are rounded by this plus the wall
+    pub corner_radius: f32,
+}
+
+impl ApertureSpec {
+    /// X and Y size of the aperture for a ring of `inner_diameter`, offset outward by `offset`
+    pub fn size(&self, inner_diameter: f32, offset: f32) -> (f32, f32) {
+        (inner_diameter + 2.0 * offset, self.inner_y + 2.0 * offset)
+    }
+
+    /// Area enclosed by the aperture outline offset outward by `offset`
+    /// (Steiner's formula: area plus perimeter times offset plus a circle)
+    pub fn area(&self, inner_radius: f32, offset: f32) -> f32 {
+        let (a, b) = (inner_radius, self.inner_y / 2.0);
+        let (area, perimeter) = match self.shape {
+            ApertureShape::Rectangle => {
+                let rounding = self.corner_radius;
+                (4.0 * a * b - (4.0 - PI) * rounding * rounding, 4.0 * (a + b) - (8.0 - 2.0 * PI) * rounding)
+            }
+            ApertureShape::Ellipse => {
+                // Ramanujan's approximation
+                let h = ((a - b) / (a + b)).powi(2);
+                (PI * a * b, PI * (a + b) * (1.0 + 3.0 * h / (10.0 + (4.0 - 3.0 * h).sqrt())))
+            }
+        };
+        area + perimeter * offset + PI * offset * offset
+    }
+
+    /// Check the aperture is printable and its corners fit
+    pub fn validate(&self, inner_radius: f32) -> Result<()> {
+        if self.inner_y < 5.0 {
+            return Err(anyhow::anyhow!(
+                "Aperture Y size ({:.1}mm) is too small for practical use",
+                self.inner_y
+            ));
+        }
+        let limit = inner_radius.min(self.inner_y / 2.0);
+        if self.corner_radius < 0.0 || self.corner_radius > limit {
+            return Err(anyhow::anyhow!(
+                "Corner radius must be between 0 and {:.2}mm, half the shorter side",
+                limit
+            ));
+        }
+        if self.shape == ApertureShape::Ellipse && self.corner_radius > 0.0 {
+            return Err(anyhow::anyhow!("Corner radii only apply to rectangular apertures"));
+        }
+        Ok(())
+    }
+
+    /// Point on the aperture outline and its outward normal at grid angle
+    /// `angle`. Rectangles are spaced evenly along the outline at mid-wall,
+    /// so straight sides and corners both get columns; ellipses use the
+    /// normal's direction, which crowds columns where they curve most.
+    fn outline(&self, angle: f32, inner_radius: f32, wall: f32) -> ([f32; 2], [f32; 2]) {
+        let (a, b) = (inner_radius, self.inner_y / 2.0);
+        let angle = angle.rem_euclid(2.0 * PI);
+        if self.shape == ApertureShape::Ellipse {
+            let t = (a * angle.sin()).atan2(b * angle.cos());
+            return ([a * t.cos(), b * t.sin()], [angle.cos(), angle.sin()]);
+        }
+
+        // The quadrants mirror the first one
+        let (u, sx, sy) = match angle {
+            u if u <= PI / 2.0 => (u, 1.0, 1.0),
+            u if u <= PI => (PI - u, -1.0, 1.0),
+            u if u <= 1.5 * PI => (u - PI, -1.0, -1.0),
+            u => (2.0 * PI - u, 1.0, -1.0),
+        };
+        let ([x, y], [nx, ny]) = self.first_quadrant(u, a, b, wall);
+        ([sx * x, sy * y], [sx * nx, sy * ny])
+    }
+
+    /// Outline from +X (u = 0) to +Y (u = π/2) of a rounded rectangle
+    fn first_quadrant(&self, u: f32, a: f32, b: f32, wall: f32) -> ([f32; 2], [f32; 2]) {
+        let (side, top, rounding) = (b - self.corner_radius, a - self.corner_radius, self.corner_radius);
+        let arc = (rounding + wall / 2.0) * PI / 2.0;
+        let s = u / (PI / 2.0) * (side + arc + top);
+        if s <= side {
+            ([a, s], [1.0, 0.0])
+        } else if s <= side + arc {
+            let turn = (s - side) / arc * PI / 2.0;
+            ([top + rounding * turn.cos(), side + rounding * turn.sin()], [turn.cos(), turn.sin()])
+        } else {
+            ([top - (s - side - arc), b], [0.0, 1.0])
+        }
+    }
+
+    /// Grid angles at the ends of the rounded corners, and enough within
+    /// them that the corners stay smooth
+    pub fn angle_breaks(&self, inner_radius: f32, wall: f32) -> Vec<f32> {
+        if self.shape == ApertureShape::Ellipse {
+            return Vec::new();
+        }
+        let (a, b) = (inner_radius, self.inner_y / 2.0);
+        let (side, top) = (b - self.corner_radius, a - self.corner_radius);
+        let arc = (self.corner_radius + wall / 2.0) * PI / 2.0;
+        let quadrant = side + arc + top;
+        let steps = (PI / 2.0 / CORNER_STEP).ceil() as usize;
+        (0..=steps)
+            .map(|k| (side + arc * k as f32 / steps as f32) / quadrant * PI / 2.0)
+            .flat_map(|u| [u, PI - u, PI + u, 2.0 * PI - u])
+            .collect()
+    }
+
+    /// Place a profile point of a ring with bore `inner_radius` on the frame:
+    /// the profile's distance from the bore becomes the distance from the
+    /// aperture outline
+    pub fn place(&self, point: &ProfilePoint, angle: f32, inner_radius: f32, outer_radius: f32) -> Point3<f32> {
+        let ([x, y], [nx, ny]) = self.outline(angle, inner_radius, outer_radius - inner_radius);
+        let offset = point.r - inner_radius;
+        Point3::new(x + offset * nx, y + offset * ny, point.z)
+    }
+}
@@ -0,0 +1,79 @@
+use anyhow::Result;
+
+/// Points sampled across the seat when checking the profile
+const CHECK_STEPS: usize = 128;
+
+/// Even-asphere lens surface that a CX or CC seat follows instead of the
+/// default curve, so aspheric lenses rest on their actual surface.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AsphereSpec {
+    /// Base radius of curvature: positive convex, negative concave
+    pub radius: f32,
+    /// Conic constant: 0 sphere, -1 paraboloid, below -1 hyperboloid
+    pub conic: f32,
+    /// Polynomial coefficients of r⁴, r⁶, r⁸, ...
+    pub coefficients: Vec<f32>,
+}
+
+impl AsphereSpec {
+    /// Sag of the surface at `r` from its axis, or `None` where the conic
+    /// term is undefined
+    pub fn sag(&self, r: f32) -> Option<f32> {
+        let (r, c, k) = (r as f64, 1.0 / self.radius as f64, self.conic as f64);
+        let root = 1.0 - (1.0 + k) * c * c * r * r;
+        if root < 0.0 {
+            return None;
+        }
+        let polynomial: f64 = self.coefficients.iter().enumerate()
+            .map(|(i, &a)| a as f64 * r.powi(2 * i as i32 + 4))
+            .sum();
+        Some((c * r * r / (1.0 + root.sqrt()) + polynomial) as f32)
+    }
+
+    /// Height of the seat at `r` for a ring from `inner_radius` to
+    /// `outer_radius`: the surface's sag, shifted so the seat's higher edge is
+    /// at `height`
+    pub fn height_at(&self, r: f32, inner_radius: f32, outer_radius: f32, height: f32) -> f32 {
+        let sag = |r: f32| self.sag(r).unwrap_or_default();
+        height + sag(r) - sag(inner_radius).max(sag(outer_radius))
+    }
+
+    /// How far the seat drops from its higher edge to its lower one
+    pub fn drop(&self, inner_radius: f32, outer_radius: f32) -> f32 {
+        let sag = |r: f32| self.sag(r).unwrap_or_default();
+        (sag(outer_radius) - sag(inner_radius)).abs()
+    }
+
+    /// Check the surface is defined across the seat, slopes one way only,
+    /// and drops less than the ring is high
+    pub fn validate(&self, inner_radius: f32, outer_radius: f32, height: f32) -> Result<()> {
+        if self.radius == 0.0 {
+            return Err(anyhow::anyhow!("Aspheric seats need a base radius. Use --lens-radius <MM>"));
+        }
+        let sags = (0..=CHECK_STEPS)
+            .map(|k| {
+                let r = inner_radius + (outer_radius - inner_radius) * k as f32 / CHECK_STEPS as f32;
+                self.sag(r).ok_or_else(|| anyhow::anyhow!(
+                    "The {:.2}mm radius, {:.3} conic surface is undefined at {:.2}mm diameter, inside the seat",
+                    self.radius, self.conic, 2.0 * r
+                ))
+            })
+            .collect::<Result<Vec<f32>>>()?;
+        let rising = sags.windows(2).any(|pair| pair[1] > pair[0] + 1e-6);
+        let falling = sags.windows(2).any(|pair| pair[1] < pair[0] - 1e-6);
+        if rising && falling {
+            return Err(anyhow::anyhow!(
+                "The aspheric surface turns back across the {:.2}-{:.2}mm seat. A seat must slope one way; check the coefficients",
+                2.0 * inner_radius, 2.0 * outer_radius
+            ));
+        }
+        let drop = self.drop(inner_radius, outer_radius);
+        if height - drop < 1.0 {
+            return Err(anyhow::anyhow!(
+                "The aspheric sag changes by {:.2}mm across the seat. Raise --height to at least {:.2}mm",
+                drop, drop + 1.0
+            ));
+        }
+        Ok(())
+    }
+}
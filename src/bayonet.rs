@@ -0,0 +1,239 @@
+use anyhow::Result;
+use nalgebra::Point3;
+use std::f32::consts::PI;
+use stl_io::Triangle;
+
+use crate::features::angle_between;
+use crate::geometry::{generate_ring_mesh, RingParameters, RING_SEGMENTS};
+use crate::mesh::{merge_angles, revolve, uniform_angles, ProfilePoint, Surface, EDGE_RAMP};
+use crate::naming::part_filename;
+
+/// Height of the near-vertical step at the top of lugs and grooves, in millimeters
+const STEP_RISE: f32 = 0.02;
+
+/// Length of the detent bump along the groove, in millimeters
+const DETENT_LENGTH: f32 = 1.0;
+
+/// Smallest web left between neighboring socket grooves, in degrees
+const MIN_WEB: f32 = 10.0;
+
+/// Solid wall the socket keeps above its grooves, in millimeters
+const SOCKET_LEDGE: f32 = 2.0;
+
+/// Bayonet lugs on the ring's outer wall and the socket they twist into.
+///
+/// The ring drops into the socket with its lugs in the entry slots, then
+/// turns counter-clockwise (seen from the top) by `twist` to lock.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BayonetSpec {
+    pub lugs: usize,
+    /// Angular width of each lug in degrees
+    pub lug_width: f32,
+    /// How far lugs stand out from the outer wall
+    pub lug_depth: f32,
+    /// Lug height above the ring base
+    pub lug_height: f32,
+    /// Lead-in: the leading edge of each lug is this much lower than the trailing edge
+    pub ramp: f32,
+    /// Depth of the bump the lugs snap past at the locked position; 0 for none
+    pub detent: f32,
+    /// Rotation from the entry slots to the locked position, in degrees
+    pub twist: f32,
+    /// Gap between the lugs and the socket on every side
+    pub clearance: f32,
+    /// Socket wall behind the grooves
+    pub socket_wall: f32,
+    /// Socket floor the ring base rests on
+    pub socket_floor: f32,
+}
+
+impl Default for BayonetSpec {
+    fn default() -> Self {
+        Self {
+            lugs: 3,
+            lug_width: 30.0,
+            lug_depth: 1.0,
+            lug_height: 2.0,
+            ramp: 0.3,
+            detent: 0.3,
+            twist: 45.0,
+            clearance: 0.2,
+            socket_wall: 2.0,
+            socket_floor: 1.5,
+        }
+    }
+}
+
+impl BayonetSpec {
+    /// Center angle of each lug in radians, starting at the clocking angle in degrees
+    pub fn lug_angles(&self, clocking: f32) -> Vec<f32> {
+        (0..self.lugs)
+            .map(|i| clocking.to_radians() + 2.0 * PI * i as f32 / self.lugs as f32)
+            .collect()
+    }
+
+    fn lug_half_angle(&self) -> f32 {
+        self.lug_width.to_radians() / 2.0
+    }
+
+    /// Check that lugs fit on the ring and the socket grooves fit around it
+    pub fn validate(&self, inner_radius: f32, outer_radius: f32, seat_height: f32) -> Result<()> {
+        if self.lugs < 2 {
+            return Err(anyhow::anyhow!("Bayonet mounts need at least 2 lugs"));
+        }
+        if self.lug_width <= 0.0 || self.lug_depth <= 0.0 || self.lug_height <= 0.0 || self.twist <= 0.0 {
+            return Err(anyhow::anyhow!("Lug width, depth, height and twist must be positive"));
+        }
+        if self.clearance < 0.0 || self.ramp < 0.0 || self.detent < 0.0 {
+            return Err(anyhow::anyhow!("Bayonet clearance, ramp and detent can't be negative"));
+        }
+        if self.ramp >= self.lug_height {
+            return Err(anyhow::anyhow!("Lug ramp ({:.2}mm) must be lower than the lug ({:.2}mm)", self.ramp, self.lug_height));
+        }
+        if self.top() > seat_height - 1.0 {
+            return Err(anyhow::anyhow!(
+                "Lugs {:.2}mm high need a ring at least {:.2}mm high under the seat",
+                self.lug_height, self.top() + 1.0
+            ));
+        }
+        if self.detent > 0.0 && self.ramp + self.clearance <= self.detent {
+            return Err(anyhow::anyhow!(
+                "The lug ramp ({:.2}mm) is too low to lead past a {:.2}mm detent with {:.2}mm clearance",
+                self.ramp, self.detent, self.clearance
+            ));
+        }
+
+        let socket_radius = outer_radius + self.clearance;
+        let slot = self.lug_width + 2.0 * (self.clearance / socket_radius).to_degrees();
+        if self.detent > 0.0 && self.twist < slot + (DETENT_LENGTH / socket_radius).to_degrees() {
+            return Err(anyhow::anyhow!(
+                "A {:.0}° twist leaves no room for the detent behind {:.0}° lugs. Use a twist of at least {:.0}°",
+                self.twist, self.lug_width, (slot + (DETENT_LENGTH / socket_radius).to_degrees()).ceil()
+            ));
+        }
+        let span = self.lugs as f32 * (slot + self.twist + MIN_WEB);
+        if span > 360.0 {
+            return Err(anyhow::anyhow!(
+                "{} lugs {:.0}° wide with {:.0}° of twist need {:.0}° of socket. Use fewer or narrower lugs",
+                self.lugs, self.lug_width, self.twist, span
+            ));
+        }
+        if inner_radius >= outer_radius {
+            return Err(anyhow::anyhow!("The socket floor needs a ring with a wall"));
+        }
+        Ok(())
+    }
+
+    /// Angles where lugs start and end on the ring
+    pub fn angle_breaks(&self, clocking: f32) -> Vec<f32> {
+        let half = self.lug_half_angle();
+        self.lug_angles(clocking).into_iter()
+            .flat_map(|lug| [lug - half - EDGE_RAMP, lug - half, lug + half, lug + half + EDGE_RAMP])
+            .collect()
+    }
+
+    /// Outer wall heights at the top of the lugs
+    pub fn height_breaks(&self) -> Vec<f32> {
+        vec![self.lug_height, self.top()]
+    }
+
+    /// Highest point the lugs reach on the ring
+    pub fn top(&self) -> f32 {
+        self.lug_height + STEP_RISE
+    }
+
+    /// Shape the lugs into an outer wall point. Lug tops slope down toward
+    /// the leading (counter-clockwise) edge by `ramp`.
+    pub fn apply_lug(&self, point: &ProfilePoint, angle: f32, clocking: f32) -> ProfilePoint {
+        let half = self.lug_half_angle();
+        let offset = self.lug_angles(clocking).into_iter()
+            .map(|lug| angle_between(angle, lug))
+            .find(|offset| offset.abs() <= half + 1e-5);
+        let offset = match offset {
+            Some(offset) if point.surface == Surface::Outer && point.z <= self.top() + 1e-5 => offset,
+            _ => return *point,
+        };
+
+        let lead = (offset / half + 1.0) / 2.0;
+        let scale = (self.lug_height - self.ramp * lead) / self.lug_height;
+        let r = if point.z <= self.lug_height + 1e-5 { point.r + self.lug_depth } else { point.r };
+        ProfilePoint { r, z: point.z * scale, ..*point }
+    }
+
+    /// Generate the socket for a ring with outer radius `outer_radius` and
+    /// bore `inner_radius`, clocked like the ring at its locked position
+    pub fn generate_socket(&self, inner_radius: f32, outer_radius: f32, clocking: f32) -> Vec<Triangle> {
+        let bore = outer_radius + self.clearance;
+        let groove = self.lug_depth + self.clearance;
+        let outer = bore + groove + self.socket_wall;
+        let floor = self.socket_floor;
+        let ceiling = floor + self.lug_height + self.clearance;
+        let height = ceiling + SOCKET_LEDGE;
+
+        // Socket angles: slots where the lugs enter, grooves they turn along
+        let slot_half = self.lug_half_angle() + self.clearance / bore;
+        let twist = self.twist.to_radians();
+        let detent_end = twist - self.lug_half_angle() - self.clearance / bore;
+        let detent_start = detent_end - DETENT_LENGTH / bore;
+        let slots = self.lug_angles(clocking - self.twist);
+
+        let cut = |angle: f32, z: f32| -> bool {
+            slots.iter().any(|&slot| {
+                let offset = angle_between(angle, slot);
+                let along = (angle - slot + slot_half).rem_euclid(2.0 * PI) - slot_half;
+                let in_slot = offset.abs() <= slot_half + 1e-5 && z >= floor - 1e-5;
+                let in_groove = along <= twist + slot_half + 1e-5 && z <= ceiling + 1e-5;
+                let on_detent = self.detent > 0.0
+                    && (detent_start - 1e-5..=detent_end + 1e-5).contains(&along)
+                    && z > ceiling - self.detent + 1e-5;
+                in_slot || (in_groove && !on_detent)
+            })
+        };
+
+        let mut breaks = Vec::new();
+        for &slot in &slots {
+            let mut edges = vec![slot - slot_half, slot + slot_half, slot + twist + slot_half];
+            if self.detent > 0.0 {
+                edges.extend([slot + detent_start, slot + detent_end]);
+            }
+            for edge in edges {
+                breaks.extend([edge - EDGE_RAMP, edge, edge + EDGE_RAMP]);
+            }
+        }
+        let angles = merge_angles(uniform_angles(RING_SEGMENTS), breaks);
+
+        let mut heights = vec![floor, ceiling, ceiling + STEP_RISE, height];
+        if self.detent > 0.0 {
+            heights.extend([ceiling - self.detent, ceiling - self.detent + STEP_RISE]);
+        }
+        heights.sort_by(|a, b| a.total_cmp(b));
+
+        let mut profile = vec![
+            ProfilePoint::new(inner_radius, 0.0, Surface::Bore),
+            ProfilePoint::new(outer, 0.0, Surface::Outer),
+            ProfilePoint::new(outer, height, Surface::Outer),
+        ];
+        profile.extend(heights.iter().rev().map(|&z| ProfilePoint::new(bore, z, Surface::Bore)));
+        profile.push(ProfilePoint::new(inner_radius, floor, Surface::Bore));
+
+        revolve(&profile, &angles, |point, angle| {
+            let on_socket_bore = point.surface == Surface::Bore && point.r >= bore - 1e-5;
+            let r = if on_socket_bore && cut(angle, point.z) { point.r + groove } else { point.r };
+            Point3::new(r * angle.cos(), r * angle.sin(), point.z)
+        }).into_triangles()
+    }
+}
+
+/// Generate a ring with bayonet lugs and its matching socket, named
+/// after the ring with a `-socket` suffix for the socket
+pub fn generate_bayonet_parts(params: &RingParameters) -> Result<Vec<(String, Vec<Triangle>)>> {
+    let spec = params.bayonet.as_ref()
+        .ok_or_else(|| anyhow::anyhow!("Bayonet parts need a bayonet specification"))?;
+    let filename = params.filename();
+    let socket = spec.generate_socket(params.inner_diameter / 2.0, params.outer_diameter / 2.0, params.features.clocking);
+
+    Ok(vec![
+        (filename.clone(), generate_ring_mesh(params)?),
+        (part_filename(&filename, "socket"), socket),
+    ])
+}
@@ -0,0 +1,99 @@
+use anyhow::Result;
+
+/// Conical (chamfered) seat: the top face is a cone opening upward, which
+/// self-centers spherical lens surfaces and balls.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConeSpec {
+    /// Angle between the cone surface and the ring axis, in degrees
+    pub half_angle: f32,
+    /// Diameter where the cone reaches the ring's height; mid-wall when unset
+    pub contact_diameter: Option<f32>,
+    /// Radius of the convex surface or ball resting on the cone, if known
+    pub lens_radius: Option<f32>,
+}
+
+impl Default for ConeSpec {
+    fn default() -> Self {
+        Self {
+            half_angle: 45.0,
+            contact_diameter: None,
+            lens_radius: None,
+        }
+    }
+}
+
+/// Where a lens of known radius lands on a conical seat, in ring coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConeContact {
+    pub contact_r: f32,
+    pub contact_z: f32,
+    /// Height of the lens vertex above the ring base; negative below it
+    pub vertex_z: f32,
+}
+
+impl ConeSpec {
+    /// Radius where the cone reaches the ring's height
+    pub fn contact_radius(&self, inner_radius: f32, outer_radius: f32) -> f32 {
+        self.contact_diameter.map_or((inner_radius + outer_radius) / 2.0, |d| d / 2.0)
+    }
+
+    /// Height of the cone at radius `r` for a ring from `inner_radius` to `outer_radius`
+    pub fn height_at(&self, r: f32, inner_radius: f32, outer_radius: f32, height: f32) -> f32 {
+        height + (r - self.contact_radius(inner_radius, outer_radius)) / self.half_angle.to_radians().tan()
+    }
+
+    /// Where a sphere of the lens radius touches the cone. The contact normal
+    /// is square to the cone, so the sphere lands at `R cos(half angle)` whatever
+    /// the cone's position.
+    pub fn landing(&self, inner_radius: f32, outer_radius: f32, height: f32) -> Option<ConeContact> {
+        let lens_radius = self.lens_radius?;
+        let half_angle = self.half_angle.to_radians();
+        let contact_r = lens_radius * half_angle.cos();
+        let contact_z = self.height_at(contact_r, inner_radius, outer_radius, height);
+        Some(ConeContact {
+            contact_r,
+            contact_z,
+            vertex_z: contact_z + lens_radius * half_angle.sin() - lens_radius,
+        })
+    }
+
+    /// Check the cone angle, that the cone stays above the base, and that a
+    /// lens of known radius lands on the wall
+    pub fn validate(&self, inner_radius: f32, outer_radius: f32, height: f32) -> Result<()> {
+        if !(10.0..=80.0).contains(&self.half_angle) {
+            return Err(anyhow::anyhow!(
+                "Cone half-angle {:.1}° is out of range. Use 10-80°",
+                self.half_angle
+            ));
+        }
+        let contact_r = self.contact_radius(inner_radius, outer_radius);
+        if contact_r < inner_radius || contact_r > outer_radius {
+            return Err(anyhow::anyhow!(
+                "Contact diameter {:.2}mm must lie on the {:.2}-{:.2}mm seat",
+                2.0 * contact_r, 2.0 * inner_radius, 2.0 * outer_radius
+            ));
+        }
+        let lowest = self.height_at(inner_radius, inner_radius, outer_radius, height);
+        if lowest < 1.0 {
+            return Err(anyhow::anyhow!(
+                "The cone drops to {:.2}mm at the bore. Raise --height to at least {:.2}mm",
+                lowest, height + 1.0 - lowest
+            ));
+        }
+        if let Some(lens_radius) = self.lens_radius {
+            if lens_radius <= 0.0 {
+                return Err(anyhow::anyhow!("Conical seats hold convex surfaces and balls. Use a positive --lens-radius"));
+            }
+        }
+        if let Some(landing) = self.landing(inner_radius, outer_radius, height) {
+            if landing.contact_r < inner_radius + 0.5 || landing.contact_r > outer_radius - 0.5 {
+                return Err(anyhow::anyhow!(
+                    "A {:.2}mm lens radius lands on the {:.0}° cone at {:.2}mm diameter, off the {:.2}-{:.2}mm wall. Change --cone-angle or the ring diameters",
+                    self.lens_radius.unwrap_or_default(), self.half_angle, 2.0 * landing.contact_r,
+                    2.0 * inner_radius, 2.0 * outer_radius
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// User settings persisted between runs
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Project directories bookmarked in the directory browser
+    pub bookmarks: Vec<PathBuf>,
+    /// Filename template, e.g. `{type}-{inner}-{material}.stl`
+    pub filename_template: Option<String>,
+    /// Material name used for the `{material}` placeholder
+    pub material: Option<String>,
+}
+
+impl Config {
+    /// Location of the config file, e.g. `~/.config/optics-ring-generator/config.toml`
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("optics-ring-generator").join("config.toml"))
+    }
+
+    /// Load the user config, falling back to defaults if it is missing or unreadable
+    pub fn load() -> Self {
+        Self::default_path()
+            .filter(|path| path.exists())
+            .and_then(|path| Self::load_from(&path).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        toml::from_str(&contents)
+            .with_context(|| format!("Invalid config file {}", path.display()))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::default_path()
+            .ok_or_else(|| anyhow::anyhow!("Could not determine the user config directory"))?;
+        self.save_to(&path)
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, toml::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write config file {}", path.display()))
+    }
+
+    /// Add `path` to the bookmarks, or remove it if already present.
+    /// Returns true if the path is bookmarked afterwards.
+    pub fn toggle_bookmark(&mut self, path: &Path) -> bool {
+        if let Some(i) = self.bookmarks.iter().position(|bookmark| bookmark == path) {
+            self.bookmarks.remove(i);
+            false
+        } else {
+            self.bookmarks.push(path.to_path_buf());
+            true
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use anyhow::Result;
+
+/// Sharp-corner seat: a convex lens surface rests on the bore's top edge,
+/// optionally rounded to a small relief radius.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CornerSpec {
+    /// Radius of the convex lens surface on the seat
+    pub lens_radius: f32,
+    /// Radius the bore corner is rounded to; 0 for a sharp edge
+    pub relief: f32,
+}
+
+/// Where the lens touches a sharp-corner seat, in ring coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CornerContact {
+    pub contact_r: f32,
+    pub contact_z: f32,
+    /// Height of the lens vertex above the ring base; negative below it
+    pub vertex_z: f32,
+}
+
+impl CornerSpec {
+    /// Contact for a ring with bore `inner_radius` whose top face is at `height`
+    pub fn contact(&self, inner_radius: f32, height: f32) -> CornerContact {
+        // The lens rests on the relief circle; a sharp edge is a circle of radius 0
+        let (center_r, center_z) = (inner_radius + self.relief, height - self.relief);
+        let reach = self.lens_radius + self.relief;
+        let lens_center = center_z + (reach * reach - center_r * center_r).max(0.0).sqrt();
+        CornerContact {
+            contact_r: center_r - self.relief * center_r / reach,
+            contact_z: center_z + self.relief * (lens_center - center_z) / reach,
+            vertex_z: lens_center - self.lens_radius,
+        }
+    }
+
+    /// Check the lens rests on the corner and the relief fits the wall
+    pub fn validate(&self, inner_radius: f32, outer_radius: f32, height: f32) -> Result<()> {
+        if self.lens_radius <= 0.0 {
+            return Err(anyhow::anyhow!(
+                "Sharp-corner seats hold convex surfaces. Use a positive --lens-radius <MM>"
+            ));
+        }
+        if self.relief < 0.0 {
+            return Err(anyhow::anyhow!("Relief radius can't be negative"));
+        }
+        if self.relief > (outer_radius - inner_radius) / 2.0 || self.relief > height - 1.0 {
+            return Err(anyhow::anyhow!(
+                "A {:.2}mm relief radius doesn't fit the corner. Maximum: {:.2}mm",
+                self.relief, ((outer_radius - inner_radius) / 2.0).min(height - 1.0).max(0.0)
+            ));
+        }
+        if self.lens_radius <= inner_radius + self.relief {
+            return Err(anyhow::anyhow!(
+                "A {:.2}mm lens surface radius is too small to rest on a {:.2}mm bore",
+                self.lens_radius, 2.0 * inner_radius
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use anyhow::Result;
+use stl_io::Triangle;
+
+use crate::features::{IndexMark, MarkStyle};
+use crate::geometry::{generate_ring_mesh, RingParameters};
+use crate::mesh::ProfilePoint;
+use crate::naming::part_filename;
+
+/// Thinnest wall an eccentric ring may leave, in millimeters
+const MIN_ECCENTRIC_WALL: f32 = 1.0;
+
+/// Offset between the bore's axis and the outer wall's axis.
+///
+/// The bore stays on the lens axis at the origin and the outer wall moves,
+/// so turning the ring in its barrel swings the lens on a circle of radius
+/// `offset` around the barrel axis.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EccentricSpec {
+    /// Distance between the two axes, in millimeters
+    pub offset: f32,
+    /// Direction the bore is offset toward, in degrees counter-clockwise from +X
+    pub direction: f32,
+}
+
+impl EccentricSpec {
+    /// Distance from the bore axis to the outer wall at `angle` (radians)
+    pub fn outer_radius_at(&self, angle: f32, outer_radius: f32) -> f32 {
+        // The outer wall's center sits opposite the bore offset
+        let direction = self.direction.to_radians();
+        let along = -self.offset * (angle - direction).cos();
+        along + (outer_radius * outer_radius - self.offset * self.offset + along * along).max(0.0).sqrt()
+    }
+
+    /// Move a profile point so the outer wall follows the offset circle. The
+    /// shift grows from nothing at the bore to the full offset at the outer
+    /// wall; lugs and bosses beyond the wall move with it.
+    pub fn apply(&self, point: &ProfilePoint, angle: f32, inner_radius: f32, outer_radius: f32) -> ProfilePoint {
+        let shift = self.outer_radius_at(angle, outer_radius) - outer_radius;
+        let weight = ((point.r - inner_radius) / (outer_radius - inner_radius)).clamp(0.0, 1.0);
+        ProfilePoint { r: point.r + shift * weight, ..*point }
+    }
+
+    /// Check the thin side of the wall is still printable
+    pub fn validate(&self, inner_radius: f32, outer_radius: f32) -> Result<()> {
+        if self.offset < 0.0 {
+            return Err(anyhow::anyhow!("Eccentricity can't be negative"));
+        }
+        let thinnest = outer_radius - inner_radius - self.offset;
+        if thinnest < MIN_ECCENTRIC_WALL {
+            return Err(anyhow::anyhow!(
+                "A {:.2}mm eccentricity leaves a {:.2}mm wall on the thin side. Maximum: {:.2}mm",
+                self.offset, thinnest, (outer_radius - inner_radius - MIN_ECCENTRIC_WALL).max(0.0)
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Rings of a stepped set: `count` rings from centered to `count - 1`
+/// steps, each with an index mark pointing the way its bore is offset
+pub fn eccentric_set(params: &RingParameters, count: usize, step: f32) -> Vec<RingParameters> {
+    let direction = params.eccentric.as_ref().map_or(0.0, |eccentric| eccentric.direction);
+    let style = params.features.mark.as_ref().map_or(MarkStyle::Arrow, |mark| mark.style);
+    (0..count)
+        .map(|k| {
+            let mut ring = params.clone();
+            ring.eccentric = Some(EccentricSpec { offset: k as f32 * step, direction });
+            ring.features.mark = Some(IndexMark { style, angle: direction });
+            ring
+        })
+        .collect()
+}
+
+/// Generate a stepped set of eccentric rings, each named after its
+/// eccentricity, e.g. `CX-25.0-e0.05.stl`
+pub fn generate_eccentric_set(params: &RingParameters, count: usize, step: f32) -> Result<Vec<(String, Vec<Triangle>)>> {
+    let filename = params.filename();
+    eccentric_set(params, count, step)
+        .iter()
+        .map(|ring| {
+            let offset = ring.eccentric.as_ref().map_or(0.0, |eccentric| eccentric.offset);
+            Ok((part_filename(&filename, &format!("e{:.2}", offset)), generate_ring_mesh(ring)?))
+        })
+        .collect()
+}
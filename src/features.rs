@@ -0,0 +1,229 @@
+use anyhow::Result;
+use nalgebra::Point3;
+use std::f32::consts::PI;
+
+use crate::mesh::{ProfilePoint, Surface, EDGE_RAMP};
+
+/// Width and depth of a V notch index mark, in millimeters
+const NOTCH_WIDTH: f32 = 1.5;
+const NOTCH_DEPTH: f32 = 0.8;
+
+/// Deepest an engraved arrow is cut into the top face, in millimeters
+const ARROW_DEPTH: f32 = 0.4;
+
+/// Radial spacing of the top face where marks are engraved, in millimeters
+const ENGRAVING_STEP: f32 = 0.2;
+
+/// Whether a key stands out from the outer wall or is cut into it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyStyle {
+    Key,
+    Keyway,
+}
+
+/// Rectangular key or keyway running the full height of the outer wall
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeySpec {
+    pub style: KeyStyle,
+    /// Center angle in degrees, counter-clockwise from +X
+    pub angle: f32,
+    pub width: f32,
+    pub depth: f32,
+}
+
+/// D-flat cut into the outer wall
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlatSpec {
+    /// Direction of the flat's normal in degrees, counter-clockwise from +X
+    pub angle: f32,
+    /// Depth at the middle of the flat
+    pub depth: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkStyle {
+    /// V notch in the outer wall
+    Notch,
+    /// Arrow engraved in the top face, pointing outward
+    Arrow,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexMark {
+    pub style: MarkStyle,
+    /// Angle in degrees, counter-clockwise from +X
+    pub angle: f32,
+}
+
+/// Optional orientation features that fix a ring's clocking in its cell.
+///
+/// All angles share one frame, so a key at 0° lines up with the first
+/// contact point of a three-point ring clocked at 0°.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OrientationFeatures {
+    pub key: Option<KeySpec>,
+    pub flat: Option<FlatSpec>,
+    pub mark: Option<IndexMark>,
+    /// Angle of the first three-point contact, in degrees
+    pub clocking: f32,
+}
+
+/// Signed difference `a - b` wrapped into -π..π
+pub fn angle_between(a: f32, b: f32) -> f32 {
+    (a - b + PI).rem_euclid(2.0 * PI) - PI
+}
+
+impl OrientationFeatures {
+    pub fn is_empty(&self) -> bool {
+        self.key.is_none() && self.flat.is_none() && self.mark.is_none()
+    }
+
+    /// Check that the features leave a printable wall
+    pub fn validate(&self, inner_radius: f32, outer_radius: f32) -> Result<()> {
+        let wall = outer_radius - inner_radius;
+        let mut cuts = Vec::new();
+        if let Some(ref flat) = self.flat {
+            cuts.push(("D-flat", flat.depth));
+        }
+        if let Some(KeySpec { style: KeyStyle::Keyway, depth, .. }) = self.key {
+            cuts.push(("Keyway", depth));
+        }
+        for (name, depth) in cuts {
+            if depth <= 0.0 || wall - depth < 1.0 {
+                return Err(anyhow::anyhow!(
+                    "{} depth ({:.2}mm) must be positive and leave 1.0mm of the {:.2}mm wall",
+                    name, depth, wall
+                ));
+            }
+        }
+        if let Some(ref key) = self.key {
+            if key.width <= 0.0 || key.width >= outer_radius {
+                return Err(anyhow::anyhow!("Key width ({:.2}mm) must be positive and smaller than the ring radius", key.width));
+            }
+        }
+        if let Some(IndexMark { style: MarkStyle::Arrow, .. }) = self.mark {
+            if wall < 2.5 {
+                return Err(anyhow::anyhow!(
+                    "Arrow index marks need at least 2.5mm of wall ({:.2}mm here). Use a notch instead",
+                    wall
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Extra angles where features start and end, so their edges fall on mesh vertices
+    pub fn angle_breaks(&self, inner_radius: f32, outer_radius: f32) -> Vec<f32> {
+        let mut breaks = Vec::new();
+        if let Some(ref flat) = self.flat {
+            let a = flat.angle.to_radians();
+            let half = ((outer_radius - flat.depth) / outer_radius).clamp(-1.0, 1.0).acos();
+            breaks.extend([a - half, a + half]);
+        }
+        if let Some(ref key) = self.key {
+            let a = key.angle.to_radians();
+            let half = key.width / 2.0 / outer_radius;
+            breaks.extend([a - half - EDGE_RAMP, a - half, a + half, a + half + EDGE_RAMP]);
+        }
+        match self.mark {
+            Some(IndexMark { style: MarkStyle::Notch, angle }) => {
+                let a = angle.to_radians();
+                let half = NOTCH_WIDTH / 2.0 / outer_radius;
+                breaks.extend([a - half, a, a + half]);
+            }
+            Some(IndexMark { style: MarkStyle::Arrow, angle }) => {
+                // Fine angular steps across the arrow so its outline is crisp
+                let a = angle.to_radians();
+                let (_, half_width) = arrow_size(inner_radius, outer_radius);
+                let step = ENGRAVING_STEP / outer_radius;
+                let half = 1.2 * half_width / inner_radius;
+                let steps = (2.0 * half / step).ceil() as usize;
+                breaks.extend((0..=steps).map(|i| a - half + i as f32 * step));
+            }
+            None => {}
+        }
+        breaks
+    }
+
+    /// Radial spacing needed on the top face, if a mark is engraved there
+    pub fn top_step(&self) -> Option<f32> {
+        match self.mark {
+            Some(IndexMark { style: MarkStyle::Arrow, .. }) => Some(ENGRAVING_STEP),
+            _ => None,
+        }
+    }
+
+    /// Outer wall radius at `angle` after flats, keys, keyways and notches
+    fn outer_radius_at(&self, angle: f32, outer_radius: f32) -> f32 {
+        let mut radius = outer_radius;
+
+        if let Some(ref flat) = self.flat {
+            let projection = angle_between(angle, flat.angle.to_radians()).cos();
+            let chord = outer_radius - flat.depth;
+            if projection > 0.0 {
+                radius = radius.min(chord / projection);
+            }
+        }
+        if let Some(ref key) = self.key {
+            let half = key.width / 2.0 / outer_radius;
+            if angle_between(angle, key.angle.to_radians()).abs() <= half + 1e-5 {
+                radius = match key.style {
+                    KeyStyle::Key => radius.max(outer_radius + key.depth),
+                    KeyStyle::Keyway => radius.min(outer_radius - key.depth),
+                };
+            }
+        }
+        if let Some(IndexMark { style: MarkStyle::Notch, angle: mark }) = self.mark {
+            let half = NOTCH_WIDTH / 2.0 / outer_radius;
+            let offset = angle_between(angle, mark.to_radians()).abs();
+            if offset < half {
+                radius = radius.min(outer_radius - NOTCH_DEPTH * (1.0 - offset / half));
+            }
+        }
+        radius
+    }
+
+    /// Depth engraved into the top face at radius `r` and `angle`
+    fn engraving_depth(&self, r: f32, angle: f32, inner_radius: f32, outer_radius: f32) -> f32 {
+        let mark = match self.mark {
+            Some(IndexMark { style: MarkStyle::Arrow, angle }) => angle,
+            _ => return 0.0,
+        };
+
+        // Arrow in local coordinates: u runs outward along the mark, v across it
+        let (length, half_width) = arrow_size(inner_radius, outer_radius);
+        let u = r - (inner_radius + outer_radius - length) / 2.0;
+        let v = r * angle_between(angle, mark.to_radians());
+        let head = 0.45 * length;
+        let inside = if (0.0..length - head).contains(&u) {
+            v.abs() <= 0.35 * half_width
+        } else if (length - head..=length).contains(&u) {
+            v.abs() <= half_width * (length - u) / head
+        } else {
+            false
+        };
+
+        if inside { ARROW_DEPTH } else { 0.0 }
+    }
+
+    /// Final position of a profile point: the wall between bore and outer
+    /// surface is scaled to meet the feature outline, and engravings lower
+    /// the top face. Scaling keeps every profile point distinct, so the
+    /// revolved mesh stays closed.
+    pub fn place(&self, point: &ProfilePoint, angle: f32, inner_radius: f32, outer_radius: f32) -> Point3<f32> {
+        let limit = self.outer_radius_at(angle, outer_radius);
+        let r = inner_radius + (point.r - inner_radius) * (limit - inner_radius) / (outer_radius - inner_radius);
+        let z = if point.surface == Surface::Top {
+            point.z - self.engraving_depth(point.r, angle, inner_radius, outer_radius)
+        } else {
+            point.z
+        };
+        Point3::new(r * angle.cos(), r * angle.sin(), z)
+    }
+}
+
+/// Length and half width of an engraved arrow on a wall from `inner_radius` to `outer_radius`
+fn arrow_size(inner_radius: f32, outer_radius: f32) -> (f32, f32) {
+    let length = 0.6 * (outer_radius - inner_radius);
+    (length, (0.3 * length).min(2.0))
+}
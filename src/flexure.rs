@@ -0,0 +1,193 @@
+use anyhow::Result;
+use std::f32::consts::PI;
+use stl_io::Triangle;
+
+use crate::geometry::RingParameters;
+use crate::mesh::{extrude_cells, merge_angles, uniform_angles};
+
+/// Young's modulus of printed PLA, in MPa
+const PRINT_MODULUS: f32 = 3500.0;
+
+/// Bending stress printed PLA holds long-term without creeping, in MPa
+const ALLOWABLE_STRESS: f32 = 20.0;
+
+/// Friction between a lens edge and the finger pads
+const PAD_FRICTION: f32 = 0.3;
+
+/// Radial gap between the lens and the bore away from the pads, in millimeters
+const BORE_CLEARANCE: f32 = 0.5;
+
+/// Width of the slots that free each finger, in millimeters
+const SLOT_WIDTH: f32 = 1.0;
+
+/// Length of the contact pad at each finger tip, in millimeters
+const PAD_LENGTH: f32 = 2.0;
+
+/// Solid wall left between neighboring fingers, in millimeters
+const MIN_ROOT_LENGTH: f32 = 2.0;
+
+/// Angular segments for flexure mounts
+const FLEXURE_SEGMENTS: usize = 180;
+
+/// Radial flexure fingers that grip the lens edge.
+///
+/// Each finger is a cantilever cut from the wall by an arc slot behind it,
+/// with a pad at its free tip that reaches `preload` inside the lens edge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlexureSpec {
+    pub fingers: usize,
+    /// Beam length along the bore, from root to tip
+    pub beam_length: f32,
+    /// Radial thickness of each beam
+    pub beam_thickness: f32,
+    /// Radial interference between the pads and the lens edge
+    pub preload: f32,
+    /// Angle of the first finger root in degrees
+    pub angle: f32,
+}
+
+impl Default for FlexureSpec {
+    fn default() -> Self {
+        Self {
+            fingers: 3,
+            beam_length: 10.0,
+            beam_thickness: 1.2,
+            preload: 0.1,
+            angle: 0.0,
+        }
+    }
+}
+
+/// Estimated mechanics of a flexure mount, from cantilever beam theory
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlexureReport {
+    /// Radial stiffness of one finger at its pad, in N/mm
+    pub finger_stiffness: f32,
+    /// Radial force of one finger on the lens edge, in N
+    pub preload_force: f32,
+    /// Stiffness of the lens against sideways decenter, in N/mm
+    pub lateral_stiffness: f32,
+    /// Heaviest lens the pads hold along the axis by friction, in grams
+    pub holding_mass: f32,
+    /// Peak bending stress at the finger roots, in MPa
+    pub root_stress: f32,
+}
+
+/// Radii of the flexure grid: pad face, bore, beam back, slot back, outer wall
+fn radii(spec: &FlexureSpec, params: &RingParameters) -> [f32; 5] {
+    let lens_radius = params.inner_diameter / 2.0;
+    let bore = lens_radius + BORE_CLEARANCE;
+    [
+        lens_radius - spec.preload,
+        bore,
+        bore + spec.beam_thickness,
+        bore + spec.beam_thickness + SLOT_WIDTH,
+        params.outer_diameter / 2.0,
+    ]
+}
+
+impl FlexureSpec {
+    /// Radius of the beam's neutral axis
+    fn beam_radius(&self, params: &RingParameters) -> f32 {
+        let [_, bore, back, ..] = radii(self, params);
+        (bore + back) / 2.0
+    }
+
+    /// Root, tip and slot end angles of each finger, in radians
+    fn finger_angles(&self, params: &RingParameters) -> Vec<(f32, f32, f32)> {
+        let radius = self.beam_radius(params);
+        (0..self.fingers).map(|k| {
+            let root = self.angle.to_radians() + 2.0 * PI * k as f32 / self.fingers as f32;
+            let tip = root + self.beam_length / radius;
+            (root, tip, tip + SLOT_WIDTH / radius)
+        }).collect()
+    }
+
+    pub fn report(&self, params: &RingParameters) -> FlexureReport {
+        let (length, thickness, depth) = (self.beam_length, self.beam_thickness, params.height);
+        let finger_stiffness = PRINT_MODULUS * depth * thickness.powi(3) / (4.0 * length.powi(3));
+        let preload_force = finger_stiffness * self.preload;
+        FlexureReport {
+            finger_stiffness,
+            preload_force,
+            lateral_stiffness: self.fingers as f32 * finger_stiffness / 2.0,
+            holding_mass: PAD_FRICTION * self.fingers as f32 * preload_force / 9.81 * 1000.0,
+            root_stress: 6.0 * preload_force * length / (depth * thickness.powi(2)),
+        }
+    }
+
+    pub fn validate(&self, params: &RingParameters) -> Result<()> {
+        if self.fingers < 3 {
+            return Err(anyhow::anyhow!("Flexure mounts need at least 3 fingers to center the lens"));
+        }
+        if self.beam_length <= 0.0 || self.preload <= 0.0 {
+            return Err(anyhow::anyhow!("Finger length and preload must be positive"));
+        }
+        if self.beam_thickness < 0.6 {
+            return Err(anyhow::anyhow!(
+                "Fingers {:.2}mm thick are too thin to print. Minimum: 0.6mm",
+                self.beam_thickness
+            ));
+        }
+        if self.beam_length < PAD_LENGTH + self.beam_thickness {
+            return Err(anyhow::anyhow!("Fingers must be longer than their {:.1}mm tip pads", PAD_LENGTH));
+        }
+
+        let [.., slot_back, outer] = radii(self, params);
+        if outer - slot_back < 1.0 {
+            return Err(anyhow::anyhow!(
+                "Flexure fingers need an outer diameter of at least {:.2}mm for a 1.0mm wall behind the slots",
+                2.0 * (slot_back + 1.0)
+            ));
+        }
+
+        let sector = 2.0 * PI * self.beam_radius(params) / self.fingers as f32;
+        let needed = self.beam_length + SLOT_WIDTH + MIN_ROOT_LENGTH;
+        if needed > sector {
+            return Err(anyhow::anyhow!(
+                "{} fingers {:.2}mm long don't fit around the lens; each has {:.2}mm including slot and root",
+                self.fingers, self.beam_length, sector - SLOT_WIDTH - MIN_ROOT_LENGTH
+            ));
+        }
+
+        let report = self.report(params);
+        if report.root_stress > ALLOWABLE_STRESS {
+            return Err(anyhow::anyhow!(
+                "A {:.2}mm preload stresses the finger roots to {:.1}MPa, above the {:.0}MPa printed plastic holds without creeping. Use longer or thinner fingers, or less preload",
+                self.preload, report.root_stress, ALLOWABLE_STRESS
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Generate a flexure mount: a prismatic ring whose fingers grip the lens edge.
+/// `inner_diameter` is the lens diameter.
+pub fn generate_flexure_mount(params: &RingParameters) -> Result<Vec<Triangle>> {
+    let spec = params.flexure.clone().unwrap_or_default();
+    let radii = radii(&spec, params);
+    let fingers = spec.finger_angles(params);
+    let pad = PAD_LENGTH / spec.beam_radius(params);
+
+    let breaks = fingers.iter().flat_map(|&(root, tip, end)| [root, tip - pad, tip, end]);
+    let angles = merge_angles(uniform_angles(FLEXURE_SEGMENTS), breaks);
+
+    // Which part of a finger, if any, the angle lies in
+    let within = |angle: f32, from: f32, to: f32| (angle - from).rem_euclid(2.0 * PI) < (to - from) - 1e-5;
+    let mesh = extrude_cells(&angles, &radii, params.height, |i, j| {
+        let next = angles[(i + 1) % angles.len()];
+        let previous = angles[i];
+        let middle = previous + (next - previous).rem_euclid(2.0 * PI) / 2.0;
+        let on_beam = fingers.iter().any(|&(root, tip, _)| within(middle, root, tip));
+        let on_pad = fingers.iter().any(|&(_, tip, _)| within(middle, tip - pad, tip));
+        let on_slot = fingers.iter().any(|&(root, _, end)| within(middle, root, end));
+        match j {
+            0 => on_pad,
+            1 => on_beam || !on_slot,
+            2 => !on_slot,
+            _ => true,
+        }
+    });
+
+    Ok(mesh.into_triangles())
+}
@@ -1,13 +1,59 @@
 use anyhow::Result;
-use nalgebra::{Point3, Vector3};
+use std::f32::consts::PI;
 use stl_io::Triangle;
 
-/// Represents the three types of support rings
+use crate::aperture::ApertureSpec;
+use crate::asphere::AsphereSpec;
+use crate::bayonet::BayonetSpec;
+use crate::cone::ConeSpec;
+use crate::corner::CornerSpec;
+use crate::eccentric::EccentricSpec;
+use crate::features::{angle_between, OrientationFeatures};
+use crate::flexure::{generate_flexure_mount, FlexureSpec};
+use crate::grip::GripSpec;
+use crate::holes::{cut_holes, HoleSpec, MIN_HOLE_WALL};
+use crate::kinematic::KinematicSpec;
+use crate::label::{LabelSpec, RingLabel};
+use crate::vent::VentSpec;
+use crate::wedge::WedgeSpec;
+use crate::tangent::{TangentSpec, ToroidSection};
+use crate::mesh::{merge_angles, revolve, subdivide, uniform_angles, ProfilePoint, Surface};
+use crate::naming::{render_filename, NameOptions};
+use crate::thread::{generate_retaining_ring, ThreadSide, ThreadSpec};
+
+/// Represents the types of support rings and retainers
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum RingType {
     Convex,   // CX
     Concave,  // CC
     ThreePoint, // 3P
+    RetainingRing, // RR
+    Spacer,   // SP
+    Flexure,  // FX
+    Tangent,  // TG
+    SharpCorner, // SC
+    Conical,  // CN
+    Saddle,   // SD, rectangular cradle for a cylindrical lens (--saddle)
+    Profile,  // PR, user-defined cross-section (--profile)
+}
+
+impl RingType {
+    /// Human-readable name, e.g. "Convex"
+    pub fn name(&self) -> &'static str {
+        match self {
+            RingType::Convex => "Convex",
+            RingType::Concave => "Concave",
+            RingType::ThreePoint => "Three-point",
+            RingType::RetainingRing => "Retaining ring",
+            RingType::Spacer => "Spacer",
+            RingType::Flexure => "Flexure mount",
+            RingType::Tangent => "Tangent",
+            RingType::SharpCorner => "Sharp corner",
+            RingType::Conical => "Conical",
+            RingType::Saddle => "Saddle",
+            RingType::Profile => "Custom profile",
+        }
+    }
 }
 
 impl std::fmt::Display for RingType {
@@ -16,6 +62,14 @@ impl std::fmt::Display for RingType {
             RingType::Convex => write!(f, "CX"),
             RingType::Concave => write!(f, "CC"),
             RingType::ThreePoint => write!(f, "3P"),
+            RingType::RetainingRing => write!(f, "RR"),
+            RingType::Spacer => write!(f, "SP"),
+            RingType::Flexure => write!(f, "FX"),
+            RingType::Tangent => write!(f, "TG"),
+            RingType::SharpCorner => write!(f, "SC"),
+            RingType::Conical => write!(f, "CN"),
+            RingType::Saddle => write!(f, "SD"),
+            RingType::Profile => write!(f, "PR"),
         }
     }
 }
@@ -28,7 +82,15 @@ impl std::str::FromStr for RingType {
             "CX" | "CONVEX" => Ok(RingType::Convex),
             "CC" | "CONCAVE" => Ok(RingType::Concave),
             "3P" | "THREEPOINT" | "THREE-POINT" => Ok(RingType::ThreePoint),
-            _ => Err(anyhow::anyhow!("Invalid ring type: {}. Valid types are: CX, CC, 3P", s)),
+            "RR" | "RETAINER" | "RETAINING-RING" => Ok(RingType::RetainingRing),
+            "SP" | "SPACER" => Ok(RingType::Spacer),
+            "FX" | "FLEXURE" => Ok(RingType::Flexure),
+            "TG" | "TANGENT" | "TOROIDAL" => Ok(RingType::Tangent),
+            "SC" | "SHARP" | "SHARP-CORNER" => Ok(RingType::SharpCorner),
+            "CN" | "CONE" | "CONICAL" => Ok(RingType::Conical),
+            "SD" | "SADDLE" => Ok(RingType::Saddle),
+            "PR" | "PROFILE" => Ok(RingType::Profile),
+            _ => Err(anyhow::anyhow!("Invalid ring type: {}. Valid types are: CX, CC, 3P, RR, SP, FX, TG, SC, CN, SD, PR", s)),
         }
     }
 }
@@ -40,6 +102,39 @@ pub struct RingParameters {
     pub outer_diameter: f32,
     pub inner_diameter: f32,
     pub height: f32,
+    pub naming: NameOptions,
+    /// Thread cut into the outer wall or bore (retaining rings)
+    pub thread: Option<ThreadSpec>,
+    /// Keys, flats and index marks
+    pub features: OrientationFeatures,
+    /// Text embossed or engraved on the ring
+    pub label: Option<LabelSpec>,
+    /// Air-escape slots and grooves
+    pub vents: Option<VentSpec>,
+    /// Radial screw holes through the wall
+    pub holes: Option<HoleSpec>,
+    /// Finger layout (flexure mounts)
+    pub flexure: Option<FlexureSpec>,
+    /// Twist-lock lugs on the outer wall
+    pub bayonet: Option<BayonetSpec>,
+    /// Knurl, flutes or wrench flats on the outer wall
+    pub grip: Option<GripSpec>,
+    /// Lens surface the seat touches tangentially (tangent rings)
+    pub tangent: Option<TangentSpec>,
+    /// Lens surface resting on the bore corner (sharp-corner rings)
+    pub corner: Option<CornerSpec>,
+    /// Cone angle and contact diameter (conical rings)
+    pub cone: Option<ConeSpec>,
+    /// Rectangular or elliptical aperture; round when unset
+    pub aperture: Option<ApertureSpec>,
+    /// Tilt of the seat relative to the base
+    pub wedge: Option<WedgeSpec>,
+    /// Offset of the outer wall from the bore, for centering adjustment
+    pub eccentric: Option<EccentricSpec>,
+    /// V-grooves in the pads for a kinematic ball mount (three-point rings)
+    pub kinematic: Option<KinematicSpec>,
+    /// Aspheric lens surface a CX or CC seat follows
+    pub asphere: Option<AsphereSpec>,
 }
 
 impl RingParameters {
@@ -60,232 +155,612 @@ impl RingParameters {
             outer_diameter,
             inner_diameter,
             height,
+            naming: NameOptions::default(),
+            thread: None,
+            features: OrientationFeatures::default(),
+            label: None,
+            vents: None,
+            holes: None,
+            flexure: (ring_type == RingType::Flexure).then(FlexureSpec::default),
+            bayonet: None,
+            grip: None,
+            tangent: None,
+            corner: None,
+            cone: (ring_type == RingType::Conical).then(ConeSpec::default),
+            aperture: None,
+            wedge: None,
+            eccentric: None,
+            kinematic: None,
+            asphere: None,
         })
     }
 
+    /// Add a thread. The threaded wall takes its diameter from the thread:
+    /// the outer diameter for external threads, the bore (minor diameter)
+    /// for internal threads.
+    pub fn with_thread(mut self, thread: ThreadSpec) -> Result<Self> {
+        match thread.side {
+            ThreadSide::External => self.outer_diameter = thread.major_diameter,
+            ThreadSide::Internal => self.inner_diameter = thread.minor_diameter(),
+        }
+        if self.outer_diameter <= self.inner_diameter {
+            return Err(anyhow::anyhow!(
+                "Thread {} leaves no wall: outer diameter {:.2}mm, inner diameter {:.2}mm",
+                thread, self.outer_diameter, self.inner_diameter
+            ));
+        }
+
+        // At least three full turns so the ring engages reliably
+        self.height = self.height.max(3.0 * thread.pitch);
+        self.thread = Some(thread);
+        Ok(self)
+    }
+
+    /// Output file name, rendered from the filename template in `naming`
     pub fn filename(&self) -> String {
-        format!("{}-{:.1}.stl", self.ring_type, self.inner_diameter)
+        render_filename(self)
     }
 }
 
 /// Generate STL triangles for a support ring
 pub fn generate_ring_mesh(params: &RingParameters) -> Result<Vec<Triangle>> {
+    validate_aperture(params)?;
     match params.ring_type {
-        RingType::Convex => generate_convex_ring(params),
-        RingType::Concave => generate_concave_ring(params),
-        RingType::ThreePoint => generate_three_point_ring(params),
+        RingType::Convex | RingType::Concave | RingType::ThreePoint | RingType::Spacer | RingType::Tangent
+        | RingType::SharpCorner | RingType::Conical => {
+            generate_seat_ring(params)
+        }
+        RingType::Flexure if params.grip.is_some() => {
+            Err(anyhow::anyhow!("Grip surfaces are not supported on flexure mounts"))
+        }
+        RingType::RetainingRing | RingType::Flexure => {
+            if !params.features.is_empty()
+                || params.label.is_some()
+                || params.vents.is_some()
+                || params.holes.is_some()
+                || params.bayonet.is_some()
+                || params.wedge.is_some()
+                || params.eccentric.is_some()
+            {
+                return Err(anyhow::anyhow!(
+                    "Keys, flats, index marks, labels, vents, holes, bayonets, wedges and eccentric offsets are not supported on {}s",
+                    params.ring_type.name().to_lowercase()
+                ));
+            }
+            match params.ring_type {
+                RingType::Flexure => generate_flexure_mount(params),
+                _ => generate_retaining_ring(params),
+            }
+        }
+        RingType::Saddle => Err(anyhow::anyhow!("Saddles are rectangular frames, not rings. Generate them with --saddle")),
+        RingType::Profile => Err(anyhow::anyhow!("Custom profiles are revolved from a profile file. Generate them with --profile <FILE>")),
     }
 }
 
-fn generate_convex_ring(params: &RingParameters) -> Result<Vec<Triangle>> {
-    let mut triangles = Vec::new();
-    let segments = 64; // Number of segments for smooth curves
-    
-    let outer_radius = params.outer_diameter / 2.0;
-    let inner_radius = params.inner_diameter / 2.0;
-    let height = params.height;
-    
-    // Generate convex surface (curved inward)
-    let curve_depth = (outer_radius - inner_radius) * 0.3; // 30% of wall thickness
-    
-    for i in 0..segments {
-        let angle1 = 2.0 * std::f32::consts::PI * i as f32 / segments as f32;
-        let angle2 = 2.0 * std::f32::consts::PI * ((i + 1) % segments) as f32 / segments as f32;
-        
-        // Generate curved support surface
-        add_convex_segment(&mut triangles, angle1, angle2, inner_radius, outer_radius, height, curve_depth);
-    }
-    
-    // Add base and top rings
-    add_ring_base(&mut triangles, inner_radius, outer_radius, 0.0, segments);
-    add_ring_top(&mut triangles, inner_radius, outer_radius, height, segments);
-    
-    Ok(triangles)
-}
-
-fn generate_concave_ring(params: &RingParameters) -> Result<Vec<Triangle>> {
-    let mut triangles = Vec::new();
-    let segments = 64;
-    
+/// Depth of the CX/CC seat curve as a fraction of the radial seat width
+pub const SEAT_CURVE_FRACTION: f32 = 0.3;
+
+/// Angular segments for seat rings
+pub const RING_SEGMENTS: usize = 128;
+
+/// Radial steps across a curved seat
+const SEAT_STEPS: usize = 16;
+
+/// Steps around the relief radius of a sharp-corner seat
+const RELIEF_STEPS: usize = 8;
+
+/// Half width of each three-point contact pad, in radians (15°)
+const CONTACT_HALF_WIDTH: f32 = PI / 12.0;
+
+/// Height of a three-point ring between its pads, as a fraction of full height
+const CONTACT_RELIEF: f32 = 0.6;
+
+/// Axial offset of a CX or CC seat surface toward the lens at `t` across the
+/// seat (0 at the bore, 1 at the outer edge). Convex seats bulge toward the
+/// lens, concave seats dip away from it; other ring types have flat seats.
+pub fn seat_offset(ring_type: RingType, t: f32, curve_depth: f32) -> f32 {
+    let bulge = curve_depth * (1.0 - (2.0 * t - 1.0).powi(2));
+    match ring_type {
+        RingType::Convex => bulge,
+        RingType::Concave => -bulge,
+        _ => 0.0,
+    }
+}
+
+/// Angles of the three-point contacts, in radians, starting at the clocking angle
+pub fn contact_angles(params: &RingParameters) -> [f32; 3] {
+    let first = params.features.clocking.to_radians();
+    [first, first + 2.0 * PI / 3.0, first + 4.0 * PI / 3.0]
+}
+
+/// Torus section of a tangent ring's seat, if it has one
+fn toroid_section(params: &RingParameters) -> Option<ToroidSection> {
+    match params.tangent {
+        Some(ref tangent) if params.ring_type == RingType::Tangent => {
+            Some(tangent.section(params.inner_diameter / 2.0, params.outer_diameter / 2.0, params.height))
+        }
+        _ => None,
+    }
+}
+
+/// Relief radius rounding the bore corner of a sharp-corner ring
+fn corner_relief(params: &RingParameters) -> f32 {
+    match params.corner {
+        Some(ref corner) if params.ring_type == RingType::SharpCorner => corner.relief,
+        _ => 0.0,
+    }
+}
+
+/// Cone of a conical ring's seat, if it has one
+fn cone_seat(params: &RingParameters) -> Option<&ConeSpec> {
+    params.cone.as_ref().filter(|_| params.ring_type == RingType::Conical)
+}
+
+/// Aspheric surface of a CX or CC ring's seat, if it has one
+fn asphere_seat(params: &RingParameters) -> Option<&AsphereSpec> {
+    params.asphere.as_ref().filter(|_| matches!(params.ring_type, RingType::Convex | RingType::Concave))
+}
+
+/// Height of the top corners of the bore and outer wall. Convex seats are
+/// lowered so their peak sits at `height`; tangent seats peak at the contact,
+/// a relieved sharp corner's bore ends where the rounding meets it (its outer
+/// wall still reaches `height`), cones are lowest at the bore, and aspheric
+/// seats have their higher edge at `height`.
+fn wall_top(params: &RingParameters) -> f32 {
+    let (inner_radius, outer_radius) = (params.inner_diameter / 2.0, params.outer_diameter / 2.0);
+    let curve_depth = (outer_radius - inner_radius) * SEAT_CURVE_FRACTION;
+    if let Some(asphere) = asphere_seat(params) {
+        return params.height - asphere.drop(inner_radius, outer_radius);
+    }
+    match (params.ring_type, toroid_section(params)) {
+        (RingType::Convex, _) => params.height - curve_depth,
+        (RingType::Tangent, Some(section)) => section.height_at(inner_radius).min(section.height_at(outer_radius)),
+        (RingType::SharpCorner, _) => params.height - corner_relief(params),
+        (RingType::Conical, _) => cone_seat(params).map_or(params.height, |cone| {
+            cone.height_at(inner_radius, inner_radius, outer_radius, params.height)
+        }),
+        _ => params.height,
+    }
+}
+
+/// Height of the untilted seat face at `r` from the axis
+fn seat_surface(params: &RingParameters, r: f32) -> f32 {
+    let (inner_radius, outer_radius) = (params.inner_diameter / 2.0, params.outer_diameter / 2.0);
+    let curve_depth = (outer_radius - inner_radius) * SEAT_CURVE_FRACTION;
+    match (toroid_section(params), cone_seat(params), asphere_seat(params)) {
+        (Some(section), _, _) => section.height_at(r),
+        (_, Some(cone), _) => cone.height_at(r, inner_radius, outer_radius, params.height),
+        (_, _, Some(asphere)) => asphere.height_at(r, inner_radius, outer_radius, params.height),
+        _ if params.ring_type == RingType::SharpCorner => {
+            // Flat at `height`, rounding down to the bore within the relief radius
+            let relief = corner_relief(params);
+            let inset = (inner_radius + relief - r).clamp(0.0, relief);
+            params.height - relief + (relief * relief - inset * inset).sqrt()
+        }
+        _ => wall_top(params) + seat_offset(params.ring_type, (r - inner_radius) / (outer_radius - inner_radius), curve_depth),
+    }
+}
+
+/// How far a wedge lowers the seat at the outer edge, on its low side
+fn wedge_drop(params: &RingParameters) -> f32 {
+    params.wedge.as_ref().map_or(0.0, |wedge| params.outer_diameter / 2.0 * wedge.angle.to_radians().tan())
+}
+
+/// Height of the lowest point of the seat face, which slots and holes must stay below
+fn seat_height(params: &RingParameters) -> f32 {
+    let curve_depth = (params.outer_diameter - params.inner_diameter) / 2.0 * SEAT_CURVE_FRACTION;
+    let height = match params.ring_type {
+        RingType::Convex | RingType::Concave => match asphere_seat(params) {
+            Some(_) => wall_top(params),
+            None => params.height - curve_depth,
+        },
+        RingType::ThreePoint => params.kinematic.as_ref()
+            .map_or(params.height * CONTACT_RELIEF, |kinematic| (params.height - kinematic.depth()).min(params.height * CONTACT_RELIEF)),
+        RingType::Tangent | RingType::SharpCorner | RingType::Conical => wall_top(params),
+        _ => params.height,
+    };
+    height - wedge_drop(params)
+}
+
+/// Highest and lowest points of a wedged seat around the ring
+pub fn wedge_heights(params: &RingParameters) -> Option<(f32, f32)> {
+    let wedge = params.wedge.as_ref()?;
+    let (inner_radius, outer_radius) = (params.inner_diameter / 2.0, params.outer_diameter / 2.0);
+    let heights: Vec<f32> = uniform_angles(360).into_iter().flat_map(|angle| {
+        (0..=SEAT_STEPS).map(move |k| {
+            let r = inner_radius + (outer_radius - inner_radius) * k as f32 / SEAT_STEPS as f32;
+            wedge.tilted_height(r * angle.cos(), r * angle.sin(), params.height, |r| seat_surface(params, r))
+        })
+    }).collect();
+    Some((heights.iter().copied().fold(f32::MIN, f32::max), heights.iter().copied().fold(f32::MAX, f32::min)))
+}
+
+/// Check that a wedged seat stays above the base all round
+pub fn validate_wedge(params: &RingParameters) -> Result<()> {
+    let wedge = match params.wedge {
+        Some(ref wedge) => wedge,
+        None => return Ok(()),
+    };
+    wedge.validate()?;
+    if let Some((_, lowest)) = wedge_heights(params) {
+        if lowest < 1.0 {
+            return Err(anyhow::anyhow!(
+                "The {:.2}° wedge drops the seat to {:.2}mm on its low side. Raise --height to at least {:.2}mm",
+                wedge.angle, lowest, params.height + 1.0 - lowest
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Check that an eccentric ring's thin side, and any features cut into it, stay printable
+pub fn validate_eccentric(params: &RingParameters) -> Result<()> {
+    let eccentric = match params.eccentric {
+        Some(ref eccentric) => eccentric,
+        None => return Ok(()),
+    };
+    let (inner_radius, outer_radius) = (params.inner_diameter / 2.0, params.outer_diameter / 2.0);
+    eccentric.validate(inner_radius, outer_radius)?;
+    params.features.validate(inner_radius, outer_radius - eccentric.offset)
+}
+
+/// Check that a kinematic ring's grooves fit its pads and its balls fit the wall
+pub fn validate_kinematic(params: &RingParameters) -> Result<()> {
+    let kinematic = match params.kinematic {
+        Some(ref kinematic) => kinematic,
+        None => return Ok(()),
+    };
+    if params.ring_type != RingType::ThreePoint {
+        return Err(anyhow::anyhow!("V-grooves are cut into three-point pads. Use --ring-type 3p"));
+    }
+    if params.wedge.is_some() {
+        return Err(anyhow::anyhow!("V-groove rings can't be wedged"));
+    }
+    let (inner_radius, outer_radius) = (params.inner_diameter / 2.0, params.outer_diameter / 2.0);
+    kinematic.validate(inner_radius, outer_radius, params.height)?;
+    if kinematic.half_width() > inner_radius * CONTACT_HALF_WIDTH.sin() {
+        return Err(anyhow::anyhow!(
+            "{:.2}mm wide grooves for {:.2}mm balls don't fit the pads at the {:.2}mm bore. Use smaller balls or a larger bore",
+            2.0 * kinematic.half_width(), kinematic.ball_diameter, params.inner_diameter
+        ));
+    }
+    Ok(())
+}
+
+/// Check that an aspheric seat is defined, slopes one way and fits the ring's height
+pub fn validate_asphere(params: &RingParameters) -> Result<()> {
+    let asphere = match params.asphere {
+        Some(ref asphere) => asphere,
+        None => return Ok(()),
+    };
+    if !matches!(params.ring_type, RingType::Convex | RingType::Concave) {
+        return Err(anyhow::anyhow!("Aspheric seats are CX or CC seats. Use --ring-type cx or cc"));
+    }
+    asphere.validate(params.inner_diameter / 2.0, params.outer_diameter / 2.0, params.height)
+}
+
+/// Check that a tangent ring's toroid touches the lens on the seat
+pub fn validate_tangent(params: &RingParameters) -> Result<()> {
+    if params.ring_type != RingType::Tangent {
+        return Ok(());
+    }
+    match params.tangent {
+        Some(ref tangent) => tangent.validate(params.inner_diameter / 2.0, params.outer_diameter / 2.0, params.height),
+        None => Err(anyhow::anyhow!("Tangent rings need the lens surface radius. Use --lens-radius <MM>")),
+    }
+}
+
+/// Check that a conical ring's cone stays above the base and the lens lands on it
+pub fn validate_cone(params: &RingParameters) -> Result<()> {
+    match cone_seat(params) {
+        Some(cone) => cone.validate(params.inner_diameter / 2.0, params.outer_diameter / 2.0, params.height),
+        None => Ok(()),
+    }
+}
+
+/// Check that a rectangular or elliptical frame has a seat it supports and no round-only features
+pub fn validate_aperture(params: &RingParameters) -> Result<()> {
+    let aperture = match params.aperture {
+        Some(ref aperture) => aperture,
+        None => return Ok(()),
+    };
+    // Saddles are rectangular frames by construction
+    if !matches!(
+        params.ring_type,
+        RingType::Convex | RingType::Concave | RingType::ThreePoint | RingType::Spacer | RingType::Saddle
+    ) {
+        return Err(anyhow::anyhow!(
+            "{} rings need a round aperture; {} frames take CX, CC, 3P and SP seats",
+            params.ring_type.name(), aperture.shape
+        ));
+    }
+    if !params.features.is_empty()
+        || params.label.is_some()
+        || params.vents.is_some()
+        || params.holes.is_some()
+        || params.bayonet.is_some()
+        || params.grip.is_some()
+        || params.wedge.is_some()
+        || params.eccentric.is_some()
+        || params.kinematic.is_some()
+    {
+        return Err(anyhow::anyhow!(
+            "Keys, flats, index marks, labels, vents, holes, bayonets, grips, wedges, eccentric offsets and V-grooves are not supported on {} frames",
+            aperture.shape
+        ));
+    }
+    aperture.validate(params.inner_diameter / 2.0)
+}
+
+/// Check that a sharp-corner ring's lens rests on the bore corner
+pub fn validate_corner(params: &RingParameters) -> Result<()> {
+    if params.ring_type != RingType::SharpCorner {
+        return Ok(());
+    }
+    match params.corner {
+        Some(ref corner) => corner.validate(params.inner_diameter / 2.0, params.outer_diameter / 2.0, params.height),
+        None => Err(anyhow::anyhow!("Sharp-corner rings need the lens surface radius. Use --lens-radius <MM>")),
+    }
+}
+
+/// Radius where the lens bears on the seat; mid-wall unless the seat places it
+fn contact_radius(params: &RingParameters) -> f32 {
+    let (inner_radius, outer_radius) = (params.inner_diameter / 2.0, params.outer_diameter / 2.0);
+    if let Some(section) = toroid_section(params) {
+        return section.contact_r;
+    }
+    if let Some(cone) = cone_seat(params) {
+        return cone.landing(inner_radius, outer_radius, params.height)
+            .map_or(cone.contact_radius(inner_radius, outer_radius), |landing| landing.contact_r);
+    }
+    match params.corner {
+        Some(ref corner) if params.ring_type == RingType::SharpCorner => corner.contact(inner_radius, params.height).contact_r,
+        _ => (inner_radius + outer_radius) / 2.0,
+    }
+}
+
+/// Check that vents leave the seat printable and its contact annulus intact
+pub fn validate_vents(params: &RingParameters) -> Result<()> {
+    let vents = match params.vents {
+        Some(ref vents) => vents,
+        None => return Ok(()),
+    };
+    if params.ring_type == RingType::SharpCorner && vents.axial() {
+        return Err(anyhow::anyhow!(
+            "Vent grooves along the bore would notch the corner the lens rests on. Use --vents radial on sharp-corner rings"
+        ));
+    }
     let outer_radius = params.outer_diameter / 2.0;
     let inner_radius = params.inner_diameter / 2.0;
-    let height = params.height;
-    
-    // Generate concave surface (curved outward)
-    let curve_depth = (outer_radius - inner_radius) * 0.3;
-    
-    for i in 0..segments {
-        let angle1 = 2.0 * std::f32::consts::PI * i as f32 / segments as f32;
-        let angle2 = 2.0 * std::f32::consts::PI * ((i + 1) % segments) as f32 / segments as f32;
-        
-        add_concave_segment(&mut triangles, angle1, angle2, inner_radius, outer_radius, height, curve_depth);
-    }
-    
-    add_ring_base(&mut triangles, inner_radius, outer_radius, 0.0, segments);
-    add_ring_top(&mut triangles, inner_radius, outer_radius, height, segments);
-    
-    Ok(triangles)
-}
-
-fn generate_three_point_ring(params: &RingParameters) -> Result<Vec<Triangle>> {
-    let mut triangles = Vec::new();
-    let segments = 64;
-    
+    let pads: Vec<(f32, f32)> = match params.ring_type {
+        RingType::ThreePoint => contact_angles(params).iter().map(|&angle| (angle, CONTACT_HALF_WIDTH)).collect(),
+        _ => Vec::new(),
+    };
+
+    vents.validate(
+        inner_radius,
+        outer_radius,
+        seat_height(params),
+        contact_radius(params),
+        &pads,
+        params.features.clocking,
+    )
+}
+
+/// Check the wall left around each radial hole
+pub fn validate_holes(params: &RingParameters) -> Result<()> {
+    match params.holes {
+        Some(ref holes) => holes.validate(
+            params.inner_diameter / 2.0,
+            params.outer_diameter / 2.0,
+            seat_height(params),
+            params.features.clocking,
+        ),
+        None => Ok(()),
+    }
+}
+
+/// Check that a grip pattern leaves a printable wall
+pub fn validate_grip(params: &RingParameters) -> Result<()> {
+    let grip = match params.grip {
+        Some(ref grip) => grip,
+        None => return Ok(()),
+    };
+    let inner_radius = match params.thread {
+        Some(ref thread) if thread.side == ThreadSide::External => {
+            return Err(anyhow::anyhow!("Externally threaded retaining rings have no plain outer wall to grip"));
+        }
+        Some(ref thread) => thread.major_diameter / 2.0 + thread.clearance,
+        None => params.inner_diameter / 2.0,
+    };
+    grip.validate(inner_radius, params.outer_diameter / 2.0, wall_top(params))
+}
+
+/// Check that bayonet lugs fit under the seat and their socket closes around them
+pub fn validate_bayonet(params: &RingParameters) -> Result<()> {
+    match params.bayonet {
+        Some(ref bayonet) => bayonet.validate(params.inner_diameter / 2.0, params.outer_diameter / 2.0, seat_height(params)),
+        None => Ok(()),
+    }
+}
+
+/// Generate a CX, CC, 3P, TG, SC, CN or spacer ring as a closed solid.
+///
+/// The seat is on the top face; the base is flat so the ring prints without
+/// supports. Convex seats peak and concave seats meet their edges at `height`;
+/// tangent and conical seats reach `height` on the contact diameter.
+fn generate_seat_ring(params: &RingParameters) -> Result<Vec<Triangle>> {
+    validate_tangent(params)?;
+    validate_corner(params)?;
+    validate_cone(params)?;
+    validate_asphere(params)?;
+    validate_wedge(params)?;
+    validate_kinematic(params)?;
     let outer_radius = params.outer_diameter / 2.0;
     let inner_radius = params.inner_diameter / 2.0;
     let height = params.height;
-    
-    // Generate three contact points at 120-degree intervals
-    let contact_angles = [0.0, 2.0 * std::f32::consts::PI / 3.0, 4.0 * std::f32::consts::PI / 3.0];
-    let contact_width = std::f32::consts::PI / 12.0; // 15 degrees each
-    
-    for i in 0..segments {
-        let angle1 = 2.0 * std::f32::consts::PI * i as f32 / segments as f32;
-        let angle2 = 2.0 * std::f32::consts::PI * ((i + 1) % segments) as f32 / segments as f32;
-        
-        // Check if this segment is near a contact point
-        let is_contact_segment = contact_angles.iter().any(|&contact_angle| {
-            let diff = (angle1 - contact_angle).abs().min((angle1 - contact_angle + 2.0 * std::f32::consts::PI).abs());
-            diff < contact_width
-        });
-        
-        if is_contact_segment {
-            add_contact_segment(&mut triangles, angle1, angle2, inner_radius, outer_radius, height);
+    let features = &params.features;
+    let label = params.label.as_ref().map(|spec| RingLabel::new(spec, params)).transpose()?;
+
+    let seat_base = wall_top(params);
+    let relief = corner_relief(params);
+    let seat_top = |r: f32| seat_surface(params, r);
+    let seat_step = match params.ring_type {
+        RingType::Convex | RingType::Concave | RingType::Tangent => (outer_radius - inner_radius) / SEAT_STEPS as f32,
+        _ => f32::MAX,
+    };
+    let seat_step = [features.top_step(), label.as_ref().and_then(RingLabel::top_step)]
+        .into_iter()
+        .flatten()
+        .fold(seat_step, f32::min);
+    let wall_step = label.as_ref().and_then(RingLabel::wall_step).unwrap_or(f32::MAX);
+
+    // The bore and outer wall share their heights, so holes can join them
+    let clocking = features.clocking;
+    let holes = match params.holes {
+        Some(ref spec) => Some((spec, spec.layout(inner_radius, seat_height(params), clocking)?)),
+        None => None,
+    };
+    let mut heights: Vec<f32> = subdivide(
+        ProfilePoint::new(outer_radius, 0.0, Surface::Outer),
+        ProfilePoint::new(outer_radius, seat_base, Surface::Outer),
+        wall_step,
+        Surface::Outer,
+    ).iter().map(|point| point.z).chain([seat_base]).collect();
+    if let Some((spec, _)) = holes {
+        heights.extend(spec.height_breaks(inner_radius, seat_height(params))?);
+    }
+    if let Some(ref bayonet) = params.bayonet {
+        heights.extend(bayonet.height_breaks());
+    }
+    if let Some(ref grip) = params.grip {
+        heights.extend(grip.height_breaks(outer_radius, seat_base));
+    }
+    heights.sort_by(|a, b| a.total_cmp(b));
+    heights.dedup_by(|a, b| (*a - *b).abs() < 1e-4);
+
+    let top_outer = ProfilePoint::new(outer_radius, seat_base, Surface::Outer);
+    let top_inner = ProfilePoint::new(inner_radius + relief, seat_base, Surface::Bore);
+
+    let mut profile = vec![ProfilePoint::new(inner_radius, 0.0, Surface::Bore)];
+    let walls = heights.len() - 1;
+    profile.extend(heights[..walls].iter().map(|&z| ProfilePoint::new(outer_radius, z, Surface::Outer)));
+    profile.extend(subdivide(top_outer, top_inner, seat_step, Surface::Top).into_iter().map(|point| {
+        ProfilePoint { z: seat_top(point.r), ..point }
+    }));
+    if relief > 0.0 {
+        // Round the bore corner of a sharp-corner seat, from the top face down to the bore
+        profile.extend((0..RELIEF_STEPS).map(|k| {
+            let turn = PI / 2.0 * k as f32 / RELIEF_STEPS as f32;
+            let r = inner_radius + relief * (1.0 - turn.sin());
+            ProfilePoint::new(r, height - relief * (1.0 - turn.cos()), Surface::Top)
+        }));
+    }
+    profile.push(ProfilePoint::new(inner_radius, seat_top(inner_radius), Surface::Bore));
+    profile.extend(heights[1..walls].iter().rev().map(|&z| ProfilePoint::new(inner_radius, z, Surface::Bore)));
+
+    // Three-point rings are relieved between the pads; give the pads crisp edges
+    let contacts = contact_angles(params);
+    let is_three_point = params.ring_type == RingType::ThreePoint;
+    let mut breaks = features.angle_breaks(inner_radius, outer_radius);
+    if let Some(ref label) = label {
+        breaks.extend(label.angle_breaks());
+    }
+    if let Some(ref vents) = params.vents {
+        breaks.extend(vents.angle_breaks(inner_radius, outer_radius, clocking));
+    }
+    if let Some((spec, _)) = holes {
+        breaks.extend(spec.angle_breaks(inner_radius, outer_radius, seat_height(params), clocking)?);
+    }
+    if let Some(ref bayonet) = params.bayonet {
+        breaks.extend(bayonet.angle_breaks(clocking));
+    }
+    if let Some(ref grip) = params.grip {
+        breaks.extend(grip.angle_breaks(outer_radius, clocking));
+    }
+    if let Some(ref aperture) = params.aperture {
+        breaks.extend(aperture.angle_breaks(inner_radius, outer_radius - inner_radius));
+    }
+    if let Some(ref kinematic) = params.kinematic {
+        breaks.extend(kinematic.angle_breaks(&contacts, inner_radius, outer_radius));
+    }
+    if is_three_point {
+        for contact in contacts {
+            breaks.extend([-1.0, 1.0].iter().flat_map(|side| {
+                [contact + side * CONTACT_HALF_WIDTH, contact + side * (CONTACT_HALF_WIDTH + 0.02)]
+            }));
+        }
+    }
+    let angles = merge_angles(uniform_angles(RING_SEGMENTS), breaks);
+
+    // Relief between three-point pads, V-grooves and wedges leave the wall around holes and lugs at full size
+    let relief_start = holes.as_ref()
+        .and_then(|(_, layout)| layout.first())
+        .map_or(0.0, |hole| hole.z + hole.radius + MIN_HOLE_WALL / 2.0)
+        .max(params.bayonet.as_ref().map_or(0.0, BayonetSpec::top));
+    let relieve = |z: f32| {
+        if z <= relief_start {
+            z
         } else {
-            // Add minimal support structure between contact points
-            add_minimal_segment(&mut triangles, angle1, angle2, inner_radius, outer_radius, height);
+            relief_start + (z - relief_start) * (height * CONTACT_RELIEF - relief_start) / (height - relief_start)
         }
+    };
+
+    let place = |point: &ProfilePoint, angle: f32| {
+        let point = match label {
+            Some(ref label) => label.apply(point, angle),
+            None => *point,
+        };
+        let point = match params.vents {
+            Some(ref vents) => vents.apply(&point, angle, inner_radius, outer_radius, seat_base, clocking),
+            None => point,
+        };
+        let point = match params.grip {
+            Some(ref grip) => grip.apply(&point, angle, outer_radius, seat_base, clocking),
+            None => point,
+        };
+        let point = match params.bayonet {
+            Some(ref bayonet) => bayonet.apply_lug(&point, angle, clocking),
+            None => point,
+        };
+        let on_pad = contacts.iter().any(|&contact| angle_between(angle, contact).abs() <= CONTACT_HALF_WIDTH + 1e-4);
+        let point = if is_three_point && !on_pad {
+            ProfilePoint { z: relieve(point.z), ..point }
+        } else {
+            point
+        };
+        let point = match params.kinematic {
+            Some(ref kinematic) => kinematic.cut_groove(&point, angle, &contacts, height, relief_start),
+            None => point,
+        };
+        let point = match holes {
+            Some((spec, _)) => spec.apply_boss(&point, angle, inner_radius, outer_radius, clocking),
+            None => point,
+        };
+        let point = match params.eccentric {
+            Some(ref eccentric) => eccentric.apply(&point, angle, inner_radius, outer_radius),
+            None => point,
+        };
+        let mut placed = match params.aperture {
+            Some(ref aperture) => aperture.place(&point, angle, inner_radius, outer_radius),
+            None => features.place(&point, angle, inner_radius, outer_radius),
+        };
+
+        // Tilt the seat, stretching everything above the fixed wall to follow it
+        if let Some(ref wedge) = params.wedge {
+            if placed.z > relief_start {
+                let level = seat_top(point.r.clamp(inner_radius, outer_radius));
+                let tilted = wedge.tilted_height(placed.x, placed.y, height, seat_top);
+                placed.z = relief_start + (placed.z - relief_start) * (tilted - relief_start) / (level - relief_start);
+            }
+        }
+        placed
+    };
+
+    let mut mesh = revolve(&profile, &angles, place);
+    if let Some((_, ref layout)) = holes {
+        cut_holes(&mut mesh, &profile, &angles, layout, place)?;
     }
-    
-    add_ring_base(&mut triangles, inner_radius, outer_radius, 0.0, segments);
-    add_ring_top(&mut triangles, inner_radius, outer_radius, height, segments);
-    
-    Ok(triangles)
-}
-
-fn add_convex_segment(triangles: &mut Vec<Triangle>, angle1: f32, angle2: f32, 
-                     inner_radius: f32, outer_radius: f32, _height: f32, curve_depth: f32) {
-    // Create curved surface that curves inward (convex from lens perspective)
-    let steps = 8;
-    for step in 0..steps {
-        let t1 = step as f32 / steps as f32;
-        let t2 = (step + 1) as f32 / steps as f32;
-        
-        let r1 = inner_radius + (outer_radius - inner_radius) * t1;
-        let r2 = inner_radius + (outer_radius - inner_radius) * t2;
-        
-        // Curve calculation - convex shape
-        let z_offset1 = curve_depth * (1.0 - (2.0 * t1 - 1.0).powi(2));
-        let z_offset2 = curve_depth * (1.0 - (2.0 * t2 - 1.0).powi(2));
-        
-        let p1 = Point3::new(r1 * angle1.cos(), r1 * angle1.sin(), z_offset1);
-        let p2 = Point3::new(r2 * angle1.cos(), r2 * angle1.sin(), z_offset2);
-        let p3 = Point3::new(r1 * angle2.cos(), r1 * angle2.sin(), z_offset1);
-        let p4 = Point3::new(r2 * angle2.cos(), r2 * angle2.sin(), z_offset2);
-        
-        add_quad_triangles(triangles, p1, p2, p3, p4);
-    }
-}
-
-fn add_concave_segment(triangles: &mut Vec<Triangle>, angle1: f32, angle2: f32,
-                      inner_radius: f32, outer_radius: f32, _height: f32, curve_depth: f32) {
-    // Create curved surface that curves outward (concave from lens perspective)
-    let steps = 8;
-    for step in 0..steps {
-        let t1 = step as f32 / steps as f32;
-        let t2 = (step + 1) as f32 / steps as f32;
-        
-        let r1 = inner_radius + (outer_radius - inner_radius) * t1;
-        let r2 = inner_radius + (outer_radius - inner_radius) * t2;
-        
-        // Curve calculation - concave shape (inverted)
-        let z_offset1 = -curve_depth * (1.0 - (2.0 * t1 - 1.0).powi(2));
-        let z_offset2 = -curve_depth * (1.0 - (2.0 * t2 - 1.0).powi(2));
-        
-        let p1 = Point3::new(r1 * angle1.cos(), r1 * angle1.sin(), z_offset1);
-        let p2 = Point3::new(r2 * angle1.cos(), r2 * angle1.sin(), z_offset2);
-        let p3 = Point3::new(r1 * angle2.cos(), r1 * angle2.sin(), z_offset1);
-        let p4 = Point3::new(r2 * angle2.cos(), r2 * angle2.sin(), z_offset2);
-        
-        add_quad_triangles(triangles, p1, p2, p3, p4);
-    }
-}
-
-fn add_contact_segment(triangles: &mut Vec<Triangle>, angle1: f32, angle2: f32,
-                      inner_radius: f32, outer_radius: f32, _height: f32) {
-    // Full contact surface for three-point support
-    let p1 = Point3::new(inner_radius * angle1.cos(), inner_radius * angle1.sin(), 0.0);
-    let p2 = Point3::new(outer_radius * angle1.cos(), outer_radius * angle1.sin(), 0.0);
-    let p3 = Point3::new(inner_radius * angle2.cos(), inner_radius * angle2.sin(), 0.0);
-    let p4 = Point3::new(outer_radius * angle2.cos(), outer_radius * angle2.sin(), 0.0);
-    
-    add_quad_triangles(triangles, p1, p2, p3, p4);
-}
-
-fn add_minimal_segment(triangles: &mut Vec<Triangle>, angle1: f32, angle2: f32,
-                      inner_radius: f32, outer_radius: f32, height: f32) {
-    // Minimal structure between contact points - just outer ring connection
-    let reduced_radius = inner_radius + (outer_radius - inner_radius) * 0.8;
-    
-    let p1 = Point3::new(reduced_radius * angle1.cos(), reduced_radius * angle1.sin(), height * 0.5);
-    let p2 = Point3::new(outer_radius * angle1.cos(), outer_radius * angle1.sin(), 0.0);
-    let p3 = Point3::new(reduced_radius * angle2.cos(), reduced_radius * angle2.sin(), height * 0.5);
-    let p4 = Point3::new(outer_radius * angle2.cos(), outer_radius * angle2.sin(), 0.0);
-    
-    add_quad_triangles(triangles, p1, p2, p3, p4);
-}
-
-fn add_ring_base(triangles: &mut Vec<Triangle>, inner_radius: f32, outer_radius: f32, z: f32, segments: usize) {
-    for i in 0..segments {
-        let angle1 = 2.0 * std::f32::consts::PI * i as f32 / segments as f32;
-        let angle2 = 2.0 * std::f32::consts::PI * ((i + 1) % segments) as f32 / segments as f32;
-        
-        let p1 = Point3::new(inner_radius * angle1.cos(), inner_radius * angle1.sin(), z);
-        let p2 = Point3::new(outer_radius * angle1.cos(), outer_radius * angle1.sin(), z);
-        let p3 = Point3::new(inner_radius * angle2.cos(), inner_radius * angle2.sin(), z);
-        let p4 = Point3::new(outer_radius * angle2.cos(), outer_radius * angle2.sin(), z);
-        
-        // Add triangles for ring base (facing down)
-        add_triangle(triangles, p1, p3, p2);
-        add_triangle(triangles, p2, p3, p4);
-    }
-}
-
-fn add_ring_top(triangles: &mut Vec<Triangle>, inner_radius: f32, outer_radius: f32, z: f32, segments: usize) {
-    for i in 0..segments {
-        let angle1 = 2.0 * std::f32::consts::PI * i as f32 / segments as f32;
-        let angle2 = 2.0 * std::f32::consts::PI * ((i + 1) % segments) as f32 / segments as f32;
-        
-        let p1 = Point3::new(inner_radius * angle1.cos(), inner_radius * angle1.sin(), z);
-        let p2 = Point3::new(outer_radius * angle1.cos(), outer_radius * angle1.sin(), z);
-        let p3 = Point3::new(inner_radius * angle2.cos(), inner_radius * angle2.sin(), z);
-        let p4 = Point3::new(outer_radius * angle2.cos(), outer_radius * angle2.sin(), z);
-        
-        // Add triangles for ring top (facing up)
-        add_triangle(triangles, p1, p2, p3);
-        add_triangle(triangles, p2, p4, p3);
-    }
-}
-
-fn add_quad_triangles(triangles: &mut Vec<Triangle>, p1: Point3<f32>, p2: Point3<f32>, 
-                     p3: Point3<f32>, p4: Point3<f32>) {
-    // Split quad into two triangles
-    add_triangle(triangles, p1, p2, p3);
-    add_triangle(triangles, p2, p4, p3);
-}
-
-fn add_triangle(triangles: &mut Vec<Triangle>, p1: Point3<f32>, p2: Point3<f32>, p3: Point3<f32>) {
-    // Calculate normal
-    let edge1 = Vector3::new(p2.x - p1.x, p2.y - p1.y, p2.z - p1.z);
-    let edge2 = Vector3::new(p3.x - p1.x, p3.y - p1.y, p3.z - p1.z);
-    let normal = edge1.cross(&edge2).normalize();
-    
-    triangles.push(Triangle {
-        normal: stl_io::Vector::new([normal.x, normal.y, normal.z]),
-        vertices: [
-            stl_io::Vector::new([p1.x, p1.y, p1.z]),
-            stl_io::Vector::new([p2.x, p2.y, p2.z]),
-            stl_io::Vector::new([p3.x, p3.y, p3.z]),
-        ],
-    });
+
+    Ok(mesh.into_triangles())
 }
@@ -0,0 +1,191 @@
+use anyhow::Result;
+use std::f32::consts::PI;
+
+use crate::features::angle_between;
+use crate::mesh::{ProfilePoint, Surface};
+
+/// Angle of diamond knurl grooves from the ring axis
+const DIAMOND_HELIX: f32 = PI / 6.0;
+
+/// Finest tooth pitch that prints cleanly, in millimeters
+const MIN_PITCH: f32 = 0.4;
+
+/// Angular samples across each flute
+const FLUTE_STEPS: usize = 8;
+
+/// Outer wall grip pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GripStyle {
+    /// V grooves parallel to the axis
+    StraightKnurl,
+    /// Crossed helical V grooves leaving small pyramids
+    DiamondKnurl,
+    /// Rounded grooves parallel to the axis
+    Flutes,
+    /// Six flats for a wrench
+    Hexagon,
+    /// Eight flats for a wrench
+    Octagon,
+}
+
+/// Grip pattern on the outer wall, over a band of its height
+#[derive(Debug, Clone, PartialEq)]
+pub struct GripSpec {
+    pub style: GripStyle,
+    /// Tooth or flute spacing around the wall, in millimeters
+    pub pitch: f32,
+    /// Groove depth in millimeters; polygons take theirs from the flats
+    pub depth: f32,
+    /// Bottom and top of the band in millimeters, clipped to the wall; the whole wall when unset
+    pub band: Option<(f32, f32)>,
+}
+
+/// Triangle wave: 0 at whole numbers, 1 halfway between
+fn triangle(u: f32) -> f32 {
+    1.0 - (1.0 - 2.0 * u.rem_euclid(1.0)).abs()
+}
+
+impl GripStyle {
+    /// Number of flats for polygon styles
+    fn sides(&self) -> Option<usize> {
+        match self {
+            GripStyle::Hexagon => Some(6),
+            GripStyle::Octagon => Some(8),
+            _ => None,
+        }
+    }
+}
+
+impl GripSpec {
+    /// Bottom and top of the band on a wall `top` high
+    fn band(&self, top: f32) -> (f32, f32) {
+        let (from, to) = self.band.unwrap_or((0.0, top));
+        (from.max(0.0), to.min(top))
+    }
+
+    /// Teeth or flutes around a wall of `outer_radius`
+    fn teeth(&self, outer_radius: f32) -> usize {
+        ((2.0 * PI * outer_radius / self.pitch).round() as usize).max(3)
+    }
+
+    /// Deepest the pattern cuts into the wall
+    fn cut_depth(&self, outer_radius: f32) -> f32 {
+        match self.style.sides() {
+            Some(sides) => outer_radius * (1.0 - (PI / sides as f32).cos()),
+            None => self.depth,
+        }
+    }
+
+    /// Wrench size across the flats, for polygon styles
+    pub fn across_flats(&self, outer_radius: f32) -> Option<f32> {
+        self.style.sides().map(|sides| 2.0 * outer_radius * (PI / sides as f32).cos())
+    }
+
+    /// Check that the grip leaves a printable wall; `top` is the height of the outer wall
+    pub fn validate(&self, inner_radius: f32, outer_radius: f32, top: f32) -> Result<()> {
+        if self.style.sides().is_none() {
+            if self.depth <= 0.0 {
+                return Err(anyhow::anyhow!("Grip depth must be positive"));
+            }
+            if self.pitch < MIN_PITCH || self.depth > self.pitch {
+                return Err(anyhow::anyhow!(
+                    "Grip pitch ({:.2}mm) must be at least {:.1}mm and at least the {:.2}mm groove depth",
+                    self.pitch, MIN_PITCH, self.depth
+                ));
+            }
+        }
+        let wall = outer_radius - self.cut_depth(outer_radius) - inner_radius;
+        if wall < 1.0 {
+            return Err(anyhow::anyhow!(
+                "The grip cuts {:.2}mm into the wall and leaves {:.2}mm. Minimum: 1.0mm",
+                self.cut_depth(outer_radius), wall
+            ));
+        }
+        let (from, to) = self.band(top);
+        if to - from < 1.0 {
+            return Err(anyhow::anyhow!(
+                "The grip band must cover at least 1.0mm of the {:.2}mm wall ({:.2}-{:.2}mm here)",
+                top, from, to
+            ));
+        }
+        Ok(())
+    }
+
+    /// Angles where teeth, flutes and corners fall, so their edges are crisp
+    pub fn angle_breaks(&self, outer_radius: f32, clocking: f32) -> Vec<f32> {
+        if let Some(sides) = self.style.sides() {
+            let first = clocking.to_radians() + PI / sides as f32;
+            return (0..sides).map(|i| first + 2.0 * PI * i as f32 / sides as f32).collect();
+        }
+        let teeth = self.teeth(outer_radius);
+        let steps = match self.style {
+            GripStyle::StraightKnurl => 2,
+            GripStyle::DiamondKnurl => 4,
+            _ => 2 * FLUTE_STEPS,
+        };
+        (0..teeth * steps).map(|i| 2.0 * PI * i as f32 / (teeth * steps) as f32).collect()
+    }
+
+    /// Wall heights at the band edges and fades, and through the band for diamond knurls
+    pub fn height_breaks(&self, outer_radius: f32, top: f32) -> Vec<f32> {
+        let (from, to) = self.band(top);
+        let fade = self.cut_depth(outer_radius);
+        let mut heights = vec![from, to, (from - fade).max(0.0), (to + fade).min(top)];
+        if self.style == GripStyle::DiamondKnurl {
+            let tooth = 2.0 * PI * outer_radius / self.teeth(outer_radius) as f32;
+            let step = tooth / (4.0 * DIAMOND_HELIX.tan());
+            let rows = ((to - from) / step).ceil() as usize;
+            heights.extend((1..rows).map(|i| from + i as f32 * step));
+        }
+        heights
+    }
+
+    /// Share of the full depth cut at height `z`: 1 inside the band, fading at 45° beyond it
+    fn strength(&self, z: f32, outer_radius: f32, top: f32) -> f32 {
+        let (from, to) = self.band(top);
+        let fade = self.cut_depth(outer_radius).max(1e-4);
+        let below = if from <= 0.0 { 0.0 } else { (from - z) / fade };
+        let above = if to >= top { 0.0 } else { (z - to) / fade };
+        (1.0 - below.max(above).max(0.0)).clamp(0.0, 1.0)
+    }
+
+    /// Outer wall radius at `angle` and height `z` for a plain wall of `outer_radius`
+    fn radius(&self, angle: f32, z: f32, outer_radius: f32, top: f32, clocking: f32) -> f32 {
+        let strength = self.strength(z, outer_radius, top);
+        if strength <= 0.0 {
+            return outer_radius;
+        }
+
+        let teeth = self.teeth(outer_radius) as f32;
+        let phase = teeth * angle.rem_euclid(2.0 * PI) / (2.0 * PI);
+        let cut = match self.style {
+            GripStyle::StraightKnurl => self.depth * triangle(phase),
+            GripStyle::DiamondKnurl => {
+                let twist = z * DIAMOND_HELIX.tan() * teeth / (2.0 * PI * outer_radius);
+                self.depth * triangle(phase + twist).max(triangle(phase - twist))
+            }
+            GripStyle::Flutes => {
+                // Scallops half a pitch wide, centered between the lands
+                let s = 4.0 * ((phase + 0.5).rem_euclid(1.0) - 0.5);
+                self.depth * (1.0 - s * s).max(0.0).sqrt()
+            }
+            GripStyle::Hexagon | GripStyle::Octagon => {
+                let sides = self.style.sides().unwrap_or(6) as f32;
+                let sector = 2.0 * PI / sides;
+                let from_flat = angle_between(angle, clocking.to_radians());
+                let from_flat = (from_flat + sector / 2.0).rem_euclid(sector) - sector / 2.0;
+                outer_radius - outer_radius * (PI / sides).cos() / from_flat.cos()
+            }
+        };
+        outer_radius - strength * cut
+    }
+
+    /// Cut the grip into an outer wall point of a wall `top` high
+    pub fn apply(&self, point: &ProfilePoint, angle: f32, outer_radius: f32, top: f32, clocking: f32) -> ProfilePoint {
+        if point.surface != Surface::Outer || point.r < outer_radius - 1e-4 {
+            return *point;
+        }
+        let r = self.radius(angle, point.z, outer_radius, top, clocking) + (point.r - outer_radius);
+        ProfilePoint { r, ..*point }
+    }
+}
@@ -0,0 +1,332 @@
+use anyhow::Result;
+use nalgebra::Point3;
+use std::collections::HashSet;
+use std::f32::consts::PI;
+
+use crate::features::angle_between;
+use crate::mesh::{Mesh, ProfilePoint, Surface, EDGE_RAMP};
+
+/// Grid cells across each hole, in both directions
+const HOLE_STEPS: usize = 8;
+
+/// Material required around a hole, in millimeters
+pub const MIN_HOLE_WALL: f32 = 1.0;
+
+/// Screw sizes: name, nominal diameter, tap drill, heat-set insert hole,
+/// insert length and clearance hole, all in millimeters
+pub const SCREW_SIZES: [(&str, f32, f32, f32, f32, f32); 7] = [
+    ("M2", 2.0, 1.6, 3.2, 3.0, 2.4),
+    ("M2.5", 2.5, 2.05, 3.6, 3.4, 2.9),
+    ("M3", 3.0, 2.5, 4.0, 4.0, 3.4),
+    ("M4", 4.0, 3.3, 5.6, 5.0, 4.5),
+    ("4-40", 2.845, 2.26, 4.0, 4.0, 3.0),
+    ("6-32", 3.505, 2.7, 4.8, 4.5, 3.7),
+    ("8-32", 4.166, 3.4, 5.6, 5.0, 4.4),
+];
+
+/// What goes into the hole
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HoleKind {
+    /// Tap drill size, for cutting threads into the print
+    Tapped,
+    /// Sized for a heat-set threaded insert
+    Insert,
+    /// Clearance for a clamping screw
+    Clearance,
+}
+
+/// Radial holes through the ring wall for set, centering or clamping screws
+#[derive(Debug, Clone, PartialEq)]
+pub struct HoleSpec {
+    /// Screw size from [`SCREW_SIZES`], e.g. "M3"
+    pub screw: String,
+    pub kind: HoleKind,
+    pub count: usize,
+    /// Hole angles in degrees; evenly spaced from the clocking angle when empty
+    pub angles: Vec<f32>,
+    /// Hole diameter override in millimeters
+    pub diameter: Option<f32>,
+    /// Height of the hole axis; mid-way up the wall when unset
+    pub height: Option<f32>,
+    /// Radial thickness of an external boss around each hole
+    pub boss: Option<f32>,
+}
+
+/// Where one hole is cut, in ring coordinates
+#[derive(Debug, Clone, PartialEq)]
+pub struct HoleLayout {
+    pub angle: f32,
+    pub z: f32,
+    pub radius: f32,
+    /// Half extents of the grid block the hole is cut from
+    half_angle: f32,
+    half_height: f32,
+}
+
+impl HoleSpec {
+    fn size(&self) -> Result<(f32, f32, f32, f32, f32)> {
+        SCREW_SIZES.iter()
+            .find(|(name, ..)| name.eq_ignore_ascii_case(&self.screw))
+            .map(|&(_, nominal, tap, insert, insert_length, clearance)| (nominal, tap, insert, insert_length, clearance))
+            .ok_or_else(|| anyhow::anyhow!(
+                "Unknown screw size: {}. Known sizes: {}",
+                self.screw,
+                SCREW_SIZES.map(|(name, ..)| name).join(", ")
+            ))
+    }
+
+    pub fn diameter(&self) -> Result<f32> {
+        if let Some(diameter) = self.diameter {
+            return Ok(diameter);
+        }
+        let (_, tap, insert, _, clearance) = self.size()?;
+        Ok(match self.kind {
+            HoleKind::Tapped => tap,
+            HoleKind::Insert => insert,
+            HoleKind::Clearance => clearance,
+        })
+    }
+
+    /// Radial wall length the screw or insert needs to hold
+    fn engagement(&self) -> Result<f32> {
+        let (nominal, _, _, insert_length, _) = self.size()?;
+        Ok(match self.kind {
+            HoleKind::Tapped => nominal,
+            HoleKind::Insert => insert_length,
+            HoleKind::Clearance => MIN_HOLE_WALL,
+        })
+    }
+
+    /// Hole angles in radians
+    pub fn hole_angles(&self, clocking: f32) -> Vec<f32> {
+        if !self.angles.is_empty() {
+            return self.angles.iter().map(|angle| angle.to_radians()).collect();
+        }
+        (0..self.count)
+            .map(|i| clocking.to_radians() + 2.0 * PI * i as f32 / self.count as f32)
+            .collect()
+    }
+
+    fn boss_half_angle(&self, outer_radius: f32) -> Result<f32> {
+        Ok((self.diameter()? / 2.0 + 2.0 * MIN_HOLE_WALL) / outer_radius)
+    }
+
+    /// Lay the holes out on a ring whose lowest seat point is at `seat_height`
+    pub fn layout(&self, inner_radius: f32, seat_height: f32, clocking: f32) -> Result<Vec<HoleLayout>> {
+        let radius = self.diameter()? / 2.0;
+        let z = self.height.unwrap_or(seat_height / 2.0);
+        Ok(self.hole_angles(clocking).into_iter().map(|angle| HoleLayout {
+            angle,
+            z,
+            radius,
+            half_angle: radius / inner_radius,
+            half_height: radius,
+        }).collect())
+    }
+
+    /// Check the wall left around every hole
+    pub fn validate(&self, inner_radius: f32, outer_radius: f32, seat_height: f32, clocking: f32) -> Result<()> {
+        let diameter = self.diameter()?;
+        if diameter <= 0.0 {
+            return Err(anyhow::anyhow!("Hole diameter must be positive"));
+        }
+        if self.angles.is_empty() && self.count == 0 {
+            return Err(anyhow::anyhow!("Hole count must be at least 1"));
+        }
+        let holes = self.layout(inner_radius, seat_height, clocking)?;
+
+        // Above and below each hole
+        let z = holes[0].z;
+        let (below, above) = (z - diameter / 2.0, seat_height - z - diameter / 2.0);
+        if below.min(above) < MIN_HOLE_WALL {
+            return Err(anyhow::anyhow!(
+                "A {:.2}mm hole leaves {:.2}mm of wall below and {:.2}mm above it. Minimum: {:.1}mm; raise --height or use a smaller screw",
+                diameter, below, above, MIN_HOLE_WALL
+            ));
+        }
+
+        // Between neighboring holes, measured at the bore
+        let mut angles: Vec<f32> = holes.iter().map(|hole| hole.angle.rem_euclid(2.0 * PI)).collect();
+        angles.sort_by(|a, b| a.total_cmp(b));
+        for (i, angle) in angles.iter().enumerate() {
+            let next = angles[(i + 1) % angles.len()] + if i + 1 == angles.len() { 2.0 * PI } else { 0.0 };
+            if angles.len() > 1 && (next - angle) * inner_radius - diameter < MIN_HOLE_WALL {
+                return Err(anyhow::anyhow!(
+                    "Holes at {:.0}° and {:.0}° are too close together",
+                    angle.to_degrees(), next.to_degrees().rem_euclid(360.0)
+                ));
+            }
+        }
+
+        // Through the wall: enough length for the thread or insert to hold
+        let length = outer_radius + self.boss.unwrap_or(0.0) - inner_radius;
+        let needed = self.engagement()?;
+        if length < needed {
+            return Err(anyhow::anyhow!(
+                "The wall is {:.2}mm thick at the holes but a {} {} needs {:.2}mm. Add a boss with --boss {:.1}",
+                length,
+                self.screw,
+                match self.kind {
+                    HoleKind::Tapped => "thread",
+                    HoleKind::Insert => "insert",
+                    HoleKind::Clearance => "hole",
+                },
+                needed,
+                needed - (outer_radius - inner_radius)
+            ));
+        }
+        Ok(())
+    }
+
+    /// Angles for the grid blocks holes are cut from, and the boss edges
+    pub fn angle_breaks(&self, inner_radius: f32, outer_radius: f32, seat_height: f32, clocking: f32) -> Result<Vec<f32>> {
+        let mut breaks = Vec::new();
+        for hole in self.layout(inner_radius, seat_height, clocking)? {
+            breaks.extend((0..=HOLE_STEPS).map(|i| {
+                hole.angle - hole.half_angle + 2.0 * hole.half_angle * i as f32 / HOLE_STEPS as f32
+            }));
+            if self.boss.is_some() {
+                let half = self.boss_half_angle(outer_radius)?;
+                breaks.extend([hole.angle - half - EDGE_RAMP, hole.angle - half, hole.angle + half, hole.angle + half + EDGE_RAMP]);
+            }
+        }
+        Ok(breaks)
+    }
+
+    /// Wall heights for the grid blocks; the bore and outer wall must share them
+    pub fn height_breaks(&self, inner_radius: f32, seat_height: f32) -> Result<Vec<f32>> {
+        let hole = match self.layout(inner_radius, seat_height, 0.0)?.into_iter().next() {
+            Some(hole) => hole,
+            None => return Ok(Vec::new()),
+        };
+        Ok((0..=HOLE_STEPS)
+            .map(|i| hole.z - hole.half_height + 2.0 * hole.half_height * i as f32 / HOLE_STEPS as f32)
+            .collect())
+    }
+
+    /// Push the wall out into a flat-faced boss around each hole
+    pub fn apply_boss(&self, point: &ProfilePoint, angle: f32, inner_radius: f32, outer_radius: f32, clocking: f32) -> ProfilePoint {
+        let (thickness, half) = match (self.boss, self.boss_half_angle(outer_radius)) {
+            (Some(thickness), Ok(half)) => (thickness, half),
+            _ => return *point,
+        };
+        let offset = self.hole_angles(clocking).into_iter()
+            .map(|hole| angle_between(angle, hole).abs())
+            .fold(PI, f32::min);
+        if offset > half + 1e-5 {
+            return *point;
+        }
+
+        let limit = (outer_radius + thickness) / offset.cos();
+        let r = inner_radius + (point.r - inner_radius) * (limit - inner_radius) / (outer_radius - inner_radius);
+        ProfilePoint { r, ..*point }
+    }
+}
+
+/// Cut radial holes through a ring revolved from `profile` at `angles`.
+///
+/// Each hole removes a block of grid cells from the outer wall and the bore,
+/// snaps the block edges onto the hole circle and joins the two edges with a
+/// tube, so the mesh stays closed. Both walls must share the hole's grid
+/// heights, and the angles must include the hole's angle breaks.
+pub fn cut_holes(
+    mesh: &mut Mesh,
+    profile: &[ProfilePoint],
+    angles: &[f32],
+    holes: &[HoleLayout],
+    place: impl Fn(&ProfilePoint, f32) -> Point3<f32>,
+) -> Result<()> {
+    let rows = profile.len();
+    let segments = angles.len();
+    let mut removed = HashSet::new();
+    let mut tube = Vec::new();
+
+    for hole in holes {
+        let find_angle = |target: f32| {
+            angles.iter()
+                .position(|&angle| angle_between(angle, target).abs() < 1e-4)
+                .ok_or_else(|| anyhow::anyhow!("Hole grid is missing an angle"))
+        };
+        let (first, last) = (find_angle(hole.angle - hole.half_angle)?, find_angle(hole.angle + hole.half_angle)?);
+        let columns: Vec<usize> = (0..segments)
+            .map(|step| (first + step) % segments)
+            .take_while(|&i| i != (last + 1) % segments)
+            .collect();
+
+        // Rows of each wall inside the block, bottom to top
+        let (z_min, z_max) = (hole.z - hole.half_height - 1e-4, hole.z + hole.half_height + 1e-4);
+        let wall_rows = |surface: Surface| -> Vec<usize> {
+            let mut wall: Vec<usize> = (0..rows)
+                .filter(|&j| profile[j].surface == surface && (z_min..=z_max).contains(&profile[j].z))
+                .collect();
+            wall.sort_by(|&a, &b| profile[a].z.total_cmp(&profile[b].z));
+            wall
+        };
+        let (outer_rows, bore_rows) = (wall_rows(Surface::Outer), wall_rows(Surface::Bore));
+        if outer_rows.len() != bore_rows.len() || outer_rows.len() < 2 {
+            return Err(anyhow::anyhow!("Hole grid heights differ between the bore and the outer wall"));
+        }
+
+        // Quad (i, j) spans angles i..i+1 and profile points j..j+1 (see `revolve`)
+        for window in columns.windows(2) {
+            let i = window[0];
+            for k in 0..outer_rows.len() - 1 {
+                for j in [outer_rows[k], bore_rows[k + 1]] {
+                    removed.insert(2 * (i * rows + j));
+                    removed.insert(2 * (i * rows + j) + 1);
+                }
+            }
+        }
+
+        // Walk the block boundary and snap it onto the hole circle
+        let top = outer_rows.len() - 1;
+        let mut boundary: Vec<(usize, usize)> = columns.iter().map(|&i| (i, 0)).collect();
+        boundary.extend((1..=top).map(|k| (last, k)));
+        boundary.extend(columns.iter().rev().skip(1).map(|&i| (i, top)));
+        boundary.extend((1..top).rev().map(|k| (first, k)));
+
+        let mut ring = Vec::new();
+        for &(i, k) in &boundary {
+            let u = angle_between(angles[i], hole.angle) / hole.half_angle;
+            let v = (profile[outer_rows[k]].z - hole.z) / hole.half_height;
+            let length = (u * u + v * v).sqrt();
+            let (du, dv) = (u / length, v / length);
+            let z = hole.z + dv * hole.radius;
+
+            let mut pair = [0; 2];
+            for (slot, &j) in [outer_rows[k], bore_rows[k]].iter().enumerate() {
+                let point = ProfilePoint { z, ..profile[j] };
+                let angle = hole.angle + du * hole.radius / profile[j].r;
+                pair[slot] = i * rows + j;
+                mesh.vertices[pair[slot]] = place(&point, angle);
+            }
+            ring.push(pair);
+        }
+        tube.push(ring);
+    }
+
+    // Tube quads take over the removed cells' boundary edges, in the same direction
+    let edges: HashSet<(usize, usize)> = removed.iter()
+        .flat_map(|&face| {
+            let [a, b, c] = mesh.faces[face];
+            [(a, b), (b, c), (c, a)]
+        })
+        .collect();
+    mesh.faces = mesh.faces.iter()
+        .enumerate()
+        .filter(|(index, _)| !removed.contains(index))
+        .map(|(_, face)| *face)
+        .collect();
+
+    for ring in tube {
+        for (p, q) in ring.iter().zip(ring.iter().cycle().skip(1)) {
+            let ([outer_p, bore_p], [outer_q, bore_q]) = (*p, *q);
+            if edges.contains(&(outer_p, outer_q)) {
+                mesh.add_quad(outer_p, outer_q, bore_q, bore_p);
+            } else {
+                mesh.add_quad(outer_q, outer_p, bore_p, bore_q);
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,207 @@
+use anyhow::Result;
+use nalgebra::Point3;
+use std::f32::consts::PI;
+use stl_io::Triangle;
+
+use crate::features::angle_between;
+use crate::geometry::{contact_angles, generate_ring_mesh, RingParameters, RING_SEGMENTS};
+use crate::mesh::{merge_angles, revolve, subdivide, uniform_angles, ProfilePoint, Surface};
+use crate::naming::part_filename;
+
+/// Angular spacing of grid columns where a groove meets the top face, in radians
+const EDGE_STEP: f32 = 0.004;
+
+/// Largest step across the ball part's domes or sockets, in millimeters
+const BALL_STEP: f32 = 0.25;
+
+/// Solid plate under the ball part's domes and sockets, in millimeters
+const PLATE_FLOOR: f32 = 1.5;
+
+/// What the matching part carries where the balls go
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BallPart {
+    /// Printed hemispheres standing on the plate
+    Hemispheres,
+    /// Hemispherical pockets that hold steel balls
+    Sockets,
+}
+
+/// Kinematic V-groove mount: three radial grooves in a three-point ring's
+/// pads, and a matching part with three balls that drop into them.
+///
+/// Each ball touches both flanks of its groove, so the three grooves take
+/// out all six degrees of freedom and the part seats the same way every time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KinematicSpec {
+    pub ball_diameter: f32,
+    /// Included angle between the groove flanks, in degrees
+    pub groove_angle: f32,
+    /// Diameter of the circle through the ball centers; mid-wall when unset
+    pub ball_circle: Option<f32>,
+    pub part: BallPart,
+}
+
+impl KinematicSpec {
+    fn half_angle(&self) -> f32 {
+        (self.groove_angle / 2.0).to_radians()
+    }
+
+    fn ball_radius(&self) -> f32 {
+        self.ball_diameter / 2.0
+    }
+
+    /// Distance from the axis to the ball centers
+    pub fn ball_circle_radius(&self, inner_radius: f32, outer_radius: f32) -> f32 {
+        self.ball_circle.map_or((inner_radius + outer_radius) / 2.0, |d| d / 2.0)
+    }
+
+    /// Height of the ball contacts above the bottom of the groove
+    pub fn contact_height(&self) -> f32 {
+        let half_angle = self.half_angle();
+        self.ball_radius() * half_angle.cos().powi(2) / half_angle.sin()
+    }
+
+    /// Groove depth: the flanks run a quarter of the ball radius above the contacts
+    pub fn depth(&self) -> f32 {
+        self.contact_height() + self.ball_radius() / 4.0
+    }
+
+    /// Half the groove's width at the top face
+    pub fn half_width(&self) -> f32 {
+        self.depth() * self.half_angle().tan()
+    }
+
+    /// Gap between the ring's top face and the ball part's plate once seated
+    pub fn gap(&self) -> f32 {
+        self.ball_radius() / self.half_angle().sin() - self.depth()
+    }
+
+    /// Thickness of the ball part's plate
+    pub fn plate_thickness(&self) -> f32 {
+        match self.part {
+            BallPart::Hemispheres => PLATE_FLOOR,
+            BallPart::Sockets => self.ball_radius() + PLATE_FLOOR,
+        }
+    }
+
+    /// Lower a profile point of a ring with its top face at `height` into the
+    /// nearest groove. Everything above `base` stretches to follow the groove,
+    /// so the walls meet its flanks.
+    pub fn cut_groove(&self, point: &ProfilePoint, angle: f32, contacts: &[f32], height: f32, base: f32) -> ProfilePoint {
+        let offset = contacts.iter()
+            .map(|&contact| angle_between(angle, contact))
+            .fold(PI, |nearest, offset| if offset.abs() < nearest.abs() { offset } else { nearest });
+        if offset.abs() >= PI / 2.0 || point.z <= base {
+            return *point;
+        }
+        let groove = height - self.depth() + point.r * offset.sin().abs() / self.half_angle().tan();
+        if groove >= height {
+            return *point;
+        }
+        ProfilePoint { z: base + (point.z - base) * (groove - base) / (height - base), ..*point }
+    }
+
+    /// Grid angles along each groove's bottom and across the band where its
+    /// edges cross the top face
+    pub fn angle_breaks(&self, contacts: &[f32], inner_radius: f32, outer_radius: f32) -> Vec<f32> {
+        let half_width = self.half_width();
+        let (near, far) = ((half_width / outer_radius).asin(), (half_width / inner_radius).min(1.0).asin());
+        let steps = ((far - near) / EDGE_STEP).ceil() as usize;
+        contacts.iter().flat_map(|&contact| {
+            let edges = (0..=steps).map(move |k| near + (far - near) * k as f32 / steps.max(1) as f32);
+            std::iter::once(contact).chain(edges.flat_map(move |edge| [contact - edge, contact + edge]))
+        }).collect()
+    }
+
+    /// Check the grooves leave a floor under them, the balls clear the ring,
+    /// and the ball part has room for its domes or sockets
+    pub fn validate(&self, inner_radius: f32, outer_radius: f32, height: f32) -> Result<()> {
+        if self.ball_diameter <= 0.0 {
+            return Err(anyhow::anyhow!("Ball diameter must be positive"));
+        }
+        if !(60.0..=120.0).contains(&self.groove_angle) {
+            return Err(anyhow::anyhow!(
+                "Groove angle {:.1}° is out of range. Use 60-120°",
+                self.groove_angle
+            ));
+        }
+        let floor = height - self.depth();
+        if floor < 1.0 {
+            return Err(anyhow::anyhow!(
+                "{:.2}mm deep grooves for {:.2}mm balls leave {:.2}mm below them. Raise --height to at least {:.2}mm",
+                self.depth(), self.ball_diameter, floor, self.depth() + 1.0
+            ));
+        }
+        let margin = match self.part {
+            BallPart::Hemispheres => 0.0,
+            BallPart::Sockets => 1.0,
+        };
+        let ball_circle = self.ball_circle_radius(inner_radius, outer_radius);
+        let reach = self.ball_radius() + margin;
+        if ball_circle - reach < inner_radius || ball_circle + reach > outer_radius {
+            return Err(anyhow::anyhow!(
+                "{:.2}mm balls on a {:.2}mm circle don't fit the {:.2}-{:.2}mm wall. Use smaller balls or a wider ring",
+                self.ball_diameter, 2.0 * ball_circle, 2.0 * inner_radius, 2.0 * outer_radius
+            ));
+        }
+        Ok(())
+    }
+
+    /// Generate the matching part: a plate the size of the ring with three
+    /// hemispheres or ball sockets on its top face, at the contact angles.
+    /// It prints face up and turns over onto the ring.
+    pub fn generate_ball_part(&self, inner_radius: f32, outer_radius: f32, contacts: &[f32]) -> Vec<Triangle> {
+        let ball_radius = self.ball_radius();
+        let ball_circle = self.ball_circle_radius(inner_radius, outer_radius);
+        let thickness = self.plate_thickness();
+
+        let span = (ball_radius / ball_circle).min(1.0).asin();
+        let steps = (span * ball_circle / BALL_STEP).ceil() as usize;
+        let breaks = contacts.iter().flat_map(|&contact| {
+            (0..=2 * steps).map(move |k| contact - span + span * k as f32 / steps as f32)
+        });
+        let angles = merge_angles(uniform_angles(RING_SEGMENTS), breaks);
+
+        let top_outer = ProfilePoint::new(outer_radius, thickness, Surface::Top);
+        let top_inner = ProfilePoint::new(inner_radius, thickness, Surface::Bore);
+        let mut profile = vec![
+            ProfilePoint::new(inner_radius, 0.0, Surface::Bore),
+            ProfilePoint::new(outer_radius, 0.0, Surface::Outer),
+        ];
+        profile.extend(subdivide(top_outer, top_inner, BALL_STEP, Surface::Top));
+        profile.push(top_inner);
+
+        let centers: Vec<[f32; 2]> = contacts.iter()
+            .map(|&contact| [ball_circle * contact.cos(), ball_circle * contact.sin()])
+            .collect();
+        revolve(&profile, &angles, |point, angle| {
+            let (x, y) = (point.r * angle.cos(), point.r * angle.sin());
+            let mut z = point.z;
+            if point.surface == Surface::Top {
+                let nearest = centers.iter()
+                    .map(|&[cx, cy]| (x - cx).powi(2) + (y - cy).powi(2))
+                    .fold(f32::MAX, f32::min);
+                let rise = (ball_radius * ball_radius - nearest).max(0.0).sqrt();
+                z += match self.part {
+                    BallPart::Hemispheres => rise,
+                    BallPart::Sockets => -rise,
+                };
+            }
+            Point3::new(x, y, z)
+        }).into_triangles()
+    }
+}
+
+/// Generate a V-groove ring and its matching ball part, named after the
+/// ring with a `-balls` suffix for the ball part
+pub fn generate_kinematic_parts(params: &RingParameters) -> Result<Vec<(String, Vec<Triangle>)>> {
+    let spec = params.kinematic.as_ref()
+        .ok_or_else(|| anyhow::anyhow!("Kinematic parts need a V-groove specification"))?;
+    let filename = params.filename();
+    let balls = spec.generate_ball_part(params.inner_diameter / 2.0, params.outer_diameter / 2.0, &contact_angles(params));
+
+    Ok(vec![
+        (filename.clone(), generate_ring_mesh(params)?),
+        (part_filename(&filename, "balls"), balls),
+    ])
+}
@@ -0,0 +1,309 @@
+use anyhow::Result;
+
+use crate::features::angle_between;
+use crate::geometry::{RingParameters, RingType};
+use crate::mesh::{ProfilePoint, Surface};
+use crate::naming::render_label;
+
+/// Label text used when none is given, e.g. "CC 25.4"
+pub const DEFAULT_LABEL: &str = "{type} {inner}";
+
+/// Glyph cell in font units: glyphs are 4 wide and 6 tall, advancing by 6
+const GLYPH_WIDTH: f32 = 4.0;
+const GLYPH_HEIGHT: f32 = 6.0;
+const GLYPH_ADVANCE: f32 = 6.0;
+
+/// Stroke width in font units
+const STROKE_WIDTH: f32 = 1.0;
+
+/// Depth of engraved and height of embossed text, in millimeters
+const LABEL_DEPTH: f32 = 0.4;
+
+/// Mesh spacing under a label, in millimeters
+const LABEL_STEP: f32 = 0.15;
+
+/// Smallest cap height that prints legibly, in millimeters
+const MIN_TEXT_HEIGHT: f32 = 1.5;
+
+/// Single-stroke font: each glyph is a list of polylines on a 4x6 grid, y up.
+/// A polyline with one point is a dot.
+fn glyph(c: char) -> Option<&'static [&'static [(f32, f32)]]> {
+    Some(match c.to_ascii_uppercase() {
+        ' ' => &[],
+        '0' => &[&[(0.0, 0.0), (4.0, 0.0), (4.0, 6.0), (0.0, 6.0), (0.0, 0.0), (4.0, 6.0)]],
+        '1' => &[&[(1.0, 5.0), (2.0, 6.0), (2.0, 0.0)], &[(1.0, 0.0), (3.0, 0.0)]],
+        '2' => &[&[(0.0, 6.0), (4.0, 6.0), (4.0, 3.0), (0.0, 3.0), (0.0, 0.0), (4.0, 0.0)]],
+        '3' => &[&[(0.0, 6.0), (4.0, 6.0), (4.0, 0.0), (0.0, 0.0)], &[(1.0, 3.0), (4.0, 3.0)]],
+        '4' => &[&[(0.0, 6.0), (0.0, 3.0), (4.0, 3.0)], &[(4.0, 6.0), (4.0, 0.0)]],
+        '5' | 'S' => &[&[(4.0, 6.0), (0.0, 6.0), (0.0, 3.0), (4.0, 3.0), (4.0, 0.0), (0.0, 0.0)]],
+        '6' => &[&[(4.0, 6.0), (0.0, 6.0), (0.0, 0.0), (4.0, 0.0), (4.0, 3.0), (0.0, 3.0)]],
+        '7' => &[&[(0.0, 6.0), (4.0, 6.0), (1.0, 0.0)]],
+        '8' => &[&[(0.0, 0.0), (4.0, 0.0), (4.0, 6.0), (0.0, 6.0), (0.0, 0.0)], &[(0.0, 3.0), (4.0, 3.0)]],
+        '9' => &[&[(4.0, 3.0), (0.0, 3.0), (0.0, 6.0), (4.0, 6.0), (4.0, 0.0), (0.0, 0.0)]],
+        'A' => &[&[(0.0, 0.0), (0.0, 4.0), (2.0, 6.0), (4.0, 4.0), (4.0, 0.0)], &[(0.0, 3.0), (4.0, 3.0)]],
+        'B' => &[
+            &[(0.0, 0.0), (0.0, 6.0), (3.0, 6.0), (4.0, 5.0), (4.0, 4.0), (3.0, 3.0), (0.0, 3.0)],
+            &[(3.0, 3.0), (4.0, 2.0), (4.0, 1.0), (3.0, 0.0), (0.0, 0.0)],
+        ],
+        'C' => &[&[(4.0, 6.0), (0.0, 6.0), (0.0, 0.0), (4.0, 0.0)]],
+        'D' => &[&[(0.0, 0.0), (0.0, 6.0), (2.0, 6.0), (4.0, 4.0), (4.0, 2.0), (2.0, 0.0), (0.0, 0.0)]],
+        'E' => &[&[(4.0, 6.0), (0.0, 6.0), (0.0, 0.0), (4.0, 0.0)], &[(0.0, 3.0), (3.0, 3.0)]],
+        'F' => &[&[(4.0, 6.0), (0.0, 6.0), (0.0, 0.0)], &[(0.0, 3.0), (3.0, 3.0)]],
+        'G' => &[&[(4.0, 6.0), (0.0, 6.0), (0.0, 0.0), (4.0, 0.0), (4.0, 3.0), (2.0, 3.0)]],
+        'H' => &[&[(0.0, 6.0), (0.0, 0.0)], &[(4.0, 6.0), (4.0, 0.0)], &[(0.0, 3.0), (4.0, 3.0)]],
+        'I' => &[&[(1.0, 6.0), (3.0, 6.0)], &[(2.0, 6.0), (2.0, 0.0)], &[(1.0, 0.0), (3.0, 0.0)]],
+        'J' => &[&[(4.0, 6.0), (4.0, 0.0), (0.0, 0.0), (0.0, 2.0)]],
+        'K' => &[&[(0.0, 6.0), (0.0, 0.0)], &[(4.0, 6.0), (0.0, 3.0), (4.0, 0.0)]],
+        'L' => &[&[(0.0, 6.0), (0.0, 0.0), (4.0, 0.0)]],
+        'M' => &[&[(0.0, 0.0), (0.0, 6.0), (2.0, 3.0), (4.0, 6.0), (4.0, 0.0)]],
+        'N' => &[&[(0.0, 0.0), (0.0, 6.0), (4.0, 0.0), (4.0, 6.0)]],
+        'O' => &[&[(0.0, 0.0), (4.0, 0.0), (4.0, 6.0), (0.0, 6.0), (0.0, 0.0)]],
+        'P' => &[&[(0.0, 0.0), (0.0, 6.0), (4.0, 6.0), (4.0, 3.0), (0.0, 3.0)]],
+        'Q' => &[&[(0.0, 0.0), (4.0, 0.0), (4.0, 6.0), (0.0, 6.0), (0.0, 0.0)], &[(2.0, 2.0), (4.0, 0.0)]],
+        'R' => &[&[(0.0, 0.0), (0.0, 6.0), (4.0, 6.0), (4.0, 3.0), (0.0, 3.0), (4.0, 0.0)]],
+        'T' => &[&[(0.0, 6.0), (4.0, 6.0)], &[(2.0, 6.0), (2.0, 0.0)]],
+        'U' => &[&[(0.0, 6.0), (0.0, 0.0), (4.0, 0.0), (4.0, 6.0)]],
+        'V' => &[&[(0.0, 6.0), (2.0, 0.0), (4.0, 6.0)]],
+        'W' => &[&[(0.0, 6.0), (1.0, 0.0), (2.0, 3.0), (3.0, 0.0), (4.0, 6.0)]],
+        'X' => &[&[(0.0, 0.0), (4.0, 6.0)], &[(0.0, 6.0), (4.0, 0.0)]],
+        'Y' => &[&[(0.0, 6.0), (2.0, 3.0), (4.0, 6.0)], &[(2.0, 3.0), (2.0, 0.0)]],
+        'Z' => &[&[(0.0, 6.0), (4.0, 6.0), (0.0, 0.0), (4.0, 0.0)]],
+        '.' => &[&[(2.0, 0.0)]],
+        ',' => &[&[(2.0, 1.0), (1.0, -1.0)]],
+        '-' => &[&[(1.0, 3.0), (3.0, 3.0)]],
+        '+' => &[&[(2.0, 1.0), (2.0, 5.0)], &[(0.0, 3.0), (4.0, 3.0)]],
+        '/' => &[&[(0.0, 0.0), (4.0, 6.0)]],
+        '#' => &[&[(1.0, 0.0), (1.0, 6.0)], &[(3.0, 0.0), (3.0, 6.0)], &[(0.0, 2.0), (4.0, 2.0)], &[(0.0, 4.0), (4.0, 4.0)]],
+        '_' => &[&[(0.0, 0.0), (4.0, 0.0)]],
+        _ => return None,
+    })
+}
+
+/// Distance from `(x, y)` to the segment `a`-`b`
+fn segment_distance((x, y): (f32, f32), a: (f32, f32), b: (f32, f32)) -> f32 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length_squared = dx * dx + dy * dy;
+    let t = if length_squared > 0.0 {
+        (((x - a.0) * dx + (y - a.1) * dy) / length_squared).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    ((x - a.0 - t * dx).powi(2) + (y - a.1 - t * dy).powi(2)).sqrt()
+}
+
+/// Whether `(x, y)` in font units, measured from the start of the baseline, is on a stroke
+fn on_stroke(text: &str, (x, y): (f32, f32)) -> bool {
+    if x < -STROKE_WIDTH || !(-STROKE_WIDTH..=GLYPH_HEIGHT + STROKE_WIDTH).contains(&y) {
+        return false;
+    }
+    let index = ((x + (GLYPH_ADVANCE - GLYPH_WIDTH) / 2.0) / GLYPH_ADVANCE).floor().max(0.0) as usize;
+    let c = match text.chars().nth(index) {
+        Some(c) => c,
+        None => return false,
+    };
+    let local = (x - index as f32 * GLYPH_ADVANCE, y);
+
+    glyph(c).unwrap_or(&[]).iter().any(|stroke| {
+        let segments: Vec<((f32, f32), (f32, f32))> = match stroke.len() {
+            1 => vec![(stroke[0], stroke[0])],
+            _ => stroke.windows(2).map(|pair| (pair[0], pair[1])).collect(),
+        };
+        segments.iter().any(|&(a, b)| segment_distance(local, a, b) <= STROKE_WIDTH / 2.0)
+    })
+}
+
+/// Where on the ring a label goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelPlacement {
+    /// Along the top face, letters pointing outward
+    Top,
+    /// Around the outer wall, upright
+    Wall,
+}
+
+impl LabelPlacement {
+    /// Default face for a ring type: the outer wall where the top face is a
+    /// lens seat, the top face elsewhere
+    pub fn for_ring(ring_type: RingType) -> Self {
+        if seats_lens_on_top(ring_type) {
+            LabelPlacement::Wall
+        } else {
+            LabelPlacement::Top
+        }
+    }
+}
+
+/// Whether the lens rests on the ring's top face, so text there would lift
+/// or undercut it
+fn seats_lens_on_top(ring_type: RingType) -> bool {
+    matches!(
+        ring_type,
+        RingType::Convex | RingType::Concave | RingType::ThreePoint | RingType::Tangent | RingType::SharpCorner
+            | RingType::Conical
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelStyle {
+    Engrave,
+    Emboss,
+}
+
+/// Text on a ring, rendered with the built-in stroke font
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelSpec {
+    /// Text with optional filename placeholders, e.g. `{type} {inner}` or `SN {seq:03}`
+    pub template: String,
+    pub placement: LabelPlacement,
+    pub style: LabelStyle,
+    /// Center of the text in degrees, counter-clockwise from +X
+    pub angle: f32,
+    /// Cap height in millimeters; sized to the face if unset
+    pub size: Option<f32>,
+}
+
+impl Default for LabelSpec {
+    fn default() -> Self {
+        Self {
+            template: DEFAULT_LABEL.to_string(),
+            placement: LabelPlacement::Top,
+            style: LabelStyle::Engrave,
+            angle: 270.0,
+            size: None,
+        }
+    }
+}
+
+/// A label laid out on a particular ring
+#[derive(Debug, Clone, PartialEq)]
+pub struct RingLabel {
+    pub text: String,
+    placement: LabelPlacement,
+    /// Signed offset of the surface: positive for embossed text
+    relief: f32,
+    angle: f32,
+    /// Millimeters per font unit
+    unit: f32,
+    /// Radius the text is laid out along
+    radius: f32,
+    /// Radius (top face) or height (wall) of the text baseline
+    baseline: f32,
+}
+
+impl RingLabel {
+    /// Lay out `spec` on the ring described by `params`
+    pub fn new(spec: &LabelSpec, params: &RingParameters) -> Result<Self> {
+        let text = render_label(&spec.template, params).trim().to_string();
+        if text.is_empty() {
+            return Err(anyhow::anyhow!("Label text is empty"));
+        }
+        if let Some(c) = text.chars().find(|c| glyph(*c).is_none()) {
+            return Err(anyhow::anyhow!(
+                "Label character '{}' is not in the stroke font. Use letters, digits, space and . , - + / # _",
+                c
+            ));
+        }
+
+        if spec.placement == LabelPlacement::Top && seats_lens_on_top(params.ring_type) {
+            return Err(anyhow::anyhow!(
+                "A top-face label would sit under the lens on a {} seat. Put it on the wall with --label-on wall",
+                params.ring_type.name().to_lowercase()
+            ));
+        }
+
+        let (inner_radius, outer_radius) = (params.inner_diameter / 2.0, params.outer_diameter / 2.0);
+        let wall = outer_radius - inner_radius;
+        let (face, radius) = match spec.placement {
+            LabelPlacement::Top => (wall, (inner_radius + outer_radius) / 2.0),
+            LabelPlacement::Wall => (params.height, outer_radius),
+        };
+        let size = spec.size.unwrap_or((0.5 * face).min(4.0));
+        if size < MIN_TEXT_HEIGHT {
+            return Err(anyhow::anyhow!(
+                "Label text would be {:.2}mm tall, too small to print legibly. Minimum: {:.1}mm",
+                size, MIN_TEXT_HEIGHT
+            ));
+        }
+        if size > 0.8 * face {
+            return Err(anyhow::anyhow!(
+                "Label text ({:.2}mm) does not fit the {:.2}mm {}",
+                size, face, if spec.placement == LabelPlacement::Top { "wall" } else { "ring height" }
+            ));
+        }
+
+        let unit = size / (GLYPH_HEIGHT + STROKE_WIDTH);
+        let label = Self {
+            text,
+            placement: spec.placement,
+            relief: match spec.style {
+                LabelStyle::Engrave => -LABEL_DEPTH,
+                LabelStyle::Emboss => LABEL_DEPTH,
+            },
+            angle: spec.angle.to_radians(),
+            unit,
+            radius,
+            baseline: match spec.placement {
+                LabelPlacement::Top => radius - GLYPH_HEIGHT * unit / 2.0,
+                LabelPlacement::Wall => (params.height - GLYPH_HEIGHT * unit) / 2.0,
+            },
+        };
+        if label.width() > std::f32::consts::PI * radius {
+            return Err(anyhow::anyhow!(
+                "Label \"{}\" is {:.1}mm long and does not fit half way around the ring",
+                label.text, label.width()
+            ));
+        }
+        Ok(label)
+    }
+
+    /// Length of the text along the ring, in millimeters
+    pub fn width(&self) -> f32 {
+        (self.text.chars().count() as f32 * GLYPH_ADVANCE - (GLYPH_ADVANCE - GLYPH_WIDTH)) * self.unit
+    }
+
+    /// Half the angle the text spans, with a margin for the stroke width
+    fn half_span(&self) -> f32 {
+        (self.width() / 2.0 + STROKE_WIDTH * self.unit) / self.radius
+    }
+
+    /// Dense angles across the text so strokes are resolved
+    pub fn angle_breaks(&self) -> Vec<f32> {
+        let half = self.half_span();
+        let step = LABEL_STEP / self.radius;
+        let steps = (2.0 * half / step).ceil() as usize;
+        (0..=steps).map(|i| self.angle - half + i as f32 * step).collect()
+    }
+
+    /// Profile spacing needed on the top face and outer wall
+    pub fn top_step(&self) -> Option<f32> {
+        (self.placement == LabelPlacement::Top).then_some(LABEL_STEP)
+    }
+
+    pub fn wall_step(&self) -> Option<f32> {
+        (self.placement == LabelPlacement::Wall).then_some(LABEL_STEP)
+    }
+
+    /// Raise or cut the profile point where it falls on a stroke
+    pub fn apply(&self, point: &ProfilePoint, angle: f32) -> ProfilePoint {
+        let offset = angle_between(angle, self.angle);
+        if offset.abs() > self.half_span() {
+            return *point;
+        }
+        let along = offset * self.radius;
+
+        // Top-face text reads clockwise with letters pointing outward; wall
+        // text reads counter-clockwise seen from outside
+        let (x, y, on_face) = match self.placement {
+            LabelPlacement::Top => (self.width() / 2.0 - along, point.r - self.baseline, point.surface == Surface::Top),
+            LabelPlacement::Wall => (along + self.width() / 2.0, point.z - self.baseline, point.surface == Surface::Outer),
+        };
+        if !on_face || !on_stroke(&self.text, (x / self.unit, y / self.unit)) {
+            return *point;
+        }
+
+        match self.placement {
+            LabelPlacement::Top => ProfilePoint { z: point.z + self.relief, ..*point },
+            LabelPlacement::Wall => ProfilePoint { r: point.r + self.relief, ..*point },
+        }
+    }
+}
@@ -0,0 +1,275 @@
+use anyhow::Result;
+use nalgebra::Point3;
+use stl_io::Triangle;
+
+use crate::geometry::{seat_offset, RingParameters, RingType, SEAT_CURVE_FRACTION};
+use crate::mesh::{revolve, subdivide, uniform_angles, ProfilePoint, Surface};
+use crate::naming::{part_filename, NameOptions};
+
+/// Angular segments for lens cell parts
+const CELL_SEGMENTS: usize = 128;
+
+/// Radial steps across a curved seat
+const SEAT_STEPS: usize = 16;
+
+/// A lens to be mounted, in millimeters.
+///
+/// Surface radii are signed from the lens' point of view: positive for a
+/// convex surface, negative for a concave one, zero for a flat.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LensSpec {
+    pub diameter: f32,
+    pub edge_thickness: f32,
+    /// Surface resting on the barrel seat
+    pub front_radius: f32,
+    /// Surface held by the retainer
+    pub back_radius: f32,
+}
+
+impl LensSpec {
+    pub fn new(diameter: f32, edge_thickness: f32, front_radius: f32, back_radius: f32) -> Result<Self> {
+        if diameter <= 0.0 || edge_thickness <= 0.0 {
+            return Err(anyhow::anyhow!("Lens diameter and edge thickness must be positive"));
+        }
+        for (name, radius) in [("Front", front_radius), ("Back", back_radius)] {
+            if radius != 0.0 && radius.abs() < diameter / 2.0 {
+                return Err(anyhow::anyhow!(
+                    "{} radius ({:.2}mm) is smaller than the lens semi-diameter ({:.2}mm)",
+                    name, radius.abs(), diameter / 2.0
+                ));
+            }
+        }
+
+        let lens = Self { diameter, edge_thickness, front_radius, back_radius };
+        if lens.center_thickness() <= 0.0 {
+            return Err(anyhow::anyhow!(
+                "Lens surfaces intersect: center thickness would be {:.2}mm",
+                lens.center_thickness()
+            ));
+        }
+        Ok(lens)
+    }
+
+    /// Thickness on the optical axis
+    pub fn center_thickness(&self) -> f32 {
+        self.edge_thickness + surface_bulge(self.front_radius, self.diameter / 2.0, 0.0)
+            + surface_bulge(self.back_radius, self.diameter / 2.0, 0.0)
+    }
+}
+
+/// How far a spherical surface stands out past the lens edge plane at `r`;
+/// negative where a concave surface is recessed
+fn surface_bulge(radius: f32, semi_diameter: f32, r: f32) -> f32 {
+    if radius == 0.0 {
+        return 0.0;
+    }
+    let sag = |r: f32| radius.abs() - (radius.powi(2) - r.powi(2)).max(0.0).sqrt();
+    radius.signum() * (sag(semi_diameter) - sag(r))
+}
+
+/// Parameters for a lens cell: a barrel with an integrated seat and a retainer
+#[derive(Debug, Clone)]
+pub struct LensCellParameters {
+    pub lens: LensSpec,
+    /// Seat profile, CX or CC, shared by the barrel seat and the retainer
+    pub seat: RingType,
+    /// Barrel wall thickness around the lens bore
+    pub wall_thickness: f32,
+    /// Radial gap between the lens edge and the bore
+    pub clearance: f32,
+    /// How far the seat and retainer reach in over the lens edge
+    pub seat_width: f32,
+    /// Axial thickness of the barrel floor carrying the seat
+    pub seat_thickness: f32,
+    pub retainer_height: f32,
+    pub naming: NameOptions,
+}
+
+/// Axial layout of an assembled cell, measured from the barrel base
+#[derive(Debug, Clone, PartialEq)]
+pub struct LensCellLayout {
+    /// Height of the lens edge face resting on the seat
+    pub lens_edge_z: f32,
+    /// Diameter at which the lens touches the barrel seat
+    pub seat_contact_diameter: f32,
+    /// Diameter at which the retainer touches the lens
+    pub retainer_contact_diameter: f32,
+    /// Barrel height; the retainer sits flush with the barrel top
+    pub barrel_height: f32,
+}
+
+impl LensCellParameters {
+    pub fn new(lens: LensSpec, seat: RingType) -> Result<Self> {
+        if !matches!(seat, RingType::Convex | RingType::Concave) {
+            return Err(anyhow::anyhow!("Lens cells use a CX or CC seat, not {}", seat));
+        }
+
+        Ok(Self {
+            lens,
+            seat,
+            wall_thickness: 2.0,
+            clearance: 0.1,
+            seat_width: 1.5,
+            seat_thickness: 2.0,
+            retainer_height: 2.0,
+            naming: NameOptions::default(),
+        })
+    }
+
+    pub fn lens_radius(&self) -> f32 {
+        self.lens.diameter / 2.0
+    }
+
+    pub fn bore_radius(&self) -> f32 {
+        self.lens_radius() + self.clearance
+    }
+
+    /// Clear aperture left open by the seat and retainer
+    pub fn aperture_radius(&self) -> f32 {
+        self.lens_radius() - self.seat_width
+    }
+
+    pub fn outer_radius(&self) -> f32 {
+        self.bore_radius() + self.wall_thickness
+    }
+
+    /// Retainer radius, a slip fit in the bore
+    fn retainer_radius(&self) -> f32 {
+        self.bore_radius() - self.clearance
+    }
+
+    /// Seat surface offset toward the lens at radius `r` on a seat spanning `inner..outer`
+    fn seat_height(&self, r: f32, inner: f32, outer: f32) -> f32 {
+        let t = (r - inner) / (outer - inner);
+        seat_offset(self.seat, t, (outer - inner) * SEAT_CURVE_FRACTION)
+    }
+
+    /// Where a seat spanning `inner..outer` first touches a lens surface.
+    /// Returns the contact radius and how far the lens edge plane sits from
+    /// the seat's base plane.
+    fn seat_contact(&self, surface_radius: f32, inner: f32, outer: f32) -> (f32, f32) {
+        (0..=SEAT_STEPS * 4)
+            .map(|i| inner + (self.lens_radius() - inner) * i as f32 / (SEAT_STEPS * 4) as f32)
+            .map(|r| (r, self.seat_height(r, inner, outer) + surface_bulge(surface_radius, self.lens_radius(), r)))
+            .fold((inner, f32::MIN), |best, candidate| if candidate.1 > best.1 { candidate } else { best })
+    }
+
+    pub fn layout(&self) -> LensCellLayout {
+        let (seat_contact, seat_offset) = self.seat_contact(self.lens.front_radius, self.aperture_radius(), self.bore_radius());
+        let (retainer_contact, retainer_offset) = self.seat_contact(self.lens.back_radius, self.aperture_radius(), self.retainer_radius());
+
+        let lens_edge_z = self.seat_thickness + seat_offset;
+        LensCellLayout {
+            lens_edge_z,
+            seat_contact_diameter: 2.0 * seat_contact,
+            retainer_contact_diameter: 2.0 * retainer_contact,
+            barrel_height: lens_edge_z + self.lens.edge_thickness + retainer_offset + self.retainer_height,
+        }
+    }
+
+    /// Equivalent ring parameters, used for file naming and printing checks
+    pub fn ring_parameters(&self) -> Result<RingParameters> {
+        let mut params = RingParameters::new(self.seat, 2.0 * self.outer_radius(), self.lens.diameter)?;
+        params.height = self.layout().barrel_height;
+        params.naming = self.naming.clone();
+        Ok(params)
+    }
+
+    /// Check that every part is printable
+    pub fn validate(&self) -> Result<()> {
+        if self.seat_width <= 0.0 || self.aperture_radius() < 1.0 {
+            return Err(anyhow::anyhow!(
+                "Seat width ({:.2}mm) must be positive and leave a clear aperture",
+                self.seat_width
+            ));
+        }
+        if self.wall_thickness < 1.0 {
+            return Err(anyhow::anyhow!(
+                "Barrel wall ({:.2}mm) is too thin for reliable 3D printing. Minimum recommended: 1.0mm",
+                self.wall_thickness
+            ));
+        }
+
+        // Concave seats dip into the floor and the retainer
+        let dip = (self.bore_radius() - self.aperture_radius()) * SEAT_CURVE_FRACTION;
+        let dip = if self.seat == RingType::Concave { dip } else { 0.0 };
+        for (name, thickness) in [("Seat floor", self.seat_thickness), ("Retainer", self.retainer_height)] {
+            if thickness - dip < 1.0 {
+                return Err(anyhow::anyhow!(
+                    "{} is only {:.2}mm thick under the seat. Minimum recommended: 1.0mm",
+                    name, thickness - dip
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Generate the barrel and retainer as `(filename, triangles)` parts
+pub fn generate_lens_cell(params: &LensCellParameters) -> Result<Vec<(String, Vec<Triangle>)>> {
+    params.validate()?;
+    let filename = params.ring_parameters()?.filename();
+
+    Ok(vec![
+        (part_filename(&filename, "barrel"), generate_barrel(params)),
+        (part_filename(&filename, "retainer"), generate_retainer(params)),
+    ])
+}
+
+fn revolve_part(profile: &[ProfilePoint]) -> Vec<Triangle> {
+    revolve(profile, &uniform_angles(CELL_SEGMENTS), |point, angle| {
+        Point3::new(point.r * angle.cos(), point.r * angle.sin(), point.z)
+    }).into_triangles()
+}
+
+/// Points of a seat surface from `outer` in to `inner`, excluding `inner`
+fn seat_points(params: &LensCellParameters, inner: f32, outer: f32, z: f32) -> Vec<ProfilePoint> {
+    (0..SEAT_STEPS).map(|i| {
+        let r = outer - (outer - inner) * i as f32 / SEAT_STEPS as f32;
+        let surface = if i == 0 { Surface::Outer } else { Surface::Top };
+        ProfilePoint::new(r, z + params.seat_height(r, inner, outer), surface)
+    }).collect()
+}
+
+/// Barrel with the seat on its floor; the lens drops in from the top
+fn generate_barrel(params: &LensCellParameters) -> Vec<Triangle> {
+    let layout = params.layout();
+    let (aperture, bore, outer) = (params.aperture_radius(), params.bore_radius(), params.outer_radius());
+
+    let base_inner = ProfilePoint::new(aperture, 0.0, Surface::Bore);
+    let base_outer = ProfilePoint::new(outer, 0.0, Surface::Outer);
+    let top_outer = ProfilePoint::new(outer, layout.barrel_height, Surface::Outer);
+    let top_bore = ProfilePoint::new(bore, layout.barrel_height, Surface::Bore);
+    let seat_bore = ProfilePoint::new(bore, params.seat_thickness, Surface::Bore);
+    let seat_inner = ProfilePoint::new(aperture, params.seat_thickness, Surface::Bore);
+
+    let mut profile = Vec::new();
+    profile.extend(subdivide(base_inner, base_outer, f32::MAX, Surface::Base));
+    profile.extend(subdivide(base_outer, top_outer, f32::MAX, Surface::Outer));
+    profile.extend(subdivide(top_outer, top_bore, f32::MAX, Surface::Top));
+    profile.extend(subdivide(top_bore, seat_bore, f32::MAX, Surface::Bore));
+    profile.extend(seat_points(params, aperture, bore, params.seat_thickness));
+    profile.extend(subdivide(seat_inner, base_inner, f32::MAX, Surface::Bore));
+
+    revolve_part(&profile)
+}
+
+/// Retainer in its printing orientation: flat side down, seat facing up.
+/// It is flipped into the barrel and bonded or press-fitted above the lens.
+fn generate_retainer(params: &LensCellParameters) -> Vec<Triangle> {
+    let (aperture, outer) = (params.aperture_radius(), params.retainer_radius());
+    let height = params.retainer_height;
+
+    let base_inner = ProfilePoint::new(aperture, 0.0, Surface::Bore);
+    let base_outer = ProfilePoint::new(outer, 0.0, Surface::Outer);
+    let top_outer = ProfilePoint::new(outer, height, Surface::Outer);
+    let top_inner = ProfilePoint::new(aperture, height, Surface::Bore);
+
+    let mut profile = Vec::new();
+    profile.extend(subdivide(base_inner, base_outer, f32::MAX, Surface::Base));
+    profile.extend(subdivide(base_outer, top_outer, f32::MAX, Surface::Outer));
+    profile.extend(seat_points(params, aperture, outer, height));
+    profile.extend(subdivide(top_inner, base_inner, f32::MAX, Surface::Bore));
+
+    revolve_part(&profile)
+}
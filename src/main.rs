@@ -1,11 +1,60 @@
+mod aperture;
+mod asphere;
+mod bayonet;
+mod cone;
+mod config;
+mod corner;
+mod eccentric;
+mod features;
+mod flexure;
 mod geometry;
+mod grip;
+mod holes;
+mod kinematic;
+mod label;
+mod lens_cell;
+mod naming;
+mod profile;
+mod saddle;
+mod spacer;
+mod mesh;
 mod stl_output;
+mod tangent;
+mod thread;
 mod ui;
+mod vent;
+mod wedge;
 
 use anyhow::Result;
 use clap::{Parser, ValueEnum};
+use config::Config;
+use features::{FlatSpec, IndexMark, KeySpec, KeyStyle, MarkStyle, OrientationFeatures};
 use geometry::{RingParameters, RingType};
-use stl_output::{generate_stl_file, validate_for_printing, print_manufacturing_info};
+use label::{LabelPlacement, LabelSpec, LabelStyle};
+use aperture::{ApertureShape, ApertureSpec};
+use asphere::AsphereSpec;
+use bayonet::{generate_bayonet_parts, BayonetSpec};
+use flexure::FlexureSpec;
+use tangent::TangentSpec;
+use corner::CornerSpec;
+use cone::ConeSpec;
+use eccentric::{eccentric_set, generate_eccentric_set, EccentricSpec};
+use grip::{GripSpec, GripStyle};
+use holes::{HoleKind, HoleSpec};
+use kinematic::{generate_kinematic_parts, BallPart, KinematicSpec};
+use vent::{VentSpec, VentStyle};
+use wedge::WedgeSpec;
+use spacer::{design_spacers, generate_stack_spacer, stack_summary, Prescription};
+use lens_cell::{generate_lens_cell, LensCellParameters, LensSpec};
+use profile::{generate_profile_ring, CustomProfile};
+use saddle::{generate_saddle, SaddleParameters};
+use naming::{validate_template, NameOptions, DEFAULT_TEMPLATE};
+use thread::{ThreadSide, ThreadSpec};
+use std::io::{IsTerminal, Write};
+use stl_output::{
+    generate_stl_file, validate_for_printing, print_manufacturing_info, write_part_files,
+    CollisionPolicy, OutputExists, OutputStatus,
+};
 
 #[derive(Parser)]
 #[command(name = "optics-ring-generator")]
@@ -24,6 +73,368 @@ struct Cli {
     #[arg(short, long)]
     inner_diameter: Option<f32>,
     
+    /// Ring height in millimeters (default: the wall thickness, at least 2mm)
+    #[arg(long)]
+    height: Option<f32>,
+
+    /// Non-circular aperture; the inner and outer diameters become the X sizes
+    #[arg(long, value_enum, requires = "aperture_y")]
+    aperture: Option<CliApertureShape>,
+
+    /// Aperture size along Y in millimeters; the frame wall is the same all round
+    #[arg(long, value_name = "MM")]
+    aperture_y: Option<f32>,
+
+    /// Corner radius of a rectangular aperture in millimeters
+    #[arg(long, value_name = "MM", default_value_t = 0.0)]
+    corner_radius: f32,
+
+    /// Tilt the seat relative to the base by this wedge angle in degrees
+    #[arg(long, value_name = "DEG")]
+    wedge: Option<f32>,
+
+    /// Direction the wedged seat rises toward, in degrees from +X
+    #[arg(long, value_name = "DEG", default_value_t = 0.0, allow_negative_numbers = true)]
+    wedge_toward: f32,
+
+    /// Offset the outer wall from the bore by this much in millimeters, to correct lens decenter
+    #[arg(long, value_name = "MM", conflicts_with = "eccentric_set")]
+    eccentric: Option<f32>,
+
+    /// Direction the bore is offset toward, in degrees from +X
+    #[arg(long, value_name = "DEG", default_value_t = 0.0, allow_negative_numbers = true)]
+    eccentric_toward: f32,
+
+    /// Write a set of COUNT rings stepping the eccentricity up from centered, each marked toward its offset
+    #[arg(long, value_name = "COUNT", conflicts_with_all = ["index_mark", "bayonet"])]
+    eccentric_set: Option<usize>,
+
+    /// Eccentricity step between rings of a set in millimeters
+    #[arg(long, value_name = "MM", default_value_t = 0.05)]
+    eccentric_step: f32,
+
+    /// Add a key on the outer wall, centered at this angle in degrees
+    #[arg(long, value_name = "DEG", allow_negative_numbers = true, conflicts_with = "keyway")]
+    key: Option<f32>,
+
+    /// Cut a keyway into the outer wall, centered at this angle in degrees
+    #[arg(long, value_name = "DEG", allow_negative_numbers = true)]
+    keyway: Option<f32>,
+
+    /// Key or keyway width in millimeters
+    #[arg(long, default_value_t = 3.0)]
+    key_width: f32,
+
+    /// Key height or keyway depth in millimeters
+    #[arg(long, default_value_t = 1.0)]
+    key_depth: f32,
+
+    /// Cut a D-flat facing this angle in degrees
+    #[arg(long, value_name = "DEG", allow_negative_numbers = true)]
+    flat: Option<f32>,
+
+    /// D-flat depth in millimeters
+    #[arg(long, default_value_t = 1.0)]
+    flat_depth: f32,
+
+    /// Add an index mark at this angle in degrees
+    #[arg(long, value_name = "DEG", allow_negative_numbers = true)]
+    index_mark: Option<f32>,
+
+    /// Index mark style
+    #[arg(long, value_enum, default_value = "arrow")]
+    mark_style: CliMarkStyle,
+
+    /// Angle of the first three-point contact in degrees (0 = +X axis)
+    #[arg(long, value_name = "DEG", default_value_t = 0.0, allow_negative_numbers = true)]
+    clocking: f32,
+
+    /// Label the ring; TEXT may use filename placeholders (default: "{type} {inner}", e.g. "CC 25.4")
+    #[arg(long, value_name = "TEXT", num_args = 0..=1, default_missing_value = label::DEFAULT_LABEL)]
+    label: Option<String>,
+
+    /// Where the label goes (default: the outer wall on rings that seat a lens on top, else the top face)
+    #[arg(long, value_enum)]
+    label_on: Option<CliLabelPlacement>,
+
+    /// Engrave the label into the surface or emboss it
+    #[arg(long, value_enum, default_value = "engrave")]
+    label_style: CliLabelStyle,
+
+    /// Angle of the label center in degrees
+    #[arg(long, value_name = "DEG", default_value_t = 270.0, allow_negative_numbers = true)]
+    label_angle: f32,
+
+    /// Label cap height in millimeters (default: fits the face)
+    #[arg(long, value_name = "MM")]
+    label_size: Option<f32>,
+
+    /// Serial number for the {seq} placeholder, e.g. in labels
+    #[arg(long, default_value_t = 1)]
+    serial: u32,
+
+    /// Cut air-escape vents: radial slots across the seat, axial grooves along the bore, or both
+    #[arg(long, value_enum)]
+    vents: Option<CliVentStyle>,
+
+    /// Number of vents (default: 3 on three-point rings, one between each pair of contacts; 4 otherwise)
+    #[arg(long)]
+    vent_count: Option<usize>,
+
+    /// Vent width in millimeters
+    #[arg(long, default_value_t = 1.0)]
+    vent_width: f32,
+
+    /// Vent depth in millimeters
+    #[arg(long, default_value_t = 0.5)]
+    vent_depth: f32,
+
+    /// Angle of the first vent in degrees (default: between the first two contacts)
+    #[arg(long, value_name = "DEG", allow_negative_numbers = true)]
+    vent_angle: Option<f32>,
+
+    /// Drill radial screw holes through the wall, e.g. M3 or 4-40
+    #[arg(long, value_name = "SCREW")]
+    holes: Option<String>,
+
+    /// What the holes are sized for
+    #[arg(long, value_enum, default_value = "tapped")]
+    hole_kind: CliHoleKind,
+
+    /// Number of holes, evenly spaced from the clocking angle
+    #[arg(long, default_value_t = 3)]
+    hole_count: usize,
+
+    /// Hole angles in degrees, e.g. 0,120,240 (overrides --hole-count)
+    #[arg(long, value_name = "DEG", value_delimiter = ',', allow_negative_numbers = true)]
+    hole_angles: Vec<f32>,
+
+    /// Hole diameter in millimeters (default: from the screw size and kind)
+    #[arg(long, value_name = "MM")]
+    hole_diameter: Option<f32>,
+
+    /// Height of the hole axis above the base in millimeters (default: mid-wall)
+    #[arg(long, value_name = "MM")]
+    hole_height: Option<f32>,
+
+    /// Add an external boss this many millimeters thick around each hole
+    #[arg(long, value_name = "MM")]
+    boss: Option<f32>,
+
+    /// Grip pattern on the outer wall
+    #[arg(long, value_enum)]
+    grip: Option<CliGripStyle>,
+
+    /// Grip tooth or flute spacing around the wall in millimeters
+    #[arg(long, value_name = "MM", default_value_t = 1.0)]
+    grip_pitch: f32,
+
+    /// Grip groove depth in millimeters (polygons take theirs from the flats)
+    #[arg(long, value_name = "MM", default_value_t = 0.4)]
+    grip_depth: f32,
+
+    /// Bottom of the grip band above the base in millimeters (default: 0)
+    #[arg(long, value_name = "MM")]
+    grip_from: Option<f32>,
+
+    /// Top of the grip band above the base in millimeters (default: top of the wall)
+    #[arg(long, value_name = "MM")]
+    grip_to: Option<f32>,
+
+    /// Add bayonet lugs to the outer wall and write a matching socket part
+    #[arg(long)]
+    bayonet: bool,
+
+    /// Number of bayonet lugs
+    #[arg(long, default_value_t = 3)]
+    lugs: usize,
+
+    /// Angular width of each lug in degrees
+    #[arg(long, value_name = "DEG", default_value_t = 30.0)]
+    lug_width: f32,
+
+    /// How far lugs stand out from the outer wall in millimeters
+    #[arg(long, value_name = "MM", default_value_t = 1.0)]
+    lug_depth: f32,
+
+    /// Lug height above the ring base in millimeters
+    #[arg(long, value_name = "MM", default_value_t = 2.0)]
+    lug_height: f32,
+
+    /// Lead-in drop at the leading edge of each lug in millimeters
+    #[arg(long, value_name = "MM", default_value_t = 0.3)]
+    lug_ramp: f32,
+
+    /// Depth of the detent bump at the locked position in millimeters (0 for none)
+    #[arg(long, value_name = "MM", default_value_t = 0.3)]
+    detent: f32,
+
+    /// Rotation from the entry slots to the locked position in degrees
+    #[arg(long, value_name = "DEG", default_value_t = 45.0)]
+    twist: f32,
+
+    /// Clearance between the lugs and the socket in millimeters
+    #[arg(long, value_name = "MM", default_value_t = 0.2)]
+    bayonet_clearance: f32,
+
+    /// Cut kinematic V-grooves into a three-point ring's pads and write a matching ball part
+    #[arg(long, conflicts_with_all = ["bayonet", "eccentric_set"])]
+    v_grooves: bool,
+
+    /// Diameter of the balls seated in the V-grooves in millimeters
+    #[arg(long, value_name = "MM", default_value_t = 6.0)]
+    ball_diameter: f32,
+
+    /// Included angle of the V-grooves in degrees
+    #[arg(long, value_name = "DEG", default_value_t = 90.0)]
+    groove_angle: f32,
+
+    /// Diameter of the circle through the ball centers in millimeters (default: mid-wall)
+    #[arg(long, value_name = "MM")]
+    ball_circle: Option<f32>,
+
+    /// What the matching part carries: printed hemispheres or sockets for steel balls
+    #[arg(long, value_enum, default_value = "hemispheres")]
+    ball_part: CliBallPart,
+
+    /// Number of flexure fingers (flexure mounts)
+    #[arg(long, default_value_t = 3)]
+    fingers: usize,
+
+    /// Flexure finger length along the bore in millimeters
+    #[arg(long, value_name = "MM", default_value_t = 10.0)]
+    finger_length: f32,
+
+    /// Flexure finger radial thickness in millimeters
+    #[arg(long, value_name = "MM", default_value_t = 1.2)]
+    finger_thickness: f32,
+
+    /// Radial interference between the finger pads and the lens in millimeters
+    #[arg(long, value_name = "MM", default_value_t = 0.1)]
+    preload: f32,
+
+    /// Radius of the lens surface on a tangent, sharp-corner, conical or aspheric CX/CC seat: positive convex, negative concave
+    #[arg(long, value_name = "MM", allow_negative_numbers = true)]
+    lens_radius: Option<f32>,
+
+    /// Conic constant of an aspheric CX/CC seat or acylindrical saddle (0 for a sphere)
+    #[arg(long, value_name = "K", default_value_t = 0.0, allow_negative_numbers = true)]
+    conic: f32,
+
+    /// Even-asphere coefficients of r^4, r^6, r^8, ... for a CX/CC seat or saddle, comma separated
+    #[arg(long, value_name = "A4,A6,...", value_delimiter = ',', allow_negative_numbers = true)]
+    asphere_coeffs: Vec<f32>,
+
+    /// Diameter where a tangent seat touches the lens or a cone reaches the ring height (default: mid-wall)
+    #[arg(long, value_name = "MM")]
+    contact_diameter: Option<f32>,
+
+    /// Radius rounding the bore corner of a sharp-corner seat in millimeters (0 for sharp)
+    #[arg(long, value_name = "MM", default_value_t = 0.0)]
+    relief_radius: f32,
+
+    /// Cone half-angle from the ring axis in degrees (conical seats)
+    #[arg(long, value_name = "DEG", default_value_t = 45.0)]
+    cone_angle: f32,
+
+    /// Thread for retaining rings: SM05, SM1, SM2, SM3, C-mount, T-mount or M<diameter>x<pitch>
+    #[arg(long)]
+    thread: Option<String>,
+
+    /// Which wall carries the thread
+    #[arg(long, value_enum)]
+    thread_side: Option<CliThreadSide>,
+
+    /// Radial thread clearance for printing in millimeters
+    #[arg(long)]
+    thread_clearance: Option<f32>,
+
+    /// Generate a lens cell (barrel with a CX or CC seat plus retainer) instead of a single ring
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "outer_diameter", "inner_diameter", "height", "stack",
+            "aperture", "wedge", "eccentric", "eccentric_set", "key", "keyway", "flat", "index_mark", "label",
+            "vents", "holes", "grip", "bayonet", "v_grooves", "lens_radius", "thread",
+        ]
+    )]
+    lens_cell: bool,
+
+    /// Lens diameter in millimeters (lens cell)
+    #[arg(long)]
+    lens_diameter: Option<f32>,
+
+    /// Lens edge thickness in millimeters (lens cell)
+    #[arg(long)]
+    edge_thickness: Option<f32>,
+
+    /// Radius of the lens surface on the seat: positive convex, negative concave, 0 flat
+    #[arg(long, default_value_t = 0.0, allow_negative_numbers = true)]
+    front_radius: f32,
+
+    /// Radius of the lens surface under the retainer: positive convex, negative concave, 0 flat
+    #[arg(long, default_value_t = 0.0, allow_negative_numbers = true)]
+    back_radius: f32,
+
+    /// Barrel wall thickness in millimeters (lens cell)
+    #[arg(long)]
+    cell_wall: Option<f32>,
+
+    /// Generate a saddle for a cylindrical lens (rectangular frame with a curved seat) instead of a ring
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "ring_type", "outer_diameter", "inner_diameter", "lens_cell", "stack",
+            "aperture", "wedge", "eccentric", "eccentric_set", "key", "keyway", "flat", "index_mark", "label",
+            "vents", "holes", "grip", "bayonet", "v_grooves", "lens_radius", "thread",
+        ]
+    )]
+    saddle: bool,
+
+    /// Lens length along the cylinder axis in millimeters (saddle)
+    #[arg(long, value_name = "MM")]
+    lens_length: Option<f32>,
+
+    /// Lens width across the cylinder axis in millimeters (saddle)
+    #[arg(long, value_name = "MM")]
+    lens_width: Option<f32>,
+
+    /// Cylinder radius of the lens surface on the saddle: positive convex, negative concave
+    #[arg(long, value_name = "MM", allow_negative_numbers = true)]
+    cylinder_radius: Option<f32>,
+
+    /// Direction of the cylinder axis in degrees from +X (saddle)
+    #[arg(long, value_name = "DEG", default_value_t = 0.0, allow_negative_numbers = true)]
+    cylinder_axis: f32,
+
+    /// Rim width around the lens in millimeters (saddle)
+    #[arg(long, value_name = "MM")]
+    saddle_wall: Option<f32>,
+
+    /// Design the spacers for a lens prescription (TOML file with [[element]] entries)
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = [
+            "ring_type", "outer_diameter", "inner_diameter", "height",
+            "aperture", "wedge", "eccentric", "eccentric_set", "key", "keyway", "flat", "index_mark", "label",
+            "vents", "holes", "grip", "bayonet", "v_grooves", "lens_radius", "thread",
+        ]
+    )]
+    stack: Option<String>,
+
+    /// Revolve a custom r-z profile (CSV, JSON or SVG path) into a ring instead of a built-in type
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = [
+            "ring_type", "outer_diameter", "inner_diameter", "height", "lens_cell", "saddle", "stack",
+            "aperture", "wedge", "eccentric", "eccentric_set", "key", "keyway", "flat", "index_mark", "label",
+            "vents", "holes", "grip", "bayonet", "v_grooves", "lens_radius", "thread",
+        ]
+    )]
+    profile: Option<String>,
+
     /// Output directory for STL files (default: current directory)
     #[arg(long)]
     output_dir: Option<String>,
@@ -36,6 +447,23 @@ struct Cli {
     #[arg(long)]
     manufacturing_info: bool,
 
+    /// Filename template, e.g. "{type}-{inner}-{material}.stl".
+    /// Placeholders: {type} {inner} {outer} {height} {material} {date} {preset} {seq}
+    #[arg(long)]
+    name_template: Option<String>,
+
+    /// Material name for the {material} filename placeholder
+    #[arg(long)]
+    material: Option<String>,
+
+    /// Preset name for the {preset} filename placeholder
+    #[arg(long)]
+    preset: Option<String>,
+
+    /// What to do when the output file already exists
+    #[arg(long, value_enum, default_value = "ask")]
+    on_collision: CliCollisionPolicy,
+
     /// Launch interactive UI mode instead of CLI mode
     #[arg(long)]
     ui: bool,
@@ -52,20 +480,666 @@ enum CliRingType {
     /// Three-point support ring (3P) - minimal contact points
     #[value(name = "3p")]
     ThreePoint,
+    /// Threaded retaining ring (RR) - holds the lens in a threaded tube
+    #[value(name = "rr")]
+    RetainingRing,
+    /// Flat spacer ring (SP) - sets the air gap between elements
+    #[value(name = "sp")]
+    Spacer,
+    /// Flexure mount (FX) - lens held by compliant fingers
+    #[value(name = "fx")]
+    Flexure,
+    /// Tangent contact ring (TG) - toroidal seat touching the lens tangentially
+    #[value(name = "tg")]
+    Tangent,
+    /// Sharp-corner ring (SC) - lens rests on the bore edge
+    #[value(name = "sc")]
+    SharpCorner,
+    /// Conical ring (CN) - chamfered seat that self-centers spherical surfaces
+    #[value(name = "cn")]
+    Conical,
+}
+
+impl From<CliRingType> for RingType {
+    fn from(cli_type: CliRingType) -> Self {
+        match cli_type {
+            CliRingType::Convex => RingType::Convex,
+            CliRingType::Concave => RingType::Concave,
+            CliRingType::ThreePoint => RingType::ThreePoint,
+            CliRingType::RetainingRing => RingType::RetainingRing,
+            CliRingType::Spacer => RingType::Spacer,
+            CliRingType::Flexure => RingType::Flexure,
+            CliRingType::Tangent => RingType::Tangent,
+            CliRingType::SharpCorner => RingType::SharpCorner,
+            CliRingType::Conical => RingType::Conical,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum CliThreadSide {
+    /// Thread on the outer wall, screws into a tube
+    External,
+    /// Thread in the bore, screws onto a threaded part
+    Internal,
+}
+
+impl From<CliThreadSide> for ThreadSide {
+    fn from(cli_side: CliThreadSide) -> Self {
+        match cli_side {
+            CliThreadSide::External => ThreadSide::External,
+            CliThreadSide::Internal => ThreadSide::Internal,
+        }
+    }
+}
+
+/// Thread specification from the CLI, if one was given
+fn thread_spec(cli: &Cli) -> Result<Option<ThreadSpec>> {
+    let mut spec: ThreadSpec = match cli.thread {
+        Some(ref thread) => thread.parse()?,
+        None => return Ok(None),
+    };
+    if let Some(side) = cli.thread_side {
+        spec.side = side.into();
+    }
+    if let Some(clearance) = cli.thread_clearance {
+        if !(0.0..1.0).contains(&clearance) {
+            return Err(anyhow::anyhow!("Thread clearance must be between 0 and 1mm"));
+        }
+        spec.clearance = clearance;
+    }
+    Ok(Some(spec))
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum CliMarkStyle {
+    /// Arrow engraved in the top face, pointing outward
+    Arrow,
+    /// V notch in the outer wall
+    Notch,
+}
+
+impl From<CliMarkStyle> for MarkStyle {
+    fn from(cli_style: CliMarkStyle) -> Self {
+        match cli_style {
+            CliMarkStyle::Arrow => MarkStyle::Arrow,
+            CliMarkStyle::Notch => MarkStyle::Notch,
+        }
+    }
+}
+
+/// Orientation features requested on the CLI
+fn orientation_features(cli: &Cli) -> OrientationFeatures {
+    let key = cli.key.map(|angle| (KeyStyle::Key, angle))
+        .or(cli.keyway.map(|angle| (KeyStyle::Keyway, angle)))
+        .map(|(style, angle)| KeySpec { style, angle, width: cli.key_width, depth: cli.key_depth });
+
+    OrientationFeatures {
+        key,
+        flat: cli.flat.map(|angle| FlatSpec { angle, depth: cli.flat_depth }),
+        mark: cli.index_mark.map(|angle| IndexMark { style: cli.mark_style.into(), angle }),
+        clocking: cli.clocking,
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum CliLabelPlacement {
+    /// Along the top face
+    Top,
+    /// Around the outer wall
+    Wall,
+}
+
+impl From<CliLabelPlacement> for LabelPlacement {
+    fn from(cli_placement: CliLabelPlacement) -> Self {
+        match cli_placement {
+            CliLabelPlacement::Top => LabelPlacement::Top,
+            CliLabelPlacement::Wall => LabelPlacement::Wall,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum CliLabelStyle {
+    /// Cut into the surface
+    Engrave,
+    /// Raised from the surface
+    Emboss,
+}
+
+impl From<CliLabelStyle> for LabelStyle {
+    fn from(cli_style: CliLabelStyle) -> Self {
+        match cli_style {
+            CliLabelStyle::Engrave => LabelStyle::Engrave,
+            CliLabelStyle::Emboss => LabelStyle::Emboss,
+        }
+    }
+}
+
+/// Label requested on the CLI, if any
+fn label_spec(cli: &Cli, ring_type: RingType) -> Option<LabelSpec> {
+    cli.label.as_ref().map(|template| LabelSpec {
+        template: template.clone(),
+        placement: cli.label_on.map_or(LabelPlacement::for_ring(ring_type), LabelPlacement::from),
+        style: cli.label_style.into(),
+        angle: cli.label_angle,
+        size: cli.label_size,
+    })
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum CliVentStyle {
+    /// Slots across the seat face
+    Radial,
+    /// Grooves along the bore
+    Axial,
+    /// Slots and grooves
+    Both,
+}
+
+impl From<CliVentStyle> for VentStyle {
+    fn from(cli_style: CliVentStyle) -> Self {
+        match cli_style {
+            CliVentStyle::Radial => VentStyle::Radial,
+            CliVentStyle::Axial => VentStyle::Axial,
+            CliVentStyle::Both => VentStyle::Both,
+        }
+    }
+}
+
+/// Vents requested on the CLI, if any
+fn vent_spec(cli: &Cli, ring_type: RingType) -> Option<VentSpec> {
+    // Three-point rings take one vent in each gap between the contacts
+    let default_count = match ring_type {
+        RingType::ThreePoint => 3,
+        _ => 4,
+    };
+    cli.vents.map(|style| VentSpec {
+        style: style.into(),
+        count: cli.vent_count.unwrap_or(default_count),
+        width: cli.vent_width,
+        depth: cli.vent_depth,
+        angle: cli.vent_angle,
+    })
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum CliHoleKind {
+    /// Tap drill size, for threading the print
+    Tapped,
+    /// Sized for a heat-set insert
+    Insert,
+    /// Clearance for a clamping screw
+    Clearance,
+}
+
+impl From<CliHoleKind> for HoleKind {
+    fn from(cli_kind: CliHoleKind) -> Self {
+        match cli_kind {
+            CliHoleKind::Tapped => HoleKind::Tapped,
+            CliHoleKind::Insert => HoleKind::Insert,
+            CliHoleKind::Clearance => HoleKind::Clearance,
+        }
+    }
+}
+
+/// Flexure fingers from the CLI, for flexure mounts
+fn flexure_spec(cli: &Cli, ring_type: RingType) -> Option<FlexureSpec> {
+    (ring_type == RingType::Flexure).then_some(FlexureSpec {
+        fingers: cli.fingers,
+        beam_length: cli.finger_length,
+        beam_thickness: cli.finger_thickness,
+        preload: cli.preload,
+        angle: cli.clocking,
+    })
+}
+
+/// Lens surface from the CLI, for tangent rings
+fn tangent_spec(cli: &Cli, ring_type: RingType) -> Option<TangentSpec> {
+    cli.lens_radius.filter(|_| ring_type == RingType::Tangent).map(|lens_radius| TangentSpec {
+        lens_radius,
+        contact_diameter: cli.contact_diameter,
+    })
+}
+
+/// Lens surface from the CLI, for sharp-corner rings
+fn corner_spec(cli: &Cli, ring_type: RingType) -> Option<CornerSpec> {
+    cli.lens_radius.filter(|_| ring_type == RingType::SharpCorner).map(|lens_radius| CornerSpec {
+        lens_radius,
+        relief: cli.relief_radius,
+    })
+}
+
+/// Aspheric surface from the CLI, for CX and CC rings
+fn asphere_spec(cli: &Cli, ring_type: RingType) -> Option<AsphereSpec> {
+    let is_curved = matches!(ring_type, RingType::Convex | RingType::Concave);
+    cli.lens_radius.filter(|_| is_curved).map(|radius| AsphereSpec {
+        radius,
+        conic: cli.conic,
+        coefficients: cli.asphere_coeffs.clone(),
+    })
+}
+
+/// Cone from the CLI, for conical rings
+fn cone_spec(cli: &Cli, ring_type: RingType) -> Option<ConeSpec> {
+    (ring_type == RingType::Conical).then_some(ConeSpec {
+        half_angle: cli.cone_angle,
+        contact_diameter: cli.contact_diameter,
+        lens_radius: cli.lens_radius,
+    })
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum CliGripStyle {
+    /// Straight knurl, V grooves along the axis
+    Knurl,
+    /// Diamond knurl, crossed helical grooves
+    Diamond,
+    /// Rounded flutes along the axis
+    Flutes,
+    /// Hexagon for a wrench
+    Hex,
+    /// Octagon for a wrench
+    Octagon,
+}
+
+impl From<CliGripStyle> for GripStyle {
+    fn from(cli_style: CliGripStyle) -> Self {
+        match cli_style {
+            CliGripStyle::Knurl => GripStyle::StraightKnurl,
+            CliGripStyle::Diamond => GripStyle::DiamondKnurl,
+            CliGripStyle::Flutes => GripStyle::Flutes,
+            CliGripStyle::Hex => GripStyle::Hexagon,
+            CliGripStyle::Octagon => GripStyle::Octagon,
+        }
+    }
+}
+
+/// Grip pattern requested on the CLI, if any
+fn grip_spec(cli: &Cli) -> Option<GripSpec> {
+    let band = match (cli.grip_from, cli.grip_to) {
+        (None, None) => None,
+        (from, to) => Some((from.unwrap_or(0.0), to.unwrap_or(f32::INFINITY))),
+    };
+    cli.grip.map(|style| GripSpec {
+        style: style.into(),
+        pitch: cli.grip_pitch,
+        depth: cli.grip_depth,
+        band,
+    })
+}
+
+/// Bayonet interface requested on the CLI, if any
+fn bayonet_spec(cli: &Cli) -> Option<BayonetSpec> {
+    cli.bayonet.then(|| BayonetSpec {
+        lugs: cli.lugs,
+        lug_width: cli.lug_width,
+        lug_depth: cli.lug_depth,
+        lug_height: cli.lug_height,
+        ramp: cli.lug_ramp,
+        detent: cli.detent,
+        twist: cli.twist,
+        clearance: cli.bayonet_clearance,
+        ..BayonetSpec::default()
+    })
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum CliBallPart {
+    /// Printed hemispheres standing on a plate
+    Hemispheres,
+    /// Pockets in a plate for pressing in steel balls
+    Sockets,
+}
+
+impl From<CliBallPart> for BallPart {
+    fn from(cli_part: CliBallPart) -> Self {
+        match cli_part {
+            CliBallPart::Hemispheres => BallPart::Hemispheres,
+            CliBallPart::Sockets => BallPart::Sockets,
+        }
+    }
+}
+
+/// Kinematic V-grooves requested on the CLI, if any
+fn kinematic_spec(cli: &Cli) -> Option<KinematicSpec> {
+    cli.v_grooves.then(|| KinematicSpec {
+        ball_diameter: cli.ball_diameter,
+        groove_angle: cli.groove_angle,
+        ball_circle: cli.ball_circle,
+        part: cli.ball_part.into(),
+    })
+}
+
+/// Radial holes requested on the CLI, if any
+fn hole_spec(cli: &Cli) -> Option<HoleSpec> {
+    cli.holes.as_ref().map(|screw| HoleSpec {
+        screw: screw.clone(),
+        kind: cli.hole_kind.into(),
+        count: cli.hole_count,
+        angles: cli.hole_angles.clone(),
+        diameter: cli.hole_diameter,
+        height: cli.hole_height,
+        boss: cli.boss,
+    })
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum CliApertureShape {
+    /// Rectangle, with --corner-radius for rounded corners
+    Rect,
+    /// Ellipse with its axes along X and Y
+    Ellipse,
+}
+
+impl From<CliApertureShape> for ApertureShape {
+    fn from(cli_shape: CliApertureShape) -> Self {
+        match cli_shape {
+            CliApertureShape::Rect => ApertureShape::Rectangle,
+            CliApertureShape::Ellipse => ApertureShape::Ellipse,
+        }
+    }
+}
+
+/// Rectangular or elliptical aperture requested on the CLI, if any
+fn aperture_spec(cli: &Cli) -> Option<ApertureSpec> {
+    cli.aperture.zip(cli.aperture_y).map(|(shape, inner_y)| ApertureSpec {
+        shape: shape.into(),
+        inner_y,
+        corner_radius: cli.corner_radius,
+    })
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum CliCollisionPolicy {
+    /// Ask before replacing an existing file
+    Ask,
+    /// Replace the existing file
+    Overwrite,
+    /// Keep the existing file and skip generation
+    Skip,
+    /// Write to the next free name, e.g. CX-25.0-2.stl
+    Suffix,
+}
+
+impl From<CliCollisionPolicy> for CollisionPolicy {
+    fn from(cli_policy: CliCollisionPolicy) -> Self {
+        match cli_policy {
+            CliCollisionPolicy::Ask => CollisionPolicy::Ask,
+            CliCollisionPolicy::Overwrite => CollisionPolicy::Overwrite,
+            CliCollisionPolicy::Skip => CollisionPolicy::Skip,
+            CliCollisionPolicy::Suffix => CollisionPolicy::Suffix,
+        }
+    }
+}
+
+/// Ask on the terminal how to handle an existing output file
+fn prompt_collision(existing: &OutputExists) -> Result<CollisionPolicy> {
+    if !std::io::stdin().is_terminal() {
+        return Err(anyhow::anyhow!(
+            "{}. Use --on-collision overwrite|skip|suffix when not running interactively",
+            existing
+        ));
+    }
+
+    loop {
+        print!("{}. [o]verwrite, [s]kip, write with [n]ew suffix? ", existing);
+        std::io::stdout().flush()?;
+
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        match answer.trim().to_lowercase().as_str() {
+            "o" | "overwrite" => return Ok(CollisionPolicy::Overwrite),
+            "s" | "skip" | "" => return Ok(CollisionPolicy::Skip),
+            "n" | "suffix" => return Ok(CollisionPolicy::Suffix),
+            _ => println!("Please answer o, s or n"),
+        }
+    }
+}
+
+/// Filename options from the CLI, falling back to the user config
+fn name_options(cli: &Cli, config: &Config) -> Result<NameOptions> {
+    let template = cli.name_template.clone()
+        .or_else(|| config.filename_template.clone())
+        .unwrap_or_else(|| DEFAULT_TEMPLATE.to_string());
+    validate_template(&template)?;
+
+    Ok(NameOptions {
+        template,
+        material: cli.material.clone().or_else(|| config.material.clone()),
+        preset: cli.preset.clone(),
+        sequence: cli.serial,
+    })
+}
+
+/// Generate a lens cell from the CLI arguments
+fn run_lens_cell(cli: &Cli, naming: NameOptions) -> Result<()> {
+    let diameter = cli.lens_diameter
+        .ok_or_else(|| anyhow::anyhow!("Lens diameter is required. Use --lens-diameter <MM>"))?;
+    let edge_thickness = cli.edge_thickness
+        .ok_or_else(|| anyhow::anyhow!("Lens edge thickness is required. Use --edge-thickness <MM>"))?;
+    let lens = LensSpec::new(diameter, edge_thickness, cli.front_radius, cli.back_radius)?;
+
+    let seat = cli.ring_type.map_or(RingType::Convex, RingType::from);
+    let mut params = LensCellParameters::new(lens, seat)?;
+    if let Some(wall) = cli.cell_wall {
+        params.wall_thickness = wall;
+    }
+    params.naming = naming;
+
+    if !cli.skip_validation {
+        params.validate()?;
+        validate_for_printing(&params.ring_parameters()?)?;
+    }
+
+    let layout = params.layout();
+    println!("Generating {} lens cell for a {:.1}mm lens...", seat, diameter);
+    println!("  Barrel: {:.1}mm outer diameter, {:.1}mm high", 2.0 * params.outer_radius(), layout.barrel_height);
+    println!("  Lens edge sits {:.2}mm above the barrel base", layout.lens_edge_z);
+    println!("  Seat contact diameter: {:.2}mm", layout.seat_contact_diameter);
+    println!("  Retainer contact diameter: {:.2}mm", layout.retainer_contact_diameter);
+    println!("  Clear aperture: {:.1}mm", 2.0 * params.aperture_radius());
+
+    let statuses = write_parts(cli, &generate_lens_cell(&params)?)?;
+
+    if cli.manufacturing_info {
+        print_manufacturing_info(&params.ring_parameters()?);
+    }
+
+    print_statuses(statuses);
+    Ok(())
+}
+
+/// Write a ring with bayonet lugs and its socket
+fn run_bayonet(cli: &Cli, params: &RingParameters) -> Result<()> {
+    println!("Generating {} ring with a bayonet socket...", params.ring_type);
+    let statuses = write_parts(cli, &generate_bayonet_parts(params)?)?;
+
+    if cli.manufacturing_info {
+        print_manufacturing_info(params);
+    }
+
+    print_statuses(statuses);
+    if let Some(ref bayonet) = params.bayonet {
+        println!("  Insert with the lugs in the socket slots, then turn {:.0}° counter-clockwise to lock", bayonet.twist);
+    }
+    Ok(())
+}
+
+/// Design and write a saddle for a cylindrical lens
+fn run_saddle(cli: &Cli, naming: NameOptions) -> Result<()> {
+    let length = cli.lens_length
+        .ok_or_else(|| anyhow::anyhow!("Lens length is required. Use --lens-length <MM>"))?;
+    let width = cli.lens_width
+        .ok_or_else(|| anyhow::anyhow!("Lens width is required. Use --lens-width <MM>"))?;
+    let radius = cli.cylinder_radius
+        .ok_or_else(|| anyhow::anyhow!("Cylinder radius is required. Use --cylinder-radius <MM>"))?;
+    let surface = AsphereSpec { radius, conic: cli.conic, coefficients: cli.asphere_coeffs.clone() };
+
+    let mut params = SaddleParameters::new(length, width, surface)?;
+    params.axis = cli.cylinder_axis;
+    if let Some(height) = cli.height {
+        params.height = height;
+    }
+    if let Some(wall) = cli.saddle_wall {
+        params.wall = wall;
+    }
+    params.naming = naming;
+
+    if !cli.skip_validation {
+        params.validate()?;
+        validate_for_printing(&params.ring_parameters()?)?;
+    }
+
+    let (lowest, highest) = params.seat_range();
+    println!("Generating saddle for a {:.1} x {:.1}mm cylindrical lens...", length, width);
+    println!("  Cylinder: {:.2}mm radius, axis at {:.0}°", radius, params.axis);
+    println!("  Seat: {:.2}mm at the vertex line, {:.2}-{:.2}mm across the lens", params.seat_height(0.0), lowest, highest);
+    println!("  Rim: {:.2}mm high, {:.1}mm wide", params.rim_height(), params.wall);
+    let (aperture_u, aperture_v) = params.aperture();
+    println!("  Clear aperture: {:.1} x {:.1}mm", 2.0 * aperture_u, 2.0 * aperture_v);
+
+    let statuses = write_parts(cli, &generate_saddle(&params)?)?;
+
+    if cli.manufacturing_info {
+        print_manufacturing_info(&params.ring_parameters()?);
+    }
+
+    print_statuses(statuses);
+    Ok(())
+}
+
+/// Revolve a custom profile into a ring and write it
+fn run_profile(cli: &Cli, path: &std::path::Path, naming: NameOptions) -> Result<()> {
+    let profile = CustomProfile::load(path)?;
+    let mut params = profile.ring_parameters()?;
+    params.naming = naming;
+
+    if !cli.skip_validation {
+        validate_for_printing(&params)?;
+    }
+
+    println!("Generating ring from profile {}...", path.display());
+    println!("  {} profile points", profile.points.len());
+    println!(
+        "  Bore: {:.2}mm, outer: {:.2}mm, height: {:.2}mm",
+        params.inner_diameter, params.outer_diameter, params.height
+    );
+    println!("  Material volume: {:.2}cm³", profile.volume() / 1000.0);
+
+    let statuses = write_parts(cli, &generate_profile_ring(&profile, &params))?;
+
+    if cli.manufacturing_info {
+        print_manufacturing_info(&params);
+    }
+
+    print_statuses(statuses);
+    Ok(())
+}
+
+/// Write a V-groove ring and its ball part
+fn run_kinematic(cli: &Cli, params: &RingParameters) -> Result<()> {
+    println!("Generating {} ring with kinematic V-grooves and a ball part...", params.ring_type);
+    let statuses = write_parts(cli, &generate_kinematic_parts(params)?)?;
+
+    if cli.manufacturing_info {
+        print_manufacturing_info(params);
+    }
+
+    print_statuses(statuses);
+    if let Some(ref kinematic) = params.kinematic {
+        let angles = geometry::contact_angles(params).map(|angle| format!("{:.0}°", angle.to_degrees().rem_euclid(360.0)));
+        println!("  V-grooves: {:.0}° at {}, {:.2}mm deep", kinematic.groove_angle, angles.join(", "), kinematic.depth());
+        println!(
+            "  Balls: {:.2}mm on a {:.2}mm circle, {:.2}mm above the groove bottoms",
+            kinematic.ball_diameter,
+            2.0 * kinematic.ball_circle_radius(params.inner_diameter / 2.0, params.outer_diameter / 2.0),
+            kinematic.contact_height()
+        );
+        println!("  Seated gap between the ring and the ball part: {:.2}mm", kinematic.gap());
+    }
+    Ok(())
+}
+
+/// Write several parts, asking how to handle existing files if needed
+fn write_parts(cli: &Cli, parts: &[(String, Vec<stl_io::Triangle>)]) -> Result<Vec<OutputStatus>> {
+    let output_dir = cli.output_dir.as_deref();
+    match write_part_files(parts, output_dir, cli.on_collision.into()) {
+        Err(e) => match e.downcast_ref::<OutputExists>() {
+            Some(existing) => write_part_files(parts, output_dir, prompt_collision(existing)?),
+            None => Err(e),
+        },
+        statuses => statuses,
+    }
+}
+
+/// Report which parts were written and which were skipped
+fn print_statuses(statuses: Vec<OutputStatus>) {
+    for status in statuses {
+        match status {
+            OutputStatus::Written(path) => println!("\n✓ Successfully generated: {}", path),
+            OutputStatus::Skipped(path) => println!("\n• Skipped, file already exists: {}", path),
+        }
+    }
+}
+
+/// Write a set of rings at stepped eccentricities
+fn run_eccentric_set(cli: &Cli, params: &RingParameters, count: usize) -> Result<()> {
+    if count < 2 {
+        return Err(anyhow::anyhow!("An eccentric set needs at least 2 rings"));
+    }
+    if cli.eccentric_step <= 0.0 {
+        return Err(anyhow::anyhow!("Eccentricity step must be positive"));
+    }
+    if !cli.skip_validation {
+        for ring in eccentric_set(params, count, cli.eccentric_step) {
+            validate_for_printing(&ring)?;
+        }
+    }
+
+    println!("Generating {} {} rings at {:.2}mm eccentricity steps...", count, params.ring_type, cli.eccentric_step);
+    let statuses = write_parts(cli, &generate_eccentric_set(params, count, cli.eccentric_step)?)?;
+
+    if cli.manufacturing_info {
+        print_manufacturing_info(params);
+    }
+
+    print_statuses(statuses);
+    println!(
+        "  Eccentricity: 0-{:.2}mm; the index mark at {:.0}° points the way each bore is offset",
+        (count - 1) as f32 * cli.eccentric_step, cli.eccentric_toward
+    );
+    println!("  Turning a ring in its barrel moves the lens on a circle of that radius");
+    Ok(())
 }
 
-impl From<CliRingType> for RingType {
-    fn from(cli_type: CliRingType) -> Self {
-        match cli_type {
-            CliRingType::Convex => RingType::Convex,
-            CliRingType::Concave => RingType::Concave,
-            CliRingType::ThreePoint => RingType::ThreePoint,
+/// Design and write the spacers for a lens prescription
+fn run_spacer_stack(cli: &Cli, path: &std::path::Path, naming: NameOptions) -> Result<()> {
+    let prescription = Prescription::load(path)?;
+    let spacers = design_spacers(&prescription, &naming)?;
+
+    if !cli.skip_validation {
+        for spacer in &spacers {
+            validate_for_printing(&spacer.params)?;
         }
     }
+
+    println!("Generating {} spacers for {}...", spacers.len(), path.display());
+    let parts: Vec<(String, Vec<stl_io::Triangle>)> = spacers.iter()
+        .map(|spacer| (spacer.filename.clone(), generate_stack_spacer(spacer)))
+        .collect();
+    let statuses = write_parts(cli, &parts)?;
+
+    println!("\nStack summary:\n{}", stack_summary(&prescription, &spacers));
+
+    let skipped = statuses.iter().filter(|status| matches!(status, OutputStatus::Skipped(_))).count();
+    println!("\n✓ Generated {} spacers, skipped {}", statuses.len() - skipped, skipped);
+    Ok(())
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    let naming = name_options(&cli, &Config::load())?;
     
     // Check if UI mode is explicitly requested
     if cli.ui {
@@ -75,60 +1149,194 @@ fn main() -> Result<()> {
         // Small delay to let user read the message
         std::thread::sleep(std::time::Duration::from_millis(1500));
         
-        return ui::run_ui();
+        return ui::run_ui(cli.on_collision.into(), naming);
     }
     
     // Check if no CLI arguments provided (default to UI mode)
-    if cli.ring_type.is_none() && cli.outer_diameter.is_none() && cli.inner_diameter.is_none() {
+    if cli.ring_type.is_none() && cli.outer_diameter.is_none() && cli.inner_diameter.is_none() && !cli.lens_cell && !cli.saddle && cli.stack.is_none() && cli.profile.is_none() {
         println!("🔬 Starting Optics Ring Generator in interactive mode...");
         println!("Press F1 or 'h' for help, 'q' to quit\n");
         
         // Small delay to let user read the message
         std::thread::sleep(std::time::Duration::from_millis(1500));
         
-        return ui::run_ui();
+        return ui::run_ui(cli.on_collision.into(), naming);
     }
     
     // CLI mode - existing functionality
     println!("Optics Ring Generator v0.1.0");
     println!("============================");
     
+    if cli.lens_cell {
+        return run_lens_cell(&cli, naming);
+    }
+    if cli.saddle {
+        return run_saddle(&cli, naming);
+    }
+    if let Some(ref stack) = cli.stack {
+        return run_spacer_stack(&cli, std::path::Path::new(stack), naming);
+    }
+    if let Some(ref profile) = cli.profile {
+        return run_profile(&cli, std::path::Path::new(profile), naming);
+    }
+
     // Validate required CLI arguments
     let ring_type = cli.ring_type.ok_or_else(|| {
-        anyhow::anyhow!("Ring type is required. Use --ring-type cx|cc|3p or run with --ui for interactive mode")
+        anyhow::anyhow!("Ring type is required. Use --ring-type cx|cc|3p|rr|sp|fx|tg|sc|cn or run with --ui for interactive mode")
     })?;
+    let ring_type = RingType::from(ring_type);
+
+    let thread = thread_spec(&cli)?;
+    if ring_type == RingType::RetainingRing && thread.is_none() {
+        return Err(anyhow::anyhow!("Retaining rings need a thread. Use --thread SM1 or --thread M25x0.5"));
+    }
+
+    // The threaded wall takes its diameter from the thread, so it may be omitted
+    let threaded_outer = thread.as_ref().filter(|t| t.side == ThreadSide::External).map(|t| t.major_diameter);
+    let threaded_inner = thread.as_ref().filter(|t| t.side == ThreadSide::Internal).map(|t| t.minor_diameter());
     
-    let outer_diameter = cli.outer_diameter.ok_or_else(|| {
+    let outer_diameter = cli.outer_diameter.or(threaded_outer).ok_or_else(|| {
         anyhow::anyhow!("Outer diameter is required. Use --outer-diameter <MM> or run with --ui for interactive mode")
     })?;
     
-    let inner_diameter = cli.inner_diameter.ok_or_else(|| {
+    let inner_diameter = cli.inner_diameter.or(threaded_inner).ok_or_else(|| {
         anyhow::anyhow!("Inner diameter is required. Use --inner-diameter <MM> or run with --ui for interactive mode")
     })?;
     
     // Create ring parameters
-    let ring_type = RingType::from(ring_type);
-    let params = RingParameters::new(ring_type, outer_diameter, inner_diameter)?;
+    let mut params = RingParameters::new(ring_type, outer_diameter, inner_diameter)?;
+    if let Some(thread) = thread {
+        params = params.with_thread(thread)?;
+    }
+    params.features = orientation_features(&cli);
+    params.label = label_spec(&cli, ring_type);
+    params.vents = vent_spec(&cli, ring_type);
+    params.holes = hole_spec(&cli);
+    params.flexure = flexure_spec(&cli, ring_type);
+    params.bayonet = bayonet_spec(&cli);
+    params.grip = grip_spec(&cli);
+    params.tangent = tangent_spec(&cli, ring_type);
+    params.corner = corner_spec(&cli, ring_type);
+    params.cone = cone_spec(&cli, ring_type);
+    params.asphere = asphere_spec(&cli, ring_type);
+    params.aperture = aperture_spec(&cli);
+    params.wedge = cli.wedge.map(|angle| WedgeSpec { angle, orientation: cli.wedge_toward });
+    params.kinematic = kinematic_spec(&cli);
+    params.eccentric = cli.eccentric.or(cli.eccentric_set.map(|_| 0.0))
+        .map(|offset| EccentricSpec { offset, direction: cli.eccentric_toward });
+    if cli.eccentric_set.is_some() {
+        params.features.mark = Some(IndexMark { style: cli.mark_style.into(), angle: cli.eccentric_toward });
+    }
+    if let Some(height) = cli.height {
+        if height <= 0.0 {
+            return Err(anyhow::anyhow!("Height must be positive"));
+        }
+        params.height = height;
+    }
+    params.naming = naming;
     
     // Validate parameters if not skipped
     if !cli.skip_validation {
         validate_for_printing(&params)?;
     }
     
-    // Generate STL file
-    let output_path = generate_stl_file(&params, cli.output_dir.as_deref())?;
+    if params.bayonet.is_some() {
+        return run_bayonet(&cli, &params);
+    }
+    if params.kinematic.is_some() {
+        return run_kinematic(&cli, &params);
+    }
+    if let Some(count) = cli.eccentric_set {
+        return run_eccentric_set(&cli, &params, count);
+    }
+
+    // Generate STL file, asking how to handle an existing file if needed
+    let output_dir = cli.output_dir.as_deref();
+    let status = match generate_stl_file(&params, output_dir, cli.on_collision.into()) {
+        Err(e) => match e.downcast_ref::<OutputExists>() {
+            Some(existing) => generate_stl_file(&params, output_dir, prompt_collision(existing)?)?,
+            None => return Err(e),
+        },
+        status => status?,
+    };
     
     // Show manufacturing information if requested
     if cli.manufacturing_info {
         print_manufacturing_info(&params);
     }
     
+    let output_path = match status {
+        OutputStatus::Written(path) => path,
+        OutputStatus::Skipped(path) => {
+            println!("\n• Skipped, file already exists: {}", path);
+            return Ok(());
+        }
+    };
     println!("\n✓ Successfully generated: {}", output_path);
-    println!("  Ring type: {} ({})", ring_type, match ring_type {
-        RingType::Convex => "Convex",
-        RingType::Concave => "Concave", 
-        RingType::ThreePoint => "Three-point",
-    });
+    println!("  Ring type: {} ({})", ring_type, ring_type.name());
+    if let Some(ref aperture) = params.aperture {
+        let (inner_x, inner_y) = aperture.size(params.inner_diameter, 0.0);
+        let (outer_x, outer_y) = aperture.size(params.inner_diameter, (params.outer_diameter - params.inner_diameter) / 2.0);
+        println!("  Aperture: {:.1} x {:.1}mm {}, frame {:.1} x {:.1}mm", inner_x, inner_y, aperture.shape, outer_x, outer_y);
+    }
+    if let Some(ref thread) = params.thread {
+        println!("  Thread: {}", thread);
+    }
+    if let (Some(ref wedge), Some((highest, lowest))) = (&params.wedge, geometry::wedge_heights(&params)) {
+        println!("  Wedge: {:.2}° rising toward {:.0}°", wedge.angle, wedge.orientation);
+        println!("  Seat height around the ring: {:.3}mm max, {:.3}mm min", highest, lowest);
+    }
+    if let Some(ref eccentric) = params.eccentric {
+        println!("  Eccentricity: {:.2}mm, bore offset toward {:.0}°", eccentric.offset, eccentric.direction);
+        println!("  Turn the ring in its barrel to move the lens on a {:.2}mm radius circle", eccentric.offset);
+    }
+    if let Some(ref label) = params.label {
+        println!("  Label: {}", naming::render_label(&label.template, &params));
+    }
+    if let Some(across_flats) = params.grip.as_ref().and_then(|grip| grip.across_flats(params.outer_diameter / 2.0)) {
+        println!("  Wrench size: {:.2}mm across flats", across_flats);
+    }
+    if let Some(ref flexure) = params.flexure {
+        let report = flexure.report(&params);
+        println!("  Fingers: {} x {:.1}mm long, {:.2}mm thick, {:.2}mm preload", flexure.fingers, flexure.beam_length, flexure.beam_thickness, flexure.preload);
+        println!("  Finger stiffness: {:.1} N/mm", report.finger_stiffness);
+        println!("  Radial preload: {:.2} N per finger ({:.1}MPa at the roots)", report.preload_force, report.root_stress);
+        println!("  Decenter stiffness: {:.1} N/mm, {:.1}µm sag per 10g of lens", report.lateral_stiffness, 0.0981 / report.lateral_stiffness * 1000.0);
+        println!("  Axial hold by friction: lenses up to {:.0}g", report.holding_mass);
+    }
+    if let Some(ref tangent) = params.tangent {
+        let section = tangent.section(params.inner_diameter / 2.0, params.outer_diameter / 2.0, params.height);
+        println!("  Toroid radius: {:.2}mm for a {:.2}mm lens surface", section.radius, tangent.lens_radius);
+        println!("  Toroid section center: {:.2}mm diameter, {:.2}mm above the base", 2.0 * section.center_r, section.center_z);
+        println!("  Contact: {:.2}mm diameter at {:.2}mm height", 2.0 * section.contact_r, section.contact_z);
+    }
+    if let Some(ref corner) = params.corner {
+        let contact = corner.contact(params.inner_diameter / 2.0, params.height);
+        println!("  Contact: {:.3}mm diameter at {:.3}mm height", 2.0 * contact.contact_r, contact.contact_z);
+        println!("  Lens vertex: {:.3}mm above the ring base", contact.vertex_z);
+    }
+    if let Some(ref asphere) = params.asphere {
+        let (inner_radius, outer_radius) = (params.inner_diameter / 2.0, params.outer_diameter / 2.0);
+        println!("  Aspheric seat: {:.2}mm radius, conic {:.3}, {} coefficients", asphere.radius, asphere.conic, asphere.coefficients.len());
+        println!(
+            "  Seat height: {:.3}mm at the bore, {:.3}mm at the outer edge",
+            asphere.height_at(inner_radius, inner_radius, outer_radius, params.height),
+            asphere.height_at(outer_radius, inner_radius, outer_radius, params.height)
+        );
+    }
+    if let Some(ref cone) = params.cone {
+        let (inner_radius, outer_radius) = (params.inner_diameter / 2.0, params.outer_diameter / 2.0);
+        println!(
+            "  Cone: {:.1}° half-angle, {:.2}mm high at the bore and {:.2}mm at the outer edge",
+            cone.half_angle,
+            cone.height_at(inner_radius, inner_radius, outer_radius, params.height),
+            cone.height_at(outer_radius, inner_radius, outer_radius, params.height)
+        );
+        if let Some(landing) = cone.landing(inner_radius, outer_radius, params.height) {
+            println!("  Contact: {:.3}mm diameter at {:.3}mm height", 2.0 * landing.contact_r, landing.contact_z);
+            println!("  Lens vertex: {:.3}mm above the ring base", landing.vertex_z);
+        }
+    }
     
     Ok(())
 }
@@ -136,6 +1344,32 @@ fn main() -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
+    use stl_io::Triangle;
+
+    /// Every edge must be shared by exactly two triangles with opposite winding
+    fn is_watertight(triangles: &[Triangle]) -> bool {
+        let key = |v: &stl_io::Vector<f32>| {
+            let q = |c: f32| (c * 1e4).round() as i64;
+            (q(v[0]), q(v[1]), q(v[2]))
+        };
+        let mut edges = HashMap::new();
+        for triangle in triangles {
+            let [a, b, c] = &triangle.vertices;
+            for (from, to) in [(a, b), (b, c), (c, a)] {
+                *edges.entry((key(from), key(to))).or_insert(0) += 1;
+            }
+        }
+        edges.iter().all(|(&(from, to), &count)| count == 1 && edges.get(&(to, from)) == Some(&1))
+    }
+
+    /// Enclosed volume; positive when the triangles face outward
+    fn mesh_volume(triangles: &[Triangle]) -> f32 {
+        triangles.iter().map(|t| {
+            let v = |i: usize| nalgebra::Vector3::new(t.vertices[i][0], t.vertices[i][1], t.vertices[i][2]);
+            v(0).dot(&v(1).cross(&v(2))) / 6.0
+        }).sum()
+    }
     
     #[test]
     fn test_ring_parameters_validation() {
@@ -160,4 +1394,1090 @@ mod tests {
         let params = RingParameters::new(RingType::ThreePoint, 40.0, 20.5).unwrap();
         assert_eq!(params.filename(), "3P-20.5.stl");
     }
+
+    #[test]
+    fn test_job_queue_reports_per_item_status() {
+        let dir = std::env::temp_dir().join("optics-ring-generator-queue-test");
+        let _ = std::fs::remove_dir_all(&dir);
+        let output_dir = dir.to_string_lossy().to_string();
+
+        let mut queue = ui::JobQueue::default();
+        queue.push(RingParameters::new(RingType::Convex, 50.0, 25.0).unwrap(), output_dir.clone());
+        // Wall thickness of 0.5mm fails printing validation
+        queue.push(RingParameters::new(RingType::Concave, 26.0, 25.0).unwrap(), output_dir);
+        assert_eq!(queue.generate_all(CollisionPolicy::Ask), None);
+
+        assert!(matches!(queue.items[0].status, ui::JobStatus::Done(_)));
+        assert!(matches!(queue.items[1].status, ui::JobStatus::Failed(_)));
+
+        // Retrying a failed item fails again without touching the completed one
+        queue.list_state.select(Some(1));
+        assert_eq!(queue.retry_selected(CollisionPolicy::Ask), None);
+        assert!(matches!(queue.items[0].status, ui::JobStatus::Done(_)));
+        assert!(matches!(queue.items[1].status, ui::JobStatus::Failed(_)));
+
+        // Regenerating the same ring asks before replacing the existing file
+        queue.list_state.select(Some(0));
+        let (index, existing) = queue.retry_selected(CollisionPolicy::Ask).unwrap();
+        assert_eq!(index, 0);
+        assert!(existing.ends_with("CX-25.0.stl"));
+        assert_eq!(queue.items[0].status, ui::JobStatus::Pending);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_mouse_click_selects_ring_type_and_focuses_fields() {
+        use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+        use ratatui::layout::Rect;
+
+        let click = |column, row| MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+
+        let mut app = ui::AppState::default();
+        app.click_areas.set_field(ui::InputField::RingType, Rect::new(0, 0, 40, 6));
+        app.click_areas.set_field(ui::InputField::OuterDiameter, Rect::new(0, 6, 40, 3));
+
+        // Row 0 is the list border, so row 2 is the second ring type
+        app.handle_mouse(click(5, 2)).unwrap();
+        assert_eq!(app.ring_type, Some(RingType::Concave));
+
+        app.handle_mouse(click(5, 7)).unwrap();
+        assert_eq!(app.current_field, ui::InputField::OuterDiameter);
+
+        // The thread field only joins the form for retaining rings
+        assert!(!app.visible_fields().contains(&ui::InputField::Thread));
+        app.handle_mouse(click(5, 4)).unwrap();
+        assert_eq!(app.ring_type, Some(RingType::RetainingRing));
+        assert!(app.visible_fields().contains(&ui::InputField::Thread));
+    }
+
+    #[test]
+    fn test_directory_browser_completion_folders_and_collisions() {
+        let dir = std::env::temp_dir().join("optics-ring-generator-browser-test");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("rings-cx")).unwrap();
+        std::fs::create_dir_all(dir.join("rings-cc")).unwrap();
+        std::fs::create_dir_all(dir.join("spacers")).unwrap();
+        std::fs::write(dir.join("CX-25.0.stl"), b"").unwrap();
+
+        let base = format!("{}/", dir.display());
+        let (completed, candidates) = ui::complete_path(&format!("{}sp", base));
+        assert_eq!(completed, format!("{}spacers{}", base, std::path::MAIN_SEPARATOR));
+        assert_eq!(candidates, vec!["spacers"]);
+
+        // Ambiguous prefixes complete up to the common part
+        let (completed, candidates) = ui::complete_path(&format!("{}ri", base));
+        assert_eq!(completed, format!("{}rings-c", base));
+        assert_eq!(candidates.len(), 2);
+
+        let mut browser = ui::DirectoryBrowser::new(dir.clone()).unwrap();
+        browser.target_filename = Some("CX-25.0.stl".to_string());
+        assert_eq!(browser.collision(), Some(&dir.join("CX-25.0.stl")));
+
+        let created = browser.create_folder("assembly-a").unwrap();
+        assert!(created.is_dir());
+        assert_eq!(browser.get_selected_path(), Some(&created));
+        assert!(browser.create_folder("../escape").is_err());
+
+        browser.search("SPA");
+        assert_eq!(browser.get_selected_path(), Some(&dir.join("spacers")));
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_config_bookmarks_round_trip() {
+        let path = std::env::temp_dir().join("optics-ring-generator-config-test.toml");
+        let mut config = config::Config::default();
+        assert!(config.toggle_bookmark(std::path::Path::new("/projects/telescope")));
+        config.save_to(&path).unwrap();
+
+        let mut loaded = config::Config::load_from(&path).unwrap();
+        assert_eq!(loaded, config);
+        assert!(!loaded.toggle_bookmark(std::path::Path::new("/projects/telescope")));
+        assert!(loaded.bookmarks.is_empty());
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_collision_policies() {
+        let dir = std::env::temp_dir().join("optics-ring-generator-collision-test");
+        let _ = std::fs::remove_dir_all(&dir);
+        let output_dir = dir.to_str();
+        let params = RingParameters::new(RingType::Convex, 50.0, 25.0).unwrap();
+
+        let first = generate_stl_file(&params, output_dir, CollisionPolicy::Ask).unwrap();
+        assert_eq!(first, OutputStatus::Written(dir.join("CX-25.0.stl").to_string_lossy().to_string()));
+
+        let err = generate_stl_file(&params, output_dir, CollisionPolicy::Ask).unwrap_err();
+        assert_eq!(err.downcast_ref::<OutputExists>(), Some(&OutputExists(dir.join("CX-25.0.stl"))));
+
+        assert!(matches!(
+            generate_stl_file(&params, output_dir, CollisionPolicy::Skip).unwrap(),
+            OutputStatus::Skipped(_)
+        ));
+        assert_eq!(
+            generate_stl_file(&params, output_dir, CollisionPolicy::Suffix).unwrap(),
+            OutputStatus::Written(dir.join("CX-25.0-2.stl").to_string_lossy().to_string())
+        );
+        assert_eq!(
+            generate_stl_file(&params, output_dir, CollisionPolicy::Suffix).unwrap(),
+            OutputStatus::Written(dir.join("CX-25.0-3.stl").to_string_lossy().to_string())
+        );
+
+        // No temporary files are left behind
+        let leftovers = std::fs::read_dir(&dir).unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name().to_string_lossy().ends_with(".tmp"))
+            .count();
+        assert_eq!(leftovers, 0);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_filename_templates() {
+        let mut params = RingParameters::new(RingType::Concave, 50.0, 25.4).unwrap();
+        params.naming = NameOptions {
+            template: "{type}_{outer:.0}x{inner:.2}-{material}-{date}-{seq:03}".to_string(),
+            material: Some("PETG".to_string()),
+            preset: None,
+            sequence: 7,
+        };
+        assert_eq!(
+            naming::render_filename_on(&params, "2026-10-19"),
+            "CC_50x25.40-PETG-2026-10-19-007.stl"
+        );
+
+        // Unset optional values drop their leading separator
+        params.naming.template = "{type}-{inner}-{preset}.stl".to_string();
+        assert_eq!(params.filename(), "CC-25.4.stl");
+
+        assert!(validate_template("{type}-{diameter}").is_err());
+        assert!(validate_template("{type}-{inner").is_err());
+        assert!(validate_template("rings/{type}").is_err());
+        assert!(validate_template(DEFAULT_TEMPLATE).is_ok());
+    }
+
+    #[test]
+    fn test_threaded_retaining_ring() {
+        let sm1: ThreadSpec = "SM1".parse().unwrap();
+        assert!((sm1.major_diameter - 26.289).abs() < 1e-3);
+        assert!((sm1.pitch - 0.635).abs() < 1e-4);
+        assert_eq!(sm1.side, ThreadSide::External);
+
+        let metric: ThreadSpec = "M30x0.75 internal".parse().unwrap();
+        assert_eq!(metric.side, ThreadSide::Internal);
+        assert!("UNF1/4".parse::<ThreadSpec>().is_err());
+
+        // External SM1 retainer: the outer diameter comes from the thread
+        let params = RingParameters::new(RingType::RetainingRing, 30.0, 22.0)
+            .unwrap()
+            .with_thread(sm1)
+            .unwrap();
+        assert!((params.outer_diameter - 26.289).abs() < 1e-3);
+        assert_eq!(params.filename(), "RR-22.0.stl");
+        validate_for_printing(&params).unwrap();
+
+        let triangles = geometry::generate_ring_mesh(&params).unwrap();
+        assert!(is_watertight(&triangles));
+
+        // Volume lies between the solid at the minor and at the major diameter
+        let volume = mesh_volume(&triangles);
+        let annulus = |outer: f32| std::f32::consts::PI * (outer.powi(2) - 11.0f32.powi(2)) * params.height;
+        let thread = params.thread.as_ref().unwrap();
+        assert!(volume > annulus(thread.minor_diameter() / 2.0 - thread.clearance));
+        assert!(volume < annulus(thread.major_diameter / 2.0));
+
+        // Internal threads leave the bore at the thread minor diameter
+        let internal: ThreadSpec = "SM1 internal".parse().unwrap();
+        let params = RingParameters::new(RingType::RetainingRing, 32.0, 20.0)
+            .unwrap()
+            .with_thread(internal)
+            .unwrap();
+        assert!(is_watertight(&geometry::generate_ring_mesh(&params).unwrap()));
+        assert!(mesh_volume(&geometry::generate_ring_mesh(&params).unwrap()) > 0.0);
+    }
+
+    #[test]
+    fn test_lens_cell_parts_and_seat_position() {
+        // A flat lens rests on the rim of a concave seat, at the floor height
+        let flat = LensSpec::new(25.4, 3.0, 0.0, 0.0).unwrap();
+        let params = LensCellParameters::new(flat, RingType::Concave).unwrap();
+        let layout = params.layout();
+        assert!((layout.lens_edge_z - params.seat_thickness).abs() < 1e-4);
+        assert!(LensCellParameters::new(LensSpec::new(25.4, 3.0, 0.0, 0.0).unwrap(), RingType::ThreePoint).is_err());
+
+        // A convex front surface sits higher on a convex seat, touching inside the lens edge
+        let biconvex = LensSpec::new(25.4, 2.0, 50.0, 50.0).unwrap();
+        assert!(biconvex.center_thickness() > 2.0);
+        let params = LensCellParameters::new(biconvex, RingType::Convex).unwrap();
+        let layout = params.layout();
+        assert!(layout.lens_edge_z > params.seat_thickness);
+        assert!(layout.seat_contact_diameter < 25.4);
+        assert!(layout.barrel_height > layout.lens_edge_z + 2.0 + params.retainer_height);
+
+        let parts = lens_cell::generate_lens_cell(&params).unwrap();
+        let names: Vec<&str> = parts.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["CX-25.4-barrel.stl", "CX-25.4-retainer.stl"]);
+        for (_, triangles) in &parts {
+            assert!(is_watertight(triangles));
+            assert!(mesh_volume(triangles) > 0.0);
+        }
+
+        // Radii tighter than the lens semi-diameter are not spherical caps
+        assert!(LensSpec::new(25.4, 2.0, 10.0, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_spacer_stack_design() {
+        let dir = std::env::temp_dir().join("optics-ring-generator-stack-test");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("stack.toml");
+        std::fs::write(&path, r#"
+            [[element]]
+            name = "L1"
+            diameter = 25.4
+            center_thickness = 4.0
+            gap = 3.0
+
+            [[element]]
+            name = "L2"
+            diameter = 25.4
+            center_thickness = 5.0
+            front_radius = 50.0
+            back_radius = -50.0
+            gap = 2.0
+
+            [[element]]
+            diameter = 25.4
+            center_thickness = 4.0
+        "#).unwrap();
+
+        let prescription = Prescription::load(&path).unwrap();
+        assert_eq!(prescription.vertex_positions(), vec![0.0, 7.0, 14.0]);
+        let spacers = design_spacers(&prescription, &NameOptions::default()).unwrap();
+        assert_eq!(spacers.len(), 2);
+
+        // L2's convex front curves away from L1, so the spacer grows toward the wall
+        let first = &spacers[0];
+        assert!(first.inner_length > 3.0);
+        assert!(first.outer_length > first.inner_length);
+        assert!(first.params.height >= first.outer_length - 1e-4);
+
+        // Same-size spacers are numbered so they don't overwrite each other
+        assert_eq!(first.filename, "SP-22.4-1.stl");
+        assert_eq!(spacers[1].filename, "SP-22.4-2.stl");
+        assert!(stack_summary(&prescription, &spacers).contains("SP-22.4-2.stl"));
+
+        for spacer in &spacers {
+            let triangles = generate_stack_spacer(spacer);
+            assert!(is_watertight(&triangles));
+            assert!(mesh_volume(&triangles) > 0.0);
+        }
+
+        // Surfaces curving toward each other can close the gap before the spacer
+        let mut tight = prescription.clone();
+        tight.elements[0].gap = 1.0;
+        tight.elements[1].front_radius = -50.0;
+        assert!(design_spacers(&tight, &NameOptions::default()).is_err());
+
+        // Flat spacers take their length from the height
+        let mut params = RingParameters::new(RingType::Spacer, 25.2, 22.4).unwrap();
+        params.height = 3.5;
+        let triangles = geometry::generate_ring_mesh(&params).unwrap();
+        assert!(is_watertight(&triangles));
+        let expected = std::f32::consts::PI * (12.6f32.powi(2) - 11.2f32.powi(2)) * 3.5;
+        assert!((mesh_volume(&triangles) - expected).abs() / expected < 0.01);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_orientation_features_keep_rings_closed() {
+        use features::{FlatSpec, IndexMark, KeySpec, KeyStyle, MarkStyle};
+
+        let vertices = |triangles: &[Triangle]| -> Vec<[f32; 3]> {
+            triangles.iter().flat_map(|t| t.vertices.iter().map(|v| [v[0], v[1], v[2]])).collect()
+        };
+
+        // Plain rings of every seat type are closed solids
+        for ring_type in [RingType::Convex, RingType::Concave, RingType::ThreePoint, RingType::Spacer] {
+            let params = RingParameters::new(ring_type, 40.0, 25.0).unwrap();
+            let triangles = geometry::generate_ring_mesh(&params).unwrap();
+            assert!(is_watertight(&triangles), "{} ring is not closed", ring_type);
+            assert!(mesh_volume(&triangles) > 0.0);
+        }
+
+        let mut params = RingParameters::new(RingType::Convex, 40.0, 25.0).unwrap();
+        params.features = OrientationFeatures {
+            key: Some(KeySpec { style: KeyStyle::Key, angle: 90.0, width: 3.0, depth: 1.0 }),
+            flat: Some(FlatSpec { angle: 0.0, depth: 1.5 }),
+            mark: Some(IndexMark { style: MarkStyle::Arrow, angle: 90.0 }),
+            clocking: 0.0,
+        };
+        validate_for_printing(&params).unwrap();
+        let triangles = geometry::generate_ring_mesh(&params).unwrap();
+        assert!(is_watertight(&triangles));
+        let points = vertices(&triangles);
+        // Nothing crosses the flat, and the key stands proud along +Y
+        assert!(points.iter().all(|p| p[0] <= 20.0 - 1.5 + 1e-3));
+        assert!(points.iter().any(|p| p[1] > 20.5));
+        // The arrow is engraved into the top face
+        assert!(points.iter().any(|p| p[1] > 13.0 && p[1] < 19.0 && p[0].abs() < 0.5 && p[2] < params.height - 0.3));
+
+        // Three-point pads follow the clocking angle
+        let mut params = RingParameters::new(RingType::ThreePoint, 40.0, 25.0).unwrap();
+        params.features = OrientationFeatures {
+            key: Some(KeySpec { style: KeyStyle::Keyway, angle: 30.0, width: 3.0, depth: 1.0 }),
+            flat: None,
+            mark: Some(IndexMark { style: MarkStyle::Notch, angle: 30.0 }),
+            clocking: 30.0,
+        };
+        let triangles = geometry::generate_ring_mesh(&params).unwrap();
+        assert!(is_watertight(&triangles));
+        let pad = 30.0f32.to_radians();
+        let top = |angle: f32| vertices(&triangles).iter()
+            .filter(|p| (p[1].atan2(p[0]) - angle).abs() < 0.01)
+            .map(|p| p[2])
+            .fold(0.0, f32::max);
+        assert!((top(pad) - params.height).abs() < 1e-3);
+        assert!(top(pad + std::f32::consts::PI / 3.0) < params.height * 0.7);
+
+        // Cuts must leave a printable wall
+        params.features.flat = Some(FlatSpec { angle: 0.0, depth: 7.0 });
+        assert!(validate_for_printing(&params).is_err());
+    }
+
+    #[test]
+    fn test_ring_labels() {
+        use label::{LabelPlacement, LabelSpec, LabelStyle, RingLabel};
+
+        // The default label names the ring like its file, on the wall of a seat ring
+        let mut params = RingParameters::new(RingType::Concave, 40.0, 25.4).unwrap();
+        assert_eq!(LabelPlacement::for_ring(RingType::Concave), LabelPlacement::Wall);
+        params.label = Some(LabelSpec { placement: LabelPlacement::for_ring(RingType::Concave), ..LabelSpec::default() });
+        let label = RingLabel::new(params.label.as_ref().unwrap(), &params).unwrap();
+        assert_eq!(label.text, "CC 25.4");
+
+        let plain = mesh_volume(&geometry::generate_ring_mesh(&RingParameters::new(RingType::Concave, 40.0, 25.4).unwrap()).unwrap());
+        let triangles = geometry::generate_ring_mesh(&params).unwrap();
+        assert!(is_watertight(&triangles));
+        assert!(mesh_volume(&triangles) < plain);
+
+        // The lens rests on the top face of a seat ring, so text there is rejected
+        let mut convex = RingParameters::new(RingType::Convex, 40.0, 25.0).unwrap();
+        let top = |style| LabelSpec { placement: LabelPlacement::Top, style, ..LabelSpec::default() };
+        assert!(RingLabel::new(&top(LabelStyle::Emboss), &convex).is_err());
+        assert!(RingLabel::new(&top(LabelStyle::Engrave), &convex).is_err());
+        convex.label = Some(top(LabelStyle::Emboss));
+        assert!(validate_for_printing(&convex).is_err());
+        let top_of = |triangles: &[Triangle]| triangles.iter().flat_map(|t| t.vertices).map(|v| v[2]).fold(f32::MIN, f32::max);
+        let convex_top = top_of(&geometry::generate_ring_mesh(&RingParameters::new(RingType::Convex, 40.0, 25.0).unwrap()).unwrap());
+        convex.label = Some(LabelSpec { style: LabelStyle::Emboss, placement: LabelPlacement::for_ring(RingType::Convex), ..LabelSpec::default() });
+        assert!((top_of(&geometry::generate_ring_mesh(&convex).unwrap()) - convex_top).abs() < 1e-5);
+
+        // Spacers have no seat and keep the top-face default
+        let mut spacer = RingParameters::new(RingType::Spacer, 40.0, 25.0).unwrap();
+        assert_eq!(LabelPlacement::for_ring(RingType::Spacer), LabelPlacement::Top);
+        spacer.label = Some(LabelSpec::default());
+        let spacer_plain = mesh_volume(&geometry::generate_ring_mesh(&RingParameters::new(RingType::Spacer, 40.0, 25.0).unwrap()).unwrap());
+        let triangles = geometry::generate_ring_mesh(&spacer).unwrap();
+        assert!(is_watertight(&triangles));
+        assert!(mesh_volume(&triangles) < spacer_plain);
+
+        // Embossed serial numbers on the wall add material
+        params.naming.sequence = 7;
+        params.label = Some(LabelSpec {
+            template: "SN {seq:03}".to_string(),
+            placement: LabelPlacement::Wall,
+            style: LabelStyle::Emboss,
+            ..LabelSpec::default()
+        });
+        assert_eq!(RingLabel::new(params.label.as_ref().unwrap(), &params).unwrap().text, "SN 007");
+        let triangles = geometry::generate_ring_mesh(&params).unwrap();
+        assert!(is_watertight(&triangles));
+        assert!(mesh_volume(&triangles) > plain);
+
+        // Text must use the stroke font and fit the ring
+        let label = |template: &str| LabelSpec { template: template.to_string(), ..LabelSpec::default() };
+        assert!(RingLabel::new(&label("SP"), &spacer).is_ok());
+        assert!(RingLabel::new(&label("25.4 µm"), &spacer).is_err());
+        assert!(RingLabel::new(&label("A VERY LONG LABEL THAT WRAPS AROUND"), &spacer).is_err());
+        let narrow = RingParameters::new(RingType::Spacer, 27.0, 25.0).unwrap();
+        assert!(RingLabel::new(&label("SP"), &narrow).is_err());
+    }
+
+    #[test]
+    fn test_vent_slots_and_grooves() {
+        let vents = |style, count, angle| Some(VentSpec { style, count, width: 1.0, depth: 0.5, angle });
+        let plain = RingParameters::new(RingType::Convex, 40.0, 25.0).unwrap();
+        let plain_volume = mesh_volume(&geometry::generate_ring_mesh(&plain).unwrap());
+
+        let mut params = plain.clone();
+        params.vents = vents(VentStyle::Both, 4, Some(0.0));
+        validate_for_printing(&params).unwrap();
+        let triangles = geometry::generate_ring_mesh(&params).unwrap();
+        assert!(is_watertight(&triangles));
+        assert!(mesh_volume(&triangles) < plain_volume);
+
+        // The slot at 0° runs out through the outer wall, and the groove opens the bore
+        let points: Vec<[f32; 3]> = triangles.iter().flat_map(|t| t.vertices.iter().map(|v| [v[0], v[1], v[2]])).collect();
+        let seat_edge = params.height - 7.5 * geometry::SEAT_CURVE_FRACTION;
+        assert!(points.iter().any(|p| (p[0] - 20.0).abs() < 1e-3 && p[1].abs() < 0.1 && (p[2] - (seat_edge - 0.5)).abs() < 1e-3));
+        assert!(points.iter().any(|p| (p[0] - 13.0).abs() < 1e-3 && p[1].abs() < 0.1 && p[2] == 0.0));
+
+        // Three-point vents default to between the contacts and may not cut a pad
+        let mut params = RingParameters::new(RingType::ThreePoint, 40.0, 25.0).unwrap();
+        params.vents = vents(VentStyle::Radial, 3, None);
+        validate_for_printing(&params).unwrap();
+        assert!(is_watertight(&geometry::generate_ring_mesh(&params).unwrap()));
+        params.vents = vents(VentStyle::Radial, 3, Some(0.0));
+        assert!(validate_for_printing(&params).is_err());
+
+        // With the CLI defaults a three-point ring gets vents that clear the pads,
+        // as do larger multiples of three
+        let cli = Cli::try_parse_from(["optics-ring-generator", "-r", "3p", "-o", "40", "-i", "25", "--vents", "radial"]).unwrap();
+        params.vents = vent_spec(&cli, RingType::ThreePoint);
+        assert_eq!(params.vents.as_ref().unwrap().count, 3);
+        validate_for_printing(&params).unwrap();
+        params.features.clocking = 25.0;
+        params.vents = vents(VentStyle::Radial, 6, None);
+        validate_for_printing(&params).unwrap();
+        let cli = Cli::try_parse_from(["optics-ring-generator", "-r", "cc", "-o", "40", "-i", "25", "--vents", "radial"]).unwrap();
+        assert_eq!(vent_spec(&cli, RingType::Concave).unwrap().count, 4);
+
+        // Too many slots break the contact annulus; deep grooves reach it
+        let mut params = plain.clone();
+        params.vents = vents(VentStyle::Radial, 40, None);
+        assert!(validate_for_printing(&params).is_err());
+        params.vents = Some(VentSpec { style: VentStyle::Axial, count: 4, width: 1.0, depth: 3.5, angle: None });
+        assert!(validate_for_printing(&params).is_err());
+    }
+
+    #[test]
+    fn test_radial_holes_and_bosses() {
+        let holes = |screw: &str, kind, boss| Some(HoleSpec {
+            screw: screw.to_string(),
+            kind,
+            count: 3,
+            angles: Vec::new(),
+            diameter: None,
+            height: None,
+            boss,
+        });
+        let mut plain = RingParameters::new(RingType::Convex, 40.0, 25.0).unwrap();
+        plain.height = 10.0;
+        let plain_volume = mesh_volume(&geometry::generate_ring_mesh(&plain).unwrap());
+
+        let mut params = plain.clone();
+        params.holes = holes("M3", HoleKind::Tapped, None);
+        validate_for_printing(&params).unwrap();
+        let triangles = geometry::generate_ring_mesh(&params).unwrap();
+        assert!(is_watertight(&triangles));
+        assert!(mesh_volume(&triangles) < plain_volume);
+
+        // The first hole is round, 2.5mm across, centered at 0° halfway up the seat
+        let points: Vec<[f32; 3]> = triangles.iter().flat_map(|t| t.vertices.iter().map(|v| [v[0], v[1], v[2]])).collect();
+        let center = (10.0 - 7.5 * geometry::SEAT_CURVE_FRACTION) / 2.0;
+        for radius in [12.5, 20.0] {
+            let rim: Vec<_> = points.iter().filter(|p| (p[0] - radius).abs() < 0.1 && p[1].abs() < 1.3).collect();
+            assert!(rim.iter().any(|p| (p[2] - center - 1.25).abs() < 1e-3));
+            assert!(rim.iter().any(|p| (p[2] - center + 1.25).abs() < 1e-3));
+        }
+
+        // Heat-set inserts fit this wall, but need a boss on a thinner one
+        params.holes = holes("M3", HoleKind::Insert, None);
+        assert!(validate_for_printing(&params).is_ok());
+        let mut thin = RingParameters::new(RingType::Convex, 32.0, 25.0).unwrap();
+        thin.height = 8.0;
+        thin.holes = holes("M3", HoleKind::Insert, None);
+        assert!(validate_for_printing(&thin).is_err());
+        thin.holes = holes("M3", HoleKind::Insert, Some(1.0));
+        validate_for_printing(&thin).unwrap();
+        let triangles = geometry::generate_ring_mesh(&thin).unwrap();
+        assert!(is_watertight(&triangles));
+        assert!(triangles.iter().flat_map(|t| t.vertices).any(|v| v[0] > 16.9));
+
+        // Three-point rings keep full-size holes below the relieved seat
+        let mut params = RingParameters::new(RingType::ThreePoint, 40.0, 25.0).unwrap();
+        params.height = 8.0;
+        params.holes = holes("M2", HoleKind::Tapped, None);
+        validate_for_printing(&params).unwrap();
+        assert!(is_watertight(&geometry::generate_ring_mesh(&params).unwrap()));
+
+        // Not enough wall above and below the hole, unknown screws, crowded holes
+        params.holes = holes("M4", HoleKind::Insert, None);
+        assert!(validate_for_printing(&params).is_err());
+        params.holes = holes("M7", HoleKind::Tapped, None);
+        assert!(validate_for_printing(&params).is_err());
+        params.holes = Some(HoleSpec { angles: vec![0.0, 5.0], ..holes("M2", HoleKind::Tapped, None).unwrap() });
+        assert!(validate_for_printing(&params).is_err());
+    }
+
+    #[test]
+    fn test_flexure_mount() {
+        let mut params = RingParameters::new(RingType::Flexure, 40.0, 25.0).unwrap();
+        params.height = 5.0;
+        assert_eq!(params.flexure, Some(FlexureSpec::default()));
+        assert_eq!(params.filename(), "FX-25.0.stl");
+        validate_for_printing(&params).unwrap();
+
+        let triangles = geometry::generate_ring_mesh(&params).unwrap();
+        assert!(is_watertight(&triangles));
+        let solid = std::f32::consts::PI * (20.0f32.powi(2) - 13.0f32.powi(2)) * 5.0;
+        assert!(mesh_volume(&triangles) < solid);
+
+        // The pads reach inside the lens edge by the preload
+        let closest = triangles.iter().flat_map(|t| t.vertices)
+            .map(|v| (v[0] * v[0] + v[1] * v[1]).sqrt())
+            .fold(f32::MAX, f32::min);
+        assert!((closest - 12.4).abs() < 1e-3);
+
+        // k = E b t³ / 4 L³, and the preload force follows from the interference
+        let report = params.flexure.as_ref().unwrap().report(&params);
+        let stiffness = 3500.0 * 5.0 * 1.2f32.powi(3) / (4.0 * 1000.0);
+        assert!((report.finger_stiffness - stiffness).abs() < 1e-3);
+        assert!((report.preload_force - stiffness * 0.1).abs() < 1e-4);
+        assert!((report.lateral_stiffness - 1.5 * stiffness).abs() < 1e-3);
+
+        // Too much preload overstresses the roots; long fingers don't fit
+        params.flexure = Some(FlexureSpec { preload: 1.0, ..FlexureSpec::default() });
+        assert!(validate_for_printing(&params).is_err());
+        params.flexure = Some(FlexureSpec { beam_length: 30.0, ..FlexureSpec::default() });
+        assert!(validate_for_printing(&params).is_err());
+        params.flexure = Some(FlexureSpec { fingers: 2, ..FlexureSpec::default() });
+        assert!(validate_for_printing(&params).is_err());
+        let narrow = RingParameters::new(RingType::Flexure, 30.0, 25.0).unwrap();
+        assert!(validate_for_printing(&narrow).is_err());
+    }
+
+    #[test]
+    fn test_bayonet_ring_and_socket() {
+        let mut params = RingParameters::new(RingType::Convex, 40.0, 25.0).unwrap();
+        params.height = 6.0;
+        params.bayonet = Some(BayonetSpec::default());
+        validate_for_printing(&params).unwrap();
+
+        let parts = generate_bayonet_parts(&params).unwrap();
+        assert_eq!(parts[0].0, "CX-25.0.stl");
+        assert_eq!(parts[1].0, "CX-25.0-socket.stl");
+        for (_, triangles) in &parts {
+            assert!(is_watertight(triangles));
+        }
+
+        // Lugs stand 1mm proud at the base; the socket grooves clear them by 0.2mm
+        let radius = |v: &stl_io::Vector<f32>| (v[0] * v[0] + v[1] * v[1]).sqrt();
+        let ring_points: Vec<_> = parts[0].1.iter().flat_map(|t| t.vertices).collect();
+        let lug = ring_points.iter().filter(|v| v[2] < 1.0).map(radius).fold(0.0, f32::max);
+        assert!((lug - 21.0).abs() < 1e-3);
+        assert!(ring_points.iter().filter(|v| v[2] > 2.5).all(|v| radius(v) < 20.0 + 1e-3));
+        let groove = parts[1].1.iter().flat_map(|t| t.vertices)
+            .map(|v| radius(&v))
+            .filter(|r| *r < 23.0)
+            .fold(0.0, f32::max);
+        assert!((groove - 21.4).abs() < 1e-3);
+
+        // Lugs sit at the clocking angle; the leading edge is lowered by the ramp
+        let tops: Vec<_> = ring_points.iter().filter(|v| radius(v) > 20.9 && v[1].abs() < 0.1 && v[0] > 0.0).map(|v| v[2]).collect();
+        assert!(tops.iter().any(|z| (z - 2.0 * (2.0 - 0.15) / 2.0).abs() < 1e-3));
+
+        // The twist must leave room for the detent, and the lugs need wall under the seat
+        params.bayonet = Some(BayonetSpec { twist: 30.0, ..BayonetSpec::default() });
+        assert!(validate_for_printing(&params).is_err());
+        params.bayonet = Some(BayonetSpec { lug_height: 4.5, ..BayonetSpec::default() });
+        assert!(validate_for_printing(&params).is_err());
+        params.bayonet = Some(BayonetSpec { lugs: 6, ..BayonetSpec::default() });
+        assert!(validate_for_printing(&params).is_err());
+    }
+
+    #[test]
+    fn test_grip_surfaces() {
+        let grip = |style, band| Some(GripSpec { style, pitch: 1.0, depth: 0.4, band });
+        let radius = |v: &stl_io::Vector<f32>| (v[0] * v[0] + v[1] * v[1]).sqrt();
+        let mut params = RingParameters::new(RingType::Concave, 40.0, 25.0).unwrap();
+        params.height = 8.0;
+
+        for style in [GripStyle::StraightKnurl, GripStyle::DiamondKnurl, GripStyle::Flutes, GripStyle::Octagon] {
+            params.grip = grip(style, Some((2.0, 6.0)));
+            validate_for_printing(&params).unwrap();
+            let triangles = geometry::generate_ring_mesh(&params).unwrap();
+            assert!(is_watertight(&triangles));
+
+            // Outside the band (and its 45° fade) the wall stays round
+            let points: Vec<_> = triangles.iter().flat_map(|t| t.vertices).collect();
+            let cut = points.iter().filter(|v| (1.99..6.01).contains(&v[2])).map(radius).fold(f32::MAX, f32::min);
+            assert!(cut < 19.65);
+            assert!(points.iter().filter(|v| v[2] < 0.5 || v[2] > 7.5).all(|v| radius(v) < 13.0 || radius(v) > 20.0 - 1e-3));
+        }
+
+        // A hexagon on an internally threaded retaining ring fits a wrench
+        let thread: ThreadSpec = "SM1 internal".parse().unwrap();
+        let mut params = RingParameters::new(RingType::RetainingRing, 36.0, 20.0).unwrap().with_thread(thread).unwrap();
+        params.grip = grip(GripStyle::Hexagon, None);
+        validate_for_printing(&params).unwrap();
+        let triangles = geometry::generate_ring_mesh(&params).unwrap();
+        assert!(is_watertight(&triangles));
+        let across_flats = params.grip.as_ref().unwrap().across_flats(18.0).unwrap();
+        assert!((across_flats - 36.0 * (std::f32::consts::PI / 6.0).cos()).abs() < 1e-3);
+        let closest = triangles.iter().flat_map(|t| t.vertices).map(|v| radius(&v)).filter(|r| *r > 15.0).fold(f32::MAX, f32::min);
+        assert!((closest - across_flats / 2.0).abs() < 1e-3);
+
+        // External threads leave nothing to grip; deep grooves break thin walls
+        let thread: ThreadSpec = "SM1".parse().unwrap();
+        let mut params = RingParameters::new(RingType::RetainingRing, 30.0, 20.0).unwrap().with_thread(thread).unwrap();
+        params.grip = grip(GripStyle::StraightKnurl, None);
+        assert!(validate_for_printing(&params).is_err());
+        let mut params = RingParameters::new(RingType::Convex, 28.0, 25.0).unwrap();
+        params.grip = grip(GripStyle::Octagon, None);
+        assert!(validate_for_printing(&params).is_err());
+    }
+
+    #[test]
+    fn test_tangent_seat() {
+        let mut params = RingParameters::new(RingType::Tangent, 40.0, 20.0).unwrap();
+        params.height = 6.0;
+        assert!(validate_for_printing(&params).is_err());
+        assert_eq!("toroidal".parse::<RingType>().unwrap(), RingType::Tangent);
+
+        // The lens surface and the toroid share height and slope at the contact
+        for lens_radius in [50.0, -50.0] {
+            let tangent = TangentSpec { lens_radius, contact_diameter: None };
+            params.tangent = Some(tangent.clone());
+            validate_for_printing(&params).unwrap();
+            let triangles = geometry::generate_ring_mesh(&params).unwrap();
+            assert!(is_watertight(&triangles));
+            assert!(mesh_volume(&triangles) > 0.0);
+
+            let section = tangent.section(10.0, 20.0, 6.0);
+            assert_eq!(section.radius, 25.0);
+            assert!((section.height_at(15.0) - 6.0).abs() < 1e-4);
+            let sphere_center = if lens_radius > 0.0 { 6.0 + 2275.0_f32.sqrt() } else { 6.0 - 2275.0_f32.sqrt() };
+            let lens = |r: f32| sphere_center - lens_radius.signum() * (2500.0 - r * r).sqrt();
+            let slope = |f: &dyn Fn(f32) -> f32| (f(15.01) - f(14.99)) / 0.02;
+            assert!((slope(&|r| section.height_at(r)) - slope(&lens)).abs() < 1e-2);
+
+            // Nowhere does the seat rise into the lens
+            let highest = triangles.iter().flat_map(|t| t.vertices).map(|v| v[2] - lens((v[0] * v[0] + v[1] * v[1]).sqrt()));
+            assert!(highest.fold(f32::MIN, f32::max) < 1e-3);
+        }
+
+        // The contact must fall on the seat and the lens must be larger than it
+        params.tangent = Some(TangentSpec { lens_radius: 50.0, contact_diameter: Some(39.5) });
+        assert!(validate_for_printing(&params).is_err());
+        params.tangent = Some(TangentSpec { lens_radius: 12.0, contact_diameter: None });
+        assert!(validate_for_printing(&params).is_err());
+
+        // Bore grooves are checked against the tangent contact, not mid-wall
+        let cli = Cli::try_parse_from([
+            "optics-ring-generator", "-r", "tg", "-o", "40", "-i", "20", "--lens-radius", "50",
+            "--contact-diameter", "22", "--vents", "axial", "--vent-depth", "1.5",
+        ]).unwrap();
+        params.tangent = tangent_spec(&cli, RingType::Tangent);
+        validate_for_printing(&params).unwrap();
+        params.vents = vent_spec(&cli, RingType::Tangent);
+        let error = validate_for_printing(&params).unwrap_err().to_string();
+        assert!(error.contains("22.00mm diameter"), "{error}");
+    }
+
+    #[test]
+    fn test_sharp_corner_seat() {
+        let mut params = RingParameters::new(RingType::SharpCorner, 40.0, 20.0).unwrap();
+        params.height = 5.0;
+        assert!(validate_for_printing(&params).is_err());
+
+        // A sharp edge puts the contact on the bore corner
+        params.corner = Some(CornerSpec { lens_radius: 50.0, relief: 0.0 });
+        validate_for_printing(&params).unwrap();
+        let contact = params.corner.as_ref().unwrap().contact(10.0, 5.0);
+        assert_eq!((contact.contact_r, contact.contact_z), (10.0, 5.0));
+        assert!((contact.vertex_z - (5.0 + 2400.0_f32.sqrt() - 50.0)).abs() < 1e-4);
+        let triangles = geometry::generate_ring_mesh(&params).unwrap();
+        assert!(is_watertight(&triangles));
+
+        // A relief radius moves the contact onto the rounding, a lens radius from the lens center
+        params.corner = Some(CornerSpec { lens_radius: 50.0, relief: 0.5 });
+        validate_for_printing(&params).unwrap();
+        let triangles = geometry::generate_ring_mesh(&params).unwrap();
+        assert!(is_watertight(&triangles));
+        assert!(mesh_volume(&triangles) > 0.0);
+        let contact = params.corner.as_ref().unwrap().contact(10.0, 5.0);
+        assert!(contact.contact_r > 10.0 && contact.contact_r < 10.5);
+        let lens_center = contact.vertex_z + 50.0;
+        assert!(((contact.contact_r.powi(2) + (lens_center - contact.contact_z).powi(2)).sqrt() - 50.0).abs() < 1e-3);
+        let radius = |v: &stl_io::Vector<f32>| (v[0] * v[0] + v[1] * v[1]).sqrt();
+        let bore_top = triangles.iter().flat_map(|t| t.vertices).filter(|v| radius(v) < 10.001).map(|v| v[2]).fold(f32::MIN, f32::max);
+        assert!((bore_top - 4.5).abs() < 1e-4);
+        // Only the corner is rounded: the top face stays flat at the ring height out to the outer edge
+        let outer_top = triangles.iter().flat_map(|t| t.vertices).filter(|v| radius(v) > 19.999).map(|v| v[2]).fold(f32::MIN, f32::max);
+        assert!((outer_top - 5.0).abs() < 1e-4);
+        assert!(triangles.iter().flat_map(|t| t.vertices).filter(|v| radius(v) > 10.5 && v[2] > 0.0).all(|v| (v[2] - 5.0).abs() < 1e-4));
+        assert!(triangles.iter().flat_map(|t| t.vertices).all(|v| v[2] <= 5.0 + 1e-4));
+
+        // Vents are checked at the bore edge: slots crowd its short circumference, grooves would notch it
+        let vents = |style, count| Some(VentSpec { style, count, width: 1.0, depth: 0.5, angle: None });
+        params.vents = vents(VentStyle::Radial, 12);
+        validate_for_printing(&params).unwrap();
+        params.vents = vents(VentStyle::Radial, 17);
+        assert!(validate_for_printing(&params).is_err());
+        params.vents = vents(VentStyle::Axial, 4);
+        assert!(validate_for_printing(&params).is_err());
+        params.vents = None;
+
+        // Concave surfaces and oversized reliefs don't rest on a corner
+        params.corner = Some(CornerSpec { lens_radius: -50.0, relief: 0.0 });
+        assert!(validate_for_printing(&params).is_err());
+        params.corner = Some(CornerSpec { lens_radius: 50.0, relief: 6.0 });
+        assert!(validate_for_printing(&params).is_err());
+    }
+
+    #[test]
+    fn test_conical_seat() {
+        let mut params = RingParameters::new(RingType::Conical, 40.0, 20.0).unwrap();
+        params.height = 8.0;
+        assert_eq!(params.cone, Some(ConeSpec::default()));
+        params.cone = Some(ConeSpec { lens_radius: Some(20.0), ..ConeSpec::default() });
+        validate_for_printing(&params).unwrap();
+        let triangles = geometry::generate_ring_mesh(&params).unwrap();
+        assert!(is_watertight(&triangles));
+        assert!(mesh_volume(&triangles) > 0.0);
+
+        // The 45° cone reaches the ring height at mid-wall and rises 1:1 outward
+        let radius = |v: &stl_io::Vector<f32>| (v[0] * v[0] + v[1] * v[1]).sqrt();
+        let top: Vec<_> = triangles.iter().flat_map(|t| t.vertices).filter(|v| v[2] > 2.99).collect();
+        assert!(top.iter().all(|v| (v[2] - (8.0 + radius(v) - 15.0)).abs() < 1e-3 || radius(v) < 10.001 || radius(v) > 19.999));
+
+        // A sphere lands where its normal is square to the cone
+        let cone = params.cone.as_ref().unwrap();
+        let landing = cone.landing(10.0, 20.0, 8.0).unwrap();
+        assert!((landing.contact_r - 20.0 * std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-4);
+        assert!((landing.vertex_z - (landing.contact_z + landing.contact_r - 20.0)).abs() < 1e-4);
+
+        // Bore grooves must stay clear of where the lens lands, inside mid-wall
+        params.vents = Some(VentSpec { style: VentStyle::Axial, count: 4, width: 1.0, depth: 3.8, angle: None });
+        assert!(validate_for_printing(&params).is_err());
+        params.vents = None;
+
+        // Lenses landing off the wall and cones dipping through the base are rejected
+        params.cone = Some(ConeSpec { lens_radius: Some(50.0), ..ConeSpec::default() });
+        assert!(validate_for_printing(&params).is_err());
+        params.cone = Some(ConeSpec::default());
+        params.height = 4.0;
+        assert!(validate_for_printing(&params).is_err());
+    }
+
+    #[test]
+    fn test_rectangular_and_elliptical_frames() {
+        let rectangle = ApertureSpec { shape: ApertureShape::Rectangle, inner_y: 30.0, corner_radius: 3.0 };
+        let mut params = RingParameters::new(RingType::Spacer, 60.0, 50.0).unwrap();
+        params.height = 4.0;
+        params.aperture = Some(rectangle.clone());
+        validate_for_printing(&params).unwrap();
+        assert_eq!(params.filename(), "SP-50.0x30.0.stl");
+
+        // A flat frame encloses the offset outline, with straight sides at the wall
+        let triangles = geometry::generate_ring_mesh(&params).unwrap();
+        assert!(is_watertight(&triangles));
+        let expected = (rectangle.area(25.0, 5.0) - rectangle.area(25.0, 0.0)) * 4.0;
+        assert!((mesh_volume(&triangles) - expected).abs() / expected < 0.01);
+        let points: Vec<_> = triangles.iter().flat_map(|t| t.vertices).collect();
+        let extent = |axis: usize| points.iter().map(|v| v[axis].abs()).fold(0.0, f32::max);
+        assert!((extent(0) - 30.0).abs() < 1e-3 && (extent(1) - 20.0).abs() < 1e-3);
+
+        // Seat profiles carry over: a convex elliptical frame's bore follows the ellipse
+        let mut params = RingParameters::new(RingType::Convex, 60.0, 50.0).unwrap();
+        params.aperture = Some(ApertureSpec { shape: ApertureShape::Ellipse, inner_y: 30.0, corner_radius: 0.0 });
+        validate_for_printing(&params).unwrap();
+        let triangles = geometry::generate_ring_mesh(&params).unwrap();
+        assert!(is_watertight(&triangles));
+        assert!(mesh_volume(&triangles) > 0.0);
+        let on_ellipse = |v: &stl_io::Vector<f32>| ((v[0] / 25.0).powi(2) + (v[1] / 15.0).powi(2) - 1.0).abs() < 1e-3;
+        assert!(triangles.iter().flat_map(|t| t.vertices).filter(|v| v[2] == 0.0).any(|v| on_ellipse(&v)));
+
+        // Lens-contact seats, round-only features and oversized corners are rejected
+        params.ring_type = RingType::Conical;
+        assert!(geometry::generate_ring_mesh(&params).is_err());
+        let mut params = RingParameters::new(RingType::Concave, 60.0, 50.0).unwrap();
+        params.aperture = Some(rectangle.clone());
+        params.features.mark = Some(IndexMark { angle: 0.0, style: MarkStyle::Notch });
+        assert!(validate_for_printing(&params).is_err());
+        params.features = OrientationFeatures::default();
+        params.aperture = Some(ApertureSpec { corner_radius: 16.0, ..rectangle });
+        assert!(validate_for_printing(&params).is_err());
+    }
+
+    #[test]
+    fn test_wedged_seats() {
+        // A wedged flat seat is a plane rising toward the wedge orientation
+        let wedge = WedgeSpec { angle: 3.0, orientation: 90.0 };
+        let mut params = RingParameters::new(RingType::Spacer, 40.0, 25.0).unwrap();
+        params.height = 5.0;
+        params.wedge = Some(wedge.clone());
+        validate_for_printing(&params).unwrap();
+        let triangles = geometry::generate_ring_mesh(&params).unwrap();
+        assert!(is_watertight(&triangles));
+        let slope = 3.0_f32.to_radians().tan();
+        let top: Vec<_> = triangles.iter().flat_map(|t| t.vertices).filter(|v| v[2] > 1.0).collect();
+        assert!(top.iter().all(|v| (v[2] - (5.0 + v[1] * slope)).abs() < 1e-3));
+        let (highest, lowest) = geometry::wedge_heights(&params).unwrap();
+        assert!((highest - (5.0 + 20.0 * slope)).abs() < 1e-3 && (lowest - (5.0 - 20.0 * slope)).abs() < 1e-3);
+
+        // A wedged convex seat keeps its profile: the peak ring stays level in the tilted frame
+        let mut params = RingParameters::new(RingType::Convex, 40.0, 25.0).unwrap();
+        params.height = 6.0;
+        params.wedge = Some(wedge);
+        validate_for_printing(&params).unwrap();
+        let triangles = geometry::generate_ring_mesh(&params).unwrap();
+        assert!(is_watertight(&triangles));
+        assert!(mesh_volume(&triangles) > 0.0);
+        // Seen in the tilted frame, every top vertex lies on the untilted convex profile
+        let (sin, cos) = 3.0_f32.to_radians().sin_cos();
+        let seat = |r: f32| 3.75 + 2.25 * (1.0 - (2.0 * (r - 12.5) / 7.5 - 1.0).powi(2));
+        let radius = |v: &stl_io::Vector<f32>| (v[0] * v[0] + v[1] * v[1]).sqrt();
+        let top: Vec<_> = triangles.iter().flat_map(|t| t.vertices).filter(|v| (12.51..19.99).contains(&radius(v))).collect();
+        assert!(!top.is_empty());
+        for v in top {
+            let along = v[1] * cos + (v[2] - 6.0) * sin;
+            let up = (v[2] - 6.0) * cos - v[1] * sin;
+            assert!((up - (seat((along * along + v[0] * v[0]).sqrt()) - 6.0)).abs() < 1e-3);
+        }
+
+        // Steep wedges drop the seat through the base; retaining rings can't be wedged
+        params.wedge = Some(WedgeSpec { angle: 20.0, orientation: 0.0 });
+        assert!(validate_for_printing(&params).is_err());
+        let thread: ThreadSpec = "SM1".parse().unwrap();
+        let mut params = RingParameters::new(RingType::RetainingRing, 30.0, 20.0).unwrap().with_thread(thread).unwrap();
+        params.wedge = Some(WedgeSpec { angle: 1.0, orientation: 0.0 });
+        assert!(geometry::generate_ring_mesh(&params).is_err());
+    }
+
+    #[test]
+    fn test_eccentric_rings() {
+        // The bore stays on the axis while the outer wall's center moves opposite the offset
+        let mut params = RingParameters::new(RingType::Spacer, 40.0, 25.0).unwrap();
+        params.height = 4.0;
+        params.eccentric = Some(EccentricSpec { offset: 1.0, direction: 0.0 });
+        validate_for_printing(&params).unwrap();
+        let triangles = geometry::generate_ring_mesh(&params).unwrap();
+        assert!(is_watertight(&triangles));
+        let base: Vec<_> = triangles.iter().flat_map(|t| t.vertices).filter(|v| v[2] == 0.0).collect();
+        let distance = |v: &stl_io::Vector<f32>, cx: f32| ((v[0] - cx).powi(2) + v[1] * v[1]).sqrt();
+        assert!(base.iter().all(|v| (distance(v, 0.0) - 12.5).abs() < 1e-3 || (distance(v, -1.0) - 20.0).abs() < 1e-3));
+        assert!(base.iter().any(|v| (distance(v, -1.0) - 20.0).abs() < 1e-3 && (v[0] - 19.0).abs() < 1e-3));
+        let expected = std::f32::consts::PI * (20.0_f32.powi(2) - 12.5_f32.powi(2)) * 4.0;
+        assert!((mesh_volume(&triangles) - expected).abs() / expected < 0.01);
+
+        // A stepped set starts centered and marks each ring toward its offset
+        let set = eccentric_set(&params, 3, 0.05);
+        let offsets: Vec<_> = set.iter().map(|ring| ring.eccentric.as_ref().unwrap().offset).collect();
+        assert_eq!(offsets, vec![0.0, 0.05, 0.1]);
+        assert!(set.iter().all(|ring| ring.features.mark.as_ref().is_some_and(|mark| mark.angle == 0.0)));
+        let parts = generate_eccentric_set(&params, 3, 0.05).unwrap();
+        assert!(parts[1].0.ends_with("-e0.05.stl"));
+        assert!(parts.iter().all(|(_, triangles)| is_watertight(triangles)));
+
+        // The thin side must stay printable; retaining rings can't be eccentric
+        params.eccentric = Some(EccentricSpec { offset: 7.0, direction: 0.0 });
+        assert!(validate_for_printing(&params).is_err());
+        let thread: ThreadSpec = "SM1".parse().unwrap();
+        let mut params = RingParameters::new(RingType::RetainingRing, 30.0, 20.0).unwrap().with_thread(thread).unwrap();
+        params.eccentric = Some(EccentricSpec { offset: 0.5, direction: 0.0 });
+        assert!(geometry::generate_ring_mesh(&params).is_err());
+    }
+
+    #[test]
+    fn test_kinematic_v_grooves() {
+        let kinematic = KinematicSpec { ball_diameter: 6.0, groove_angle: 90.0, ball_circle: None, part: BallPart::Hemispheres };
+        let mut params = RingParameters::new(RingType::ThreePoint, 60.0, 40.0).unwrap();
+        params.height = 6.0;
+        params.kinematic = Some(kinematic.clone());
+        validate_for_printing(&params).unwrap();
+
+        // A ball touching both 45° flanks sits r / sin 45° above the groove bottom, clear of the top face
+        assert!((kinematic.contact_height() - 3.0 * 0.5 / 0.5_f32.sqrt()).abs() < 1e-4);
+        assert!((kinematic.depth() - (kinematic.contact_height() + 0.75)).abs() < 1e-4);
+        assert!(kinematic.gap() > 0.0);
+
+        let parts = kinematic::generate_kinematic_parts(&params).unwrap();
+        assert_eq!(parts.len(), 2);
+        assert!(parts[1].0.ends_with("-balls.stl"));
+        let (ring, balls) = (&parts[0].1, &parts[1].1);
+        assert!(is_watertight(ring) && is_watertight(balls));
+        assert!(mesh_volume(ring) > 0.0 && mesh_volume(balls) > 0.0);
+
+        // Each groove bottom runs radially at the contact angle, down to the groove depth
+        let floor = 6.0 - kinematic.depth();
+        let grooved: Vec<_> = ring.iter().flat_map(|t| t.vertices).filter(|v| (v[2] - floor).abs() < 1e-3).collect();
+        let on_groove = |v: &stl_io::Vector<f32>, contact: f32| features::angle_between(v[1].atan2(v[0]), contact).abs() < 1e-3;
+        let contacts = geometry::contact_angles(&params);
+        assert!(contacts.iter().all(|&contact| grooved.iter().any(|v| on_groove(v, contact))));
+        assert!(grooved.iter().all(|v| contacts.iter().any(|&contact| on_groove(v, contact))));
+
+        // The domes stand a ball radius above the plate at the ball circle
+        let top = balls.iter().flat_map(|t| t.vertices).map(|v| v[2]).fold(0.0, f32::max);
+        assert!((top - (kinematic.plate_thickness() + 3.0)).abs() < 0.05);
+
+        // Sockets need wall around them; grooves need three-point rings and room in the pads
+        params.kinematic = Some(KinematicSpec { part: BallPart::Sockets, ball_diameter: 9.0, ..kinematic.clone() });
+        assert!(validate_for_printing(&params).is_err());
+        params.kinematic = Some(kinematic.clone());
+        params.ring_type = RingType::Convex;
+        assert!(validate_for_printing(&params).is_err());
+        let mut params = RingParameters::new(RingType::ThreePoint, 30.0, 10.0).unwrap();
+        params.height = 6.0;
+        params.kinematic = Some(kinematic);
+        assert!(validate_for_printing(&params).is_err());
+    }
+
+    #[test]
+    fn test_aspheric_seats() {
+        // A paraboloid (conic -1) seat follows r² / 2R, with its higher edge at the ring height
+        let paraboloid = AsphereSpec { radius: 30.0, conic: -1.0, coefficients: Vec::new() };
+        assert!((paraboloid.sag(12.0).unwrap() - 144.0 / 60.0).abs() < 1e-5);
+        let mut params = RingParameters::new(RingType::Convex, 40.0, 25.0).unwrap();
+        params.height = 7.0;
+        params.asphere = Some(paraboloid.clone());
+        validate_for_printing(&params).unwrap();
+        let triangles = geometry::generate_ring_mesh(&params).unwrap();
+        assert!(is_watertight(&triangles));
+        assert!(mesh_volume(&triangles) > 0.0);
+        let radius = |v: &stl_io::Vector<f32>| (v[0] * v[0] + v[1] * v[1]).sqrt();
+        let top: Vec<_> = triangles.iter().flat_map(|t| t.vertices).filter(|v| (12.51..19.99).contains(&radius(v))).collect();
+        assert!(!top.is_empty());
+        assert!(top.iter().all(|v| (v[2] - (7.0 + (radius(v).powi(2) - 400.0) / 60.0)).abs() < 1e-3));
+
+        // Polynomial terms add to the conic sag
+        let asphere = AsphereSpec { radius: 30.0, conic: 0.0, coefficients: vec![1e-5, 2e-8] };
+        let sphere = 30.0 - (900.0_f32 - 100.0).sqrt();
+        assert!((asphere.sag(10.0).unwrap() - (sphere + 1e-5 * 1e4 + 2e-8 * 1e6)).abs() < 1e-4);
+
+        // The seat must slope one way and fit within the ring height
+        params.asphere = Some(AsphereSpec { coefficients: vec![-3.3e-5], ..asphere });
+        assert!(validate_for_printing(&params).is_err());
+        params.asphere = Some(paraboloid.clone());
+        params.height = 4.0;
+        assert!(validate_for_printing(&params).is_err());
+
+        // Only CX and CC seats take an aspheric profile
+        let mut params = RingParameters::new(RingType::ThreePoint, 40.0, 25.0).unwrap();
+        params.asphere = Some(paraboloid);
+        assert!(validate_for_printing(&params).is_err());
+    }
+
+    #[test]
+    fn test_cylindrical_saddle() {
+        let cylinder = AsphereSpec { radius: 20.0, conic: 0.0, coefficients: Vec::new() };
+        let mut params = SaddleParameters::new(40.0, 12.0, cylinder.clone()).unwrap();
+        params.validate().unwrap();
+        validate_for_printing(&params.ring_parameters().unwrap()).unwrap();
+        let parts = saddle::generate_saddle(&params).unwrap();
+        assert_eq!(parts[0].0, "SD-40.0x12.0.stl");
+        assert_eq!(RingType::Saddle.to_string().parse::<RingType>().unwrap(), RingType::Saddle);
+        assert_eq!("saddle".parse::<RingType>().unwrap(), RingType::Saddle);
+        let triangles = &parts[0].1;
+        assert!(is_watertight(triangles));
+        assert!(mesh_volume(triangles) > 0.0);
+
+        // Inside the rim the seat follows the cylinder across the lens and is straight along it
+        let seat = |y: f32| 2.0 + 20.0 - (400.0 - y * y).sqrt();
+        let in_pocket = |v: &stl_io::Vector<f32>| v[0].abs() < 20.2 && v[1].abs() < 6.2 && v[2] > 0.0;
+        let pocket: Vec<_> = triangles.iter().flat_map(|t| t.vertices).filter(|v| in_pocket(v)).collect();
+        assert!(!pocket.is_empty());
+        assert!(pocket.iter().all(|v| (v[2] - seat(v[1])).abs() < 1e-4));
+        let extent = |axis: usize| triangles.iter().flat_map(|t| t.vertices).map(|v| v[axis].abs()).fold(0.0, f32::max);
+        assert!((extent(0) - 23.2).abs() < 1e-4 && (extent(1) - 9.2).abs() < 1e-4);
+
+        // Turning the cylinder axis turns the whole part
+        params.axis = 90.0;
+        let turned = &saddle::generate_saddle(&params).unwrap()[0].1;
+        let extent = |axis: usize| turned.iter().flat_map(|t| t.vertices).map(|v| v[axis].abs()).fold(0.0, f32::max);
+        assert!((extent(0) - 9.2).abs() < 1e-3 && (extent(1) - 23.2).abs() < 1e-3);
+
+        // Concave seats keep the floor under their low edges; tight cylinders are rejected
+        let concave = SaddleParameters::new(40.0, 12.0, AsphereSpec { radius: -20.0, ..cylinder.clone() }).unwrap();
+        assert!((concave.seat_range().0 - 2.0).abs() < 1e-4);
+        assert!(is_watertight(&saddle::generate_saddle(&concave).unwrap()[0].1));
+        let tight = SaddleParameters::new(40.0, 12.0, AsphereSpec { radius: 5.0, ..cylinder }).unwrap();
+        assert!(tight.validate().is_err());
+
+        // Saddles, lens cells and stacks don't take ring features, so asking for one is an error
+        let parse = |args: &[&str]| Cli::try_parse_from([&["optics-ring-generator"][..], args].concat());
+        assert!(parse(&["--saddle", "--height", "3", "--conic", "-1"]).is_ok());
+        assert!(parse(&["--lens-cell", "-r", "cc"]).is_ok());
+        assert!(parse(&["--stack", "lens.toml"]).is_ok());
+        for mode in [&["--saddle"][..], &["--lens-cell"], &["--stack", "lens.toml"]] {
+            for extra in [&["--label"][..], &["--holes", "M3"], &["--bayonet"], &["--vents", "radial"], &["--wedge", "1"], &["-o", "40"]] {
+                assert!(parse(&[mode, extra].concat()).is_err(), "{:?} should conflict with {:?}", extra, mode);
+            }
+        }
+        assert!(parse(&["--saddle", "-r", "cx"]).is_err());
+    }
+
+    #[test]
+    fn test_custom_profiles() {
+        // A stepped ring drawn clockwise with an explicit closing point and a header
+        let csv = CustomProfile::from_csv("r,z\n# stepped spacer\n10,2\n10,7\n16,7\n16,2\n12,2\n12,2\n10,2\n").unwrap();
+        assert_eq!(csv.points.len(), 5);
+        assert!(csv.points.iter().all(|point| point[1] >= 0.0));
+        assert!((csv.height() - 5.0).abs() < 1e-6);
+        let params = csv.ring_parameters().unwrap();
+        assert_eq!(params.ring_type.to_string().parse::<RingType>().unwrap(), RingType::Profile);
+        assert_eq!("profile".parse::<RingType>().unwrap(), RingType::Profile);
+        assert!((params.inner_diameter - 20.0).abs() < 1e-6 && (params.outer_diameter - 32.0).abs() < 1e-6);
+        validate_for_printing(&params).unwrap();
+
+        // Revolved with the seat rings' segment count, closed and oriented outward
+        let parts = profile::generate_profile_ring(&csv, &params);
+        assert_eq!(parts[0].0, "PR-20.0.stl");
+        let triangles = &parts[0].1;
+        assert_eq!(triangles.len(), 2 * 5 * geometry::RING_SEGMENTS);
+        assert!(is_watertight(triangles));
+        let expected = std::f32::consts::PI * (16.0f32.powi(2) - 10.0f32.powi(2)) * 5.0;
+        assert!((csv.volume() - expected).abs() < 1e-2);
+        assert!((mesh_volume(triangles) - expected).abs() / expected < 2e-3);
+
+        // JSON arcs and SVG arcs describe the same domed ring
+        let json = CustomProfile::from_json(
+            r#"{"start": [10, 0], "segments": [{"line": [16, 0]}, {"line": [16, 3]}, {"arc": {"to": [10, 3], "center": [13, 3]}}]}"#,
+        ).unwrap();
+        let svg = CustomProfile::from_svg(r#"<svg><path id="p" d="M10,0 h6 v-3 A3 3 0 0 0 10-3z"/></svg>"#).unwrap();
+        assert_eq!(json.points.len(), svg.points.len());
+        assert!(json.points.iter().zip(&svg.points).all(|(a, b)| (a[0] - b[0]).abs() < 1e-4 && (a[1] - b[1]).abs() < 1e-4));
+        assert!((json.height() - 6.0).abs() < 1e-4);
+        assert!(is_watertight(&profile::generate_profile_ring(&json, &json.ring_parameters().unwrap())[0].1));
+        let points = CustomProfile::from_json("[[10, 0], [14, 0], [12, 3]]").unwrap();
+        assert_eq!(points.points.len(), 3);
+
+        // Crossing outlines, points on the axis, curves and stray subpaths are rejected
+        assert!(CustomProfile::from_csv("10,0\n16,5\n16,0\n10,5\n").is_err());
+        assert!(CustomProfile::from_csv("0,0\n6,0\n6,5\n").is_err());
+        assert!(CustomProfile::from_csv("10,0\n16,0\n").is_err());
+        assert!(CustomProfile::from_csv("10,0\n16;0\n16,5\n").is_err());
+        assert!(CustomProfile::from_svg(r#"<path d="M10 0 C 12 0 14 2 16 5 Z"/>"#).is_err());
+        assert!(CustomProfile::from_svg(r#"<path d="M10 0 H16 V-5 Z M20 0 H22 V-5 Z"/>"#).is_err());
+        // The equivalent ring goes through the usual printing checks
+        let thin = CustomProfile::from_csv("10,0\n10.4,0\n10.4,5\n10,5\n").unwrap();
+        assert!(validate_for_printing(&thin.ring_parameters().unwrap()).is_err());
+
+        // Ring features can't be applied to a custom profile, so asking for one is an error
+        let args = |extra: &[&str]| {
+            let mut args = vec!["optics-ring-generator", "--profile", "mount.csv"];
+            args.extend_from_slice(extra);
+            Cli::try_parse_from(args)
+        };
+        assert!(args(&[]).is_ok());
+        for extra in [
+            &["--label"][..], &["--key", "0"], &["--flat", "0"], &["--vents", "radial"], &["--holes", "M3"],
+            &["--grip", "knurl"], &["--bayonet"], &["--thread", "SM1"], &["-r", "sp"],
+        ] {
+            assert!(args(extra).is_err(), "{:?} should conflict with --profile", extra);
+        }
+    }
 }
@@ -0,0 +1,270 @@
+use nalgebra::{Point3, Vector3};
+use std::f32::consts::PI;
+use stl_io::Triangle;
+
+/// Which surface of a revolved part a profile point lies on.
+///
+/// Corner points belong to the adjacent wall (`Bore` or `Outer`), so wall
+/// modifiers such as threads move the edges of the top and base faces too.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Surface {
+    Bore,
+    Outer,
+    Top,
+    Base,
+}
+
+/// A point of a cross-section in the r-z half plane
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProfilePoint {
+    pub r: f32,
+    pub z: f32,
+    pub surface: Surface,
+}
+
+impl ProfilePoint {
+    pub fn new(r: f32, z: f32, surface: Surface) -> Self {
+        Self { r, z, surface }
+    }
+}
+
+/// Indexed triangle mesh, kept indexed so shared vertices stay watertight
+#[derive(Debug, Clone, Default)]
+pub struct Mesh {
+    pub vertices: Vec<Point3<f32>>,
+    pub faces: Vec<[usize; 3]>,
+}
+
+impl Mesh {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_vertex(&mut self, point: Point3<f32>) -> usize {
+        self.vertices.push(point);
+        self.vertices.len() - 1
+    }
+
+    /// Add a triangle; vertices are counter-clockwise seen from outside
+    pub fn add_triangle(&mut self, a: usize, b: usize, c: usize) {
+        self.faces.push([a, b, c]);
+    }
+
+    /// Add a quad as two triangles; vertices are counter-clockwise seen from outside
+    pub fn add_quad(&mut self, a: usize, b: usize, c: usize, d: usize) {
+        self.add_triangle(a, b, c);
+        self.add_triangle(a, c, d);
+    }
+
+    pub fn into_triangles(self) -> Vec<Triangle> {
+        self.faces.iter().map(|&[a, b, c]| {
+            let (p1, p2, p3) = (self.vertices[a], self.vertices[b], self.vertices[c]);
+            let normal = (p2 - p1).cross(&(p3 - p1));
+            // Degenerate faces keep the mesh closed but have no direction
+            let normal = if normal.norm() > f32::EPSILON {
+                normal.normalize()
+            } else {
+                Vector3::zeros()
+            };
+
+            Triangle {
+                normal: stl_io::Vector::new([normal.x, normal.y, normal.z]),
+                vertices: [
+                    stl_io::Vector::new([p1.x, p1.y, p1.z]),
+                    stl_io::Vector::new([p2.x, p2.y, p2.z]),
+                    stl_io::Vector::new([p3.x, p3.y, p3.z]),
+                ],
+            }
+        }).collect()
+    }
+}
+
+/// `segments` evenly spaced angles covering a full turn
+pub fn uniform_angles(segments: usize) -> Vec<f32> {
+    (0..segments).map(|i| 2.0 * PI * i as f32 / segments as f32).collect()
+}
+
+/// Angular width of the ramp at the sides of keys, vents, bosses and lugs, in radians
+pub const EDGE_RAMP: f32 = 0.003;
+
+/// Merge extra angles (feature edges) into `angles`, sorted within one turn
+pub fn merge_angles(angles: Vec<f32>, extra: impl IntoIterator<Item = f32>) -> Vec<f32> {
+    let mut merged: Vec<f32> = angles.into_iter()
+        .chain(extra)
+        .map(|angle| angle.rem_euclid(2.0 * PI))
+        .collect();
+    merged.sort_by(|a, b| a.total_cmp(b));
+    merged.dedup_by(|a, b| (*a - *b).abs() < 1e-4);
+    if merged.len() > 1 && 2.0 * PI - merged[merged.len() - 1] + merged[0] < 1e-4 {
+        merged.pop();
+    }
+    merged
+}
+
+/// Revolve a closed profile around the Z axis into a closed solid.
+///
+/// `profile` runs counter-clockwise with r to the right and z up, and is
+/// implicitly closed from the last point back to the first. `angles` must be
+/// increasing within one turn; the last segment wraps back to the first angle.
+/// `place` maps each profile point at each angle to its final position, so
+/// callers can deform the surface (threads, flats, tilt) without breaking
+/// the shared-vertex topology. Faces `2 * (i * rows + j)` and the next one
+/// form the quad between angles `i`, `i + 1` and profile points `j`, `j + 1`.
+pub fn revolve(
+    profile: &[ProfilePoint],
+    angles: &[f32],
+    place: impl Fn(&ProfilePoint, f32) -> Point3<f32>,
+) -> Mesh {
+    let mut mesh = Mesh::new();
+    let rows = profile.len();
+
+    for &angle in angles {
+        for point in profile {
+            mesh.add_vertex(place(point, angle));
+        }
+    }
+
+    let index = |angle: usize, point: usize| (angle % angles.len()) * rows + point % rows;
+    for i in 0..angles.len() {
+        for j in 0..rows {
+            mesh.add_quad(index(i, j), index(i + 1, j), index(i + 1, j + 1), index(i, j + 1));
+        }
+    }
+
+    mesh
+}
+
+/// Extrude a planar part made of polar grid cells into a closed solid of `height`.
+///
+/// Cell `(i, j)` spans angles `i` to `i + 1` (wrapping after the last angle)
+/// and `radii[j]` to `radii[j + 1]`; `filled` says which cells are solid.
+/// Filled cells must not meet only at a corner, or the mesh is not manifold.
+pub fn extrude_cells(angles: &[f32], radii: &[f32], height: f32, filled: impl Fn(usize, usize) -> bool) -> Mesh {
+    let mut mesh = Mesh::new();
+    let (segments, levels) = (angles.len(), radii.len());
+
+    for &angle in angles {
+        for &r in radii {
+            for z in [0.0, height] {
+                mesh.add_vertex(Point3::new(r * angle.cos(), r * angle.sin(), z));
+            }
+        }
+    }
+
+    let index = |i: usize, j: usize, top: bool| ((i % segments) * levels + j) * 2 + top as usize;
+    let solid = |i: usize, j: Option<usize>| match j {
+        Some(j) if j + 1 < levels => filled((i + segments) % segments, j),
+        _ => false,
+    };
+    for i in 0..segments {
+        for j in 0..levels - 1 {
+            if !filled(i, j) {
+                continue;
+            }
+            // Top face corners counter-clockwise from above, each paired with
+            // the neighbor across the edge that leaves it
+            let corners = [(i, j), (i, j + 1), (i + 1, j + 1), (i + 1, j)];
+            let neighbors = [
+                solid(i + segments - 1, Some(j)),
+                solid(i, Some(j + 1)),
+                solid(i + 1, Some(j)),
+                solid(i, j.checked_sub(1)),
+            ];
+            let [a, b, c, d] = corners.map(|(i, j)| index(i, j, true));
+            mesh.add_quad(a, b, c, d);
+            let [a, b, c, d] = corners.map(|(i, j)| index(i, j, false));
+            mesh.add_quad(d, c, b, a);
+
+            for (k, &neighbor) in neighbors.iter().enumerate() {
+                if !neighbor {
+                    let (from, to) = (corners[k], corners[(k + 1) % 4]);
+                    mesh.add_quad(
+                        index(to.0, to.1, true),
+                        index(from.0, from.1, true),
+                        index(from.0, from.1, false),
+                        index(to.0, to.1, false),
+                    );
+                }
+            }
+        }
+    }
+
+    mesh
+}
+
+/// Extrude a part made of rectangular grid cells into a closed solid standing on z = 0.
+///
+/// Cell `(i, j)` spans `xs[i]` to `xs[i + 1]` and `ys[j]` to `ys[j + 1]`;
+/// `filled` says which cells are solid. `place` maps each grid point to its
+/// final position on the base (`false`) or top (`true`) face, so callers can
+/// shape the top and turn the part without breaking shared vertices. As with
+/// [`extrude_cells`], filled cells must not meet only at a corner.
+pub fn extrude_grid(
+    xs: &[f32],
+    ys: &[f32],
+    filled: impl Fn(usize, usize) -> bool,
+    place: impl Fn(f32, f32, bool) -> Point3<f32>,
+) -> Mesh {
+    let mut mesh = Mesh::new();
+    let (columns, rows) = (xs.len(), ys.len());
+
+    for &x in xs {
+        for &y in ys {
+            for top in [false, true] {
+                mesh.add_vertex(place(x, y, top));
+            }
+        }
+    }
+
+    let index = |i: usize, j: usize, top: bool| (i * rows + j) * 2 + top as usize;
+    let solid = |i: Option<usize>, j: Option<usize>| match (i, j) {
+        (Some(i), Some(j)) if i + 1 < columns && j + 1 < rows => filled(i, j),
+        _ => false,
+    };
+    for i in 0..columns - 1 {
+        for j in 0..rows - 1 {
+            if !filled(i, j) {
+                continue;
+            }
+            // Top face corners counter-clockwise from above, each paired with
+            // the neighbor across the edge that leaves it
+            let corners = [(i, j), (i + 1, j), (i + 1, j + 1), (i, j + 1)];
+            let neighbors = [
+                solid(Some(i), j.checked_sub(1)),
+                solid(Some(i + 1), Some(j)),
+                solid(Some(i), Some(j + 1)),
+                solid(i.checked_sub(1), Some(j)),
+            ];
+            let [a, b, c, d] = corners.map(|(i, j)| index(i, j, true));
+            mesh.add_quad(a, b, c, d);
+            let [a, b, c, d] = corners.map(|(i, j)| index(i, j, false));
+            mesh.add_quad(d, c, b, a);
+
+            for (k, &neighbor) in neighbors.iter().enumerate() {
+                if !neighbor {
+                    let (from, to) = (corners[k], corners[(k + 1) % 4]);
+                    mesh.add_quad(
+                        index(to.0, to.1, true),
+                        index(from.0, from.1, true),
+                        index(from.0, from.1, false),
+                        index(to.0, to.1, false),
+                    );
+                }
+            }
+        }
+    }
+
+    mesh
+}
+
+/// Split the segment from `from` to `to` into points spaced at most `max_step`
+/// apart, excluding `to` so consecutive segments can be chained
+pub fn subdivide(from: ProfilePoint, to: ProfilePoint, max_step: f32, surface: Surface) -> Vec<ProfilePoint> {
+    let length = ((to.r - from.r).powi(2) + (to.z - from.z).powi(2)).sqrt();
+    let steps = ((length / max_step).ceil() as usize).max(1);
+    (0..steps).map(|i| {
+        let t = i as f32 / steps as f32;
+        let surface = if i == 0 { from.surface } else { surface };
+        ProfilePoint::new(from.r + (to.r - from.r) * t, from.z + (to.z - from.z) * t, surface)
+    }).collect()
+}
@@ -0,0 +1,216 @@
+use anyhow::Result;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::geometry::RingParameters;
+
+/// Template used when none is configured; produces e.g. `CX-25.0.stl`
+pub const DEFAULT_TEMPLATE: &str = "{type}-{inner}.stl";
+
+/// Placeholders understood by filename templates
+pub const PLACEHOLDERS: [&str; 8] = [
+    "type", "inner", "outer", "height", "material", "date", "preset", "seq",
+];
+
+/// How output files are named
+#[derive(Debug, Clone, PartialEq)]
+pub struct NameOptions {
+    pub template: String,
+    pub material: Option<String>,
+    pub preset: Option<String>,
+    /// Position of the part in a batch, starting at 1
+    pub sequence: u32,
+}
+
+impl Default for NameOptions {
+    fn default() -> Self {
+        Self {
+            template: DEFAULT_TEMPLATE.to_string(),
+            material: None,
+            preset: None,
+            sequence: 1,
+        }
+    }
+}
+
+/// Check that a template only uses known placeholders and balanced braces
+pub fn validate_template(template: &str) -> Result<()> {
+    if template.trim().is_empty() {
+        return Err(anyhow::anyhow!("Filename template cannot be empty"));
+    }
+
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        if rest[start..].starts_with('}') {
+            return Err(anyhow::anyhow!("Unmatched '}}' in filename template: {}", template));
+        }
+        let end = rest[start..].find('}')
+            .ok_or_else(|| anyhow::anyhow!("Unclosed '{{' in filename template: {}", template))?;
+        let (name, spec) = split_placeholder(&rest[start + 1..start + end]);
+        if !PLACEHOLDERS.contains(&name) {
+            return Err(anyhow::anyhow!(
+                "Unknown placeholder {{{}}} in filename template. Valid placeholders: {}",
+                name,
+                PLACEHOLDERS.map(|p| format!("{{{}}}", p)).join(", ")
+            ));
+        }
+        if let Some(spec) = spec {
+            parse_spec(spec)
+                .ok_or_else(|| anyhow::anyhow!("Invalid format '{}' for {{{}}}", spec, name))?;
+        }
+        rest = &rest[start + end + 1..];
+    }
+
+    if template.contains(['/', '\\']) {
+        return Err(anyhow::anyhow!("Filename template cannot contain path separators"));
+    }
+    Ok(())
+}
+
+/// Render the output filename for a ring, using today's date for `{date}`
+pub fn render_filename(params: &RingParameters) -> String {
+    render_filename_on(params, &today())
+}
+
+/// Render the output filename for a ring with an explicit `{date}` value.
+///
+/// Unknown placeholders are kept verbatim; optional values that are unset
+/// (`{material}`, `{preset}`) are dropped along with one preceding separator.
+/// The `.stl` extension is appended if the template does not end with it.
+pub fn render_filename_on(params: &RingParameters, date: &str) -> String {
+    let mut output = expand(&params.naming.template, params, date, sanitize);
+
+    if !output.to_lowercase().ends_with(".stl") {
+        output.push_str(".stl");
+    }
+    output
+}
+
+/// Render label text such as `{type} {inner}` or `SN {seq:03}` for a ring
+pub fn render_label(template: &str, params: &RingParameters) -> String {
+    expand(template, params, &today(), |value| value.to_string())
+}
+
+/// Replace the placeholders in `template`, passing each value through `clean`
+fn expand(template: &str, params: &RingParameters, date: &str, clean: fn(&str) -> String) -> String {
+    let naming = &params.naming;
+    let mut output = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        let (name, spec) = split_placeholder(&rest[start + 1..end]);
+        let spec = spec.and_then(parse_spec);
+
+        let value = match name {
+            "type" => Some(params.ring_type.to_string()),
+            "inner" => Some(format_size(params, 0.0, spec)),
+            "outer" => Some(format_size(params, (params.outer_diameter - params.inner_diameter) / 2.0, spec)),
+            "height" => Some(format_number(params.height, spec)),
+            "material" => naming.material.clone().filter(|m| !m.trim().is_empty()),
+            "preset" => naming.preset.clone().filter(|p| !p.trim().is_empty()),
+            "date" => Some(date.to_string()),
+            "seq" => Some(format_sequence(naming.sequence, spec)),
+            _ => Some(rest[start..=end].to_string()),
+        };
+
+        match value {
+            Some(value) => output.push_str(&clean(&value)),
+            None => {
+                if output.ends_with(['-', '_', ' ', '.']) {
+                    output.pop();
+                }
+            }
+        }
+        rest = &rest[end + 1..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Name of one part of a multi-part output, e.g. `CX-25.4-barrel.stl`
+pub fn part_filename(filename: &str, part: &str) -> String {
+    let stem = filename.strip_suffix(".stl")
+        .or_else(|| filename.strip_suffix(".STL"))
+        .unwrap_or(filename);
+    format!("{}-{}.stl", stem, part)
+}
+
+fn split_placeholder(placeholder: &str) -> (&str, Option<&str>) {
+    match placeholder.split_once(':') {
+        Some((name, spec)) => (name.trim(), Some(spec.trim())),
+        None => (placeholder.trim(), None),
+    }
+}
+
+/// Format specs follow Rust's syntax: `.2` for precision, `03` for zero-padded width
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FormatSpec {
+    Precision(usize),
+    Width(usize),
+}
+
+fn parse_spec(spec: &str) -> Option<FormatSpec> {
+    if let Some(precision) = spec.strip_prefix('.') {
+        precision.parse().ok().map(FormatSpec::Precision)
+    } else {
+        spec.parse().ok().map(FormatSpec::Width)
+    }
+}
+
+fn format_number(value: f32, spec: Option<FormatSpec>) -> String {
+    match spec {
+        Some(FormatSpec::Precision(precision)) => format!("{:.*}", precision, value),
+        _ => format!("{:.1}", value),
+    }
+}
+
+/// Diameter of a round ring, or `XxY` for a rectangular or elliptical frame,
+/// at `offset` outside the bore
+fn format_size(params: &RingParameters, offset: f32, spec: Option<FormatSpec>) -> String {
+    match params.aperture {
+        Some(ref aperture) => {
+            let (x, y) = aperture.size(params.inner_diameter, offset);
+            format!("{}x{}", format_number(x, spec), format_number(y, spec))
+        }
+        None => format_number(params.inner_diameter + 2.0 * offset, spec),
+    }
+}
+
+fn format_sequence(sequence: u32, spec: Option<FormatSpec>) -> String {
+    match spec {
+        Some(FormatSpec::Width(width)) => format!("{:0width$}", sequence, width = width),
+        _ => sequence.to_string(),
+    }
+}
+
+/// Replace characters that are not allowed in file names
+fn sanitize(value: &str) -> String {
+    value.chars()
+        .map(|c| if matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') { '_' } else { c })
+        .collect()
+}
+
+/// Today's date as `YYYY-MM-DD` (UTC)
+fn today() -> String {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() / 86_400)
+        .unwrap_or(0) as i64;
+
+    // Civil-from-days conversion (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
@@ -0,0 +1,481 @@
+use anyhow::{Context, Result};
+use nalgebra::Point3;
+use serde::Deserialize;
+use std::f32::consts::PI;
+use std::path::Path;
+use stl_io::Triangle;
+
+use crate::geometry::{RingParameters, RingType, RING_SEGMENTS};
+use crate::mesh::{revolve, uniform_angles, ProfilePoint, Surface};
+
+/// Largest angle an arc turns through between profile points, in radians
+const ARC_STEP: f32 = PI / 36.0;
+
+/// Points closer than this are merged, in millimeters
+const POINT_TOLERANCE: f32 = 1e-4;
+
+/// User-defined cross-section revolved into a ring, for shapes the built-in
+/// ring types don't cover.
+///
+/// Points are (r, z) in millimeters, run counter-clockwise with r to the
+/// right and z up, and close from the last point back to the first. The
+/// lowest point sits at z = 0, so the part prints on its base.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomProfile {
+    pub points: Vec<[f32; 2]>,
+}
+
+/// JSON profile: a plain list of points, or a start point followed by lines and arcs
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum JsonProfile {
+    Points(Vec<[f32; 2]>),
+    Path { start: [f32; 2], segments: Vec<JsonSegment> },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum JsonSegment {
+    Line([f32; 2]),
+    Arc {
+        to: [f32; 2],
+        center: [f32; 2],
+        #[serde(default)]
+        clockwise: bool,
+    },
+}
+
+impl CustomProfile {
+    /// Load a profile from a `.csv`, `.json` or `.svg` file
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read profile {}", path.display()))?;
+        let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or_default().to_lowercase();
+        let profile = match extension.as_str() {
+            "csv" => Self::from_csv(&contents),
+            "json" => Self::from_json(&contents),
+            "svg" => Self::from_svg(&contents),
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Unknown profile format {}. Use a .csv, .json or .svg file",
+                    path.display()
+                ))
+            }
+        };
+        profile.with_context(|| format!("Invalid profile {}", path.display()))
+    }
+
+    /// One `r,z` pair per line. Blank lines, `#` comments and a header line
+    /// are skipped.
+    pub fn from_csv(contents: &str) -> Result<Self> {
+        let mut points = Vec::new();
+        let mut header = false;
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let point = match fields.as_slice() {
+                [r, z] => r.parse::<f32>().ok().zip(z.parse::<f32>().ok()),
+                _ => None,
+            };
+            match point {
+                Some((r, z)) => points.push([r, z]),
+                None if points.is_empty() && !header => header = true,
+                None => return Err(anyhow::anyhow!("Line {}: expected r,z but found \"{}\"", number + 1, line)),
+            }
+        }
+        Self::new(points)
+    }
+
+    /// A list of `[r, z]` points, or `{"start": [r, z], "segments": [...]}`
+    /// where each segment is `{"line": [r, z]}` or
+    /// `{"arc": {"to": [r, z], "center": [r, z], "clockwise": false}}`
+    pub fn from_json(contents: &str) -> Result<Self> {
+        let points = match serde_json::from_str(contents)? {
+            JsonProfile::Points(points) => points,
+            JsonProfile::Path { start, segments } => {
+                let mut points = vec![start];
+                for segment in segments {
+                    let from = points[points.len() - 1];
+                    match segment {
+                        JsonSegment::Line(to) => points.push(to),
+                        JsonSegment::Arc { to, center, clockwise } => {
+                            points.extend(circular_arc(from, to, center, clockwise)?)
+                        }
+                    }
+                }
+                points
+            }
+        };
+        Self::new(points)
+    }
+
+    /// The `d` attribute of the first `<path>`: moves, lines (`M L H V`) and
+    /// arcs (`A`), absolute or relative. x becomes r; y points down in SVG, so
+    /// it is flipped to become z.
+    pub fn from_svg(contents: &str) -> Result<Self> {
+        let points = parse_path(path_data(contents)?)?;
+        Self::new(points.into_iter().map(|[x, y]| [x, -y]).collect())
+    }
+
+    /// Close, orient and check a profile, and set its base on z = 0
+    pub fn new(points: Vec<[f32; 2]>) -> Result<Self> {
+        let mut points: Vec<[f32; 2]> = points;
+        points.dedup_by(|a, b| distance(*a, *b) < POINT_TOLERANCE);
+        // A repeated first point is the explicit form of the implicit closure
+        if points.len() > 1 && distance(points[0], points[points.len() - 1]) < POINT_TOLERANCE {
+            points.pop();
+        }
+        if points.len() < 3 {
+            return Err(anyhow::anyhow!("A profile needs at least three distinct points"));
+        }
+        if let Some(&[r, z]) = points.iter().find(|point| point[0] <= 0.0) {
+            return Err(anyhow::anyhow!(
+                "Profile point ({:.2}, {:.2}) is on or across the axis. Keep every point at r > 0 so the ring has a bore",
+                r, z
+            ));
+        }
+
+        let n = points.len();
+        for i in 0..n {
+            // Neighbouring edges share a point, so only check edges further along
+            for j in i + 2..n {
+                if i == 0 && j == n - 1 {
+                    continue;
+                }
+                if let Some([r, z]) = crossing(points[i], points[(i + 1) % n], points[j], points[(j + 1) % n]) {
+                    return Err(anyhow::anyhow!(
+                        "The profile crosses itself near ({:.2}, {:.2}). Its outline must not intersect",
+                        r, z
+                    ));
+                }
+            }
+        }
+
+        let area = signed_area(&points);
+        if area.abs() < 1e-3 {
+            return Err(anyhow::anyhow!("The profile encloses no area"));
+        }
+        if area < 0.0 {
+            points.reverse();
+        }
+        let base = points.iter().map(|point| point[1]).fold(f32::MAX, f32::min);
+        for point in &mut points {
+            point[1] -= base;
+        }
+        Ok(Self { points })
+    }
+
+    /// Smallest distance from the axis, i.e. the bore radius
+    pub fn inner_radius(&self) -> f32 {
+        self.points.iter().map(|point| point[0]).fold(f32::MAX, f32::min)
+    }
+
+    pub fn outer_radius(&self) -> f32 {
+        self.points.iter().map(|point| point[0]).fold(f32::MIN, f32::max)
+    }
+
+    pub fn height(&self) -> f32 {
+        self.points.iter().map(|point| point[1]).fold(f32::MIN, f32::max)
+    }
+
+    /// Volume of the revolved solid, by Pappus's theorem
+    pub fn volume(&self) -> f32 {
+        let n = self.points.len();
+        let moment: f32 = (0..n)
+            .map(|i| {
+                let ([r0, z0], [r1, z1]) = (self.points[i], self.points[(i + 1) % n]);
+                (r0 + r1) * (r0 * z1 - r1 * z0)
+            })
+            .sum::<f32>() / 6.0;
+        2.0 * PI * moment
+    }
+
+    /// Profile points for `revolve`, each on the wall or face it lies along
+    pub fn profile_points(&self) -> Vec<ProfilePoint> {
+        let (inner, outer) = (self.inner_radius(), self.outer_radius());
+        self.points.iter()
+            .map(|&[r, z]| {
+                let surface = if r - inner < POINT_TOLERANCE {
+                    Surface::Bore
+                } else if outer - r < POINT_TOLERANCE {
+                    Surface::Outer
+                } else if z < POINT_TOLERANCE {
+                    Surface::Base
+                } else {
+                    Surface::Top
+                };
+                ProfilePoint::new(r, z, surface)
+            })
+            .collect()
+    }
+
+    /// Equivalent ring parameters, used for file naming, printing checks and
+    /// print advice: a PR ring spanning the profile's bore, outer diameter and height
+    pub fn ring_parameters(&self) -> Result<RingParameters> {
+        let mut params = RingParameters::new(RingType::Profile, 2.0 * self.outer_radius(), 2.0 * self.inner_radius())?;
+        params.height = self.height();
+        Ok(params)
+    }
+}
+
+/// Generate a ring from a custom profile, named as a PR ring, e.g. `PR-20.0.stl`
+pub fn generate_profile_ring(profile: &CustomProfile, params: &RingParameters) -> Vec<(String, Vec<Triangle>)> {
+    let mesh = revolve(&profile.profile_points(), &uniform_angles(RING_SEGMENTS), |point, angle| {
+        Point3::new(point.r * angle.cos(), point.r * angle.sin(), point.z)
+    });
+    vec![(params.filename(), mesh.into_triangles())]
+}
+
+fn distance(a: [f32; 2], b: [f32; 2]) -> f32 {
+    (a[0] - b[0]).hypot(a[1] - b[1])
+}
+
+/// Area enclosed by the closed polyline: positive when it runs counter-clockwise
+fn signed_area(points: &[[f32; 2]]) -> f32 {
+    let n = points.len();
+    (0..n)
+        .map(|i| {
+            let ([x0, y0], [x1, y1]) = (points[i], points[(i + 1) % n]);
+            x0 * y1 - x1 * y0
+        })
+        .sum::<f32>() / 2.0
+}
+
+/// Where segments `a`-`b` and `c`-`d` cross or touch, if they do
+fn crossing(a: [f32; 2], b: [f32; 2], c: [f32; 2], d: [f32; 2]) -> Option<[f32; 2]> {
+    let cross = |u: [f32; 2], v: [f32; 2]| u[0] * v[1] - u[1] * v[0];
+    let (ab, cd, ac) = ([b[0] - a[0], b[1] - a[1]], [d[0] - c[0], d[1] - c[1]], [c[0] - a[0], c[1] - a[1]]);
+    let denominator = cross(ab, cd);
+    if denominator.abs() < f32::EPSILON {
+        // Parallel: only overlapping collinear segments meet
+        if cross(ac, ab).abs() > 1e-6 {
+            return None;
+        }
+        let length = ab[0] * ab[0] + ab[1] * ab[1];
+        let along = |p: [f32; 2]| ((p[0] - a[0]) * ab[0] + (p[1] - a[1]) * ab[1]) / length;
+        let (t0, t1) = (along(c), along(d));
+        let overlap = t0.min(t1).max(0.0);
+        return (overlap <= t0.max(t1).min(1.0)).then(|| [a[0] + ab[0] * overlap, a[1] + ab[1] * overlap]);
+    }
+    let t = cross(ac, cd) / denominator;
+    let u = cross(ac, ab) / denominator;
+    ((0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u)).then(|| [a[0] + ab[0] * t, a[1] + ab[1] * t])
+}
+
+/// Points along a circular arc from `from` to `to` around `center`,
+/// excluding `from`
+fn circular_arc(from: [f32; 2], to: [f32; 2], center: [f32; 2], clockwise: bool) -> Result<Vec<[f32; 2]>> {
+    let radius = distance(from, center);
+    if radius < POINT_TOLERANCE || (distance(to, center) - radius).abs() > 1e-3 * radius.max(1.0) {
+        return Err(anyhow::anyhow!(
+            "Arc to ({:.2}, {:.2}) doesn't lie on a circle around ({:.2}, {:.2}) through its start",
+            to[0], to[1], center[0], center[1]
+        ));
+    }
+    let start = (from[1] - center[1]).atan2(from[0] - center[0]);
+    let end = (to[1] - center[1]).atan2(to[0] - center[0]);
+    let mut sweep = (end - start).rem_euclid(2.0 * PI);
+    if clockwise {
+        sweep -= 2.0 * PI;
+    }
+    Ok(arc_points(center, [radius, radius], 0.0, start, sweep, to))
+}
+
+/// Points along an elliptical arc starting at angle `start` and turning
+/// through `sweep`, excluding the start and ending exactly on `to`
+fn arc_points(center: [f32; 2], radii: [f32; 2], rotation: f32, start: f32, sweep: f32, to: [f32; 2]) -> Vec<[f32; 2]> {
+    let steps = (sweep.abs() / ARC_STEP).ceil().max(1.0) as usize;
+    let (sin, cos) = rotation.sin_cos();
+    let mut points: Vec<[f32; 2]> = (1..steps)
+        .map(|k| {
+            let (t_sin, t_cos) = (start + sweep * k as f32 / steps as f32).sin_cos();
+            let (x, y) = (radii[0] * t_cos, radii[1] * t_sin);
+            [center[0] + x * cos - y * sin, center[1] + x * sin + y * cos]
+        })
+        .collect();
+    points.push(to);
+    points
+}
+
+/// The `d` attribute of the first `<path>` element
+fn path_data(svg: &str) -> Result<&str> {
+    let start = svg.find("<path").ok_or_else(|| anyhow::anyhow!("The SVG file has no <path> element"))?;
+    let tag = &svg[start..];
+    let tag = &tag[..tag.find('>').unwrap_or(tag.len())];
+    let mut rest = tag;
+    while let Some(i) = rest.find("d=") {
+        let after = &rest[i + 2..];
+        let quote = after.chars().next().filter(|c| *c == '"' || *c == '\'');
+        if rest[..i].ends_with(char::is_whitespace) {
+            if let Some(quote) = quote {
+                let body = &after[1..];
+                let end = body.find(quote).ok_or_else(|| anyhow::anyhow!("Unterminated path data"))?;
+                return Ok(&body[..end]);
+            }
+        }
+        rest = after;
+    }
+    Err(anyhow::anyhow!("The first <path> has no d attribute"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PathToken {
+    Command(char),
+    Number(f32),
+}
+
+/// Split path data into commands and numbers, which may run together
+/// ("M10-5" or "1.5.5")
+fn path_tokens(data: &str) -> Result<Vec<PathToken>> {
+    let chars: Vec<char> = data.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() || c == ',' {
+            i += 1;
+        } else if c.is_ascii_alphabetic() && c != 'e' && c != 'E' {
+            tokens.push(PathToken::Command(c));
+            i += 1;
+        } else {
+            let start = i;
+            if chars[i] == '+' || chars[i] == '-' {
+                i += 1;
+            }
+            let mut seen_dot = false;
+            while i < chars.len() && (chars[i].is_ascii_digit() || (chars[i] == '.' && !seen_dot)) {
+                seen_dot |= chars[i] == '.';
+                i += 1;
+            }
+            if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+                i += 1;
+                if i < chars.len() && (chars[i] == '+' || chars[i] == '-') {
+                    i += 1;
+                }
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+            }
+            let text: String = chars[start..i].iter().collect();
+            let number = text.parse().map_err(|_| anyhow::anyhow!("Unexpected \"{}\" in path data", chars[start]))?;
+            tokens.push(PathToken::Number(number));
+            i = i.max(start + 1);
+        }
+    }
+    Ok(tokens)
+}
+
+/// Points of a single closed subpath made of lines and arcs
+fn parse_path(data: &str) -> Result<Vec<[f32; 2]>> {
+    let tokens = path_tokens(data)?;
+    let mut points: Vec<[f32; 2]> = Vec::new();
+    let mut current = [0.0, 0.0];
+    let mut command = None;
+    let mut closed = false;
+    let mut i = 0;
+
+    while i < tokens.len() {
+        if let PathToken::Command(c) = tokens[i] {
+            command = Some(c);
+            i += 1;
+            if c == 'Z' || c == 'z' {
+                closed = true;
+                continue;
+            }
+        }
+        let c = command.ok_or_else(|| anyhow::anyhow!("Path data must start with a move (M)"))?;
+        let count = match c.to_ascii_uppercase() {
+            'M' | 'L' => 2,
+            'H' | 'V' => 1,
+            'A' => 7,
+            'Z' => return Err(anyhow::anyhow!("Unexpected number after Z in path data")),
+            other => {
+                return Err(anyhow::anyhow!(
+                    "Path command {} isn't supported. Convert curves to lines and arcs",
+                    other
+                ))
+            }
+        };
+        let args: Vec<f32> = tokens.get(i..i + count)
+            .and_then(|args| args.iter()
+                .map(|token| match token {
+                    PathToken::Number(n) => Some(*n),
+                    PathToken::Command(_) => None,
+                })
+                .collect())
+            .ok_or_else(|| anyhow::anyhow!("Path command {} needs {} numbers", c, count))?;
+        i += count;
+
+        if closed || (c.eq_ignore_ascii_case(&'M') && !points.is_empty()) {
+            return Err(anyhow::anyhow!("The profile path must be a single closed outline"));
+        }
+        let relative = c.is_ascii_lowercase();
+        let offset = |x: f32, y: f32| if relative { [current[0] + x, current[1] + y] } else { [x, y] };
+        match c.to_ascii_uppercase() {
+            'M' => {
+                current = offset(args[0], args[1]);
+                points.push(current);
+                // Further pairs after a move are lines
+                command = Some(if relative { 'l' } else { 'L' });
+            }
+            'L' => {
+                current = offset(args[0], args[1]);
+                points.push(current);
+            }
+            'H' => {
+                current = [if relative { current[0] + args[0] } else { args[0] }, current[1]];
+                points.push(current);
+            }
+            'V' => {
+                current = [current[0], if relative { current[1] + args[0] } else { args[0] }];
+                points.push(current);
+            }
+            _ => {
+                let to = offset(args[5], args[6]);
+                points.extend(svg_arc(current, to, [args[0], args[1]], args[2], args[3] != 0.0, args[4] != 0.0));
+                current = to;
+            }
+        }
+    }
+    Ok(points)
+}
+
+/// Points along an SVG elliptical arc, converted from its endpoint form
+fn svg_arc(from: [f32; 2], to: [f32; 2], radii: [f32; 2], rotation: f32, large_arc: bool, sweep: bool) -> Vec<[f32; 2]> {
+    let (mut rx, mut ry) = (radii[0].abs(), radii[1].abs());
+    if rx < POINT_TOLERANCE || ry < POINT_TOLERANCE {
+        return vec![to];
+    }
+    let (sin, cos) = rotation.to_radians().sin_cos();
+    let (dx, dy) = ((from[0] - to[0]) / 2.0, (from[1] - to[1]) / 2.0);
+    let (x1, y1) = (cos * dx + sin * dy, -sin * dx + cos * dy);
+
+    // Radii too small to reach the end point scale up until they just do
+    let scale = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if scale > 1.0 {
+        rx *= scale.sqrt();
+        ry *= scale.sqrt();
+    }
+    let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let mut factor = (numerator / denominator).max(0.0).sqrt();
+    if large_arc == sweep {
+        factor = -factor;
+    }
+    let (cx1, cy1) = (factor * rx * y1 / ry, -factor * ry * x1 / rx);
+    let center = [
+        cos * cx1 - sin * cy1 + (from[0] + to[0]) / 2.0,
+        sin * cx1 + cos * cy1 + (from[1] + to[1]) / 2.0,
+    ];
+
+    let start = ((y1 - cy1) / ry).atan2((x1 - cx1) / rx);
+    let end = ((-y1 - cy1) / ry).atan2((-x1 - cx1) / rx);
+    let mut turn = (end - start).rem_euclid(2.0 * PI);
+    if !sweep && turn > 0.0 {
+        turn -= 2.0 * PI;
+    }
+    arc_points(center, [rx, ry], rotation.to_radians(), start, turn, to)
+}
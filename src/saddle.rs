@@ -0,0 +1,203 @@
+use anyhow::Result;
+use nalgebra::Point3;
+use stl_io::Triangle;
+
+use crate::aperture::{ApertureShape, ApertureSpec};
+use crate::asphere::AsphereSpec;
+use crate::geometry::{RingParameters, RingType};
+use crate::mesh::extrude_grid;
+use crate::naming::NameOptions;
+
+/// Largest step across the curved direction of the seat, in millimeters
+const CURVE_STEP: f32 = 0.5;
+
+/// Width of the near-vertical step up from the seat to the rim, in millimeters
+const RIM_RAMP: f32 = 0.02;
+
+/// Material left under the lowest point of the seat by default, in millimeters
+const SADDLE_FLOOR: f32 = 2.0;
+
+/// Saddle for a cylindrical or acylindrical lens: a rectangular frame whose
+/// seat follows the lens surface across the lens and is straight along it,
+/// with a rim around the lens to locate it.
+///
+/// Sizes are in the saddle's own frame: `length` runs along the cylinder
+/// axis, `width` across it. `axis` turns the whole part in the XY plane.
+#[derive(Debug, Clone)]
+pub struct SaddleParameters {
+    /// Lens size along the cylinder axis
+    pub length: f32,
+    /// Lens size across the cylinder axis
+    pub width: f32,
+    /// Lens surface on the seat, as a profile across the cylinder axis:
+    /// positive radius convex, negative concave
+    pub surface: AsphereSpec,
+    /// Direction of the cylinder axis, in degrees counter-clockwise from +X
+    pub axis: f32,
+    /// Height of the cylinder's vertex line above the base
+    pub height: f32,
+    /// How far the seat reaches in under the lens edges
+    pub seat_width: f32,
+    /// Gap between the lens edges and the rim
+    pub clearance: f32,
+    /// Rim width around the lens
+    pub wall: f32,
+    /// How far the rim stands above the highest point of the seat
+    pub lip: f32,
+    pub naming: NameOptions,
+}
+
+impl SaddleParameters {
+    pub fn new(length: f32, width: f32, surface: AsphereSpec) -> Result<Self> {
+        if length <= 0.0 || width <= 0.0 {
+            return Err(anyhow::anyhow!("Lens length and width must be positive"));
+        }
+        if surface.radius == 0.0 {
+            return Err(anyhow::anyhow!("Saddles need a curved lens surface. Use --cylinder-radius <MM>"));
+        }
+
+        let mut params = Self {
+            length,
+            width,
+            surface,
+            axis: 0.0,
+            height: 0.0,
+            seat_width: 1.5,
+            clearance: 0.2,
+            wall: 3.0,
+            lip: 1.5,
+            naming: NameOptions::default(),
+        };
+        // Leave the default floor under the seat's lowest point
+        params.height = SADDLE_FLOOR - params.seat_range().0;
+        Ok(params)
+    }
+
+    /// Seat height at `v` across the axis
+    pub fn seat_height(&self, v: f32) -> f32 {
+        self.height + self.surface.sag(v.abs()).unwrap_or_default()
+    }
+
+    /// Lowest and highest seat heights across the pocket
+    pub fn seat_range(&self) -> (f32, f32) {
+        let (_, pocket_v) = self.pocket();
+        let steps = (pocket_v / CURVE_STEP).ceil() as usize;
+        (0..=steps)
+            .map(|k| self.seat_height(pocket_v * k as f32 / steps as f32))
+            .fold((f32::MAX, f32::MIN), |(low, high), z| (low.min(z), high.max(z)))
+    }
+
+    /// Half sizes of the clear aperture under the lens
+    pub fn aperture(&self) -> (f32, f32) {
+        (self.length / 2.0 - self.seat_width, self.width / 2.0 - self.seat_width)
+    }
+
+    /// Half sizes of the pocket the lens sits in
+    fn pocket(&self) -> (f32, f32) {
+        (self.length / 2.0 + self.clearance, self.width / 2.0 + self.clearance)
+    }
+
+    /// Height of the rim's top face
+    pub fn rim_height(&self) -> f32 {
+        self.seat_range().1 + self.lip
+    }
+
+    /// Equivalent frame parameters, used for file naming, printing checks and
+    /// print advice: an SD frame with a rectangular aperture the size of the lens
+    pub fn ring_parameters(&self) -> Result<RingParameters> {
+        let wall = self.clearance + self.wall;
+        let mut params = RingParameters::new(RingType::Saddle, self.length + 2.0 * wall, self.length)?;
+        params.aperture = Some(ApertureSpec { shape: ApertureShape::Rectangle, inner_y: self.width, corner_radius: 0.0 });
+        params.height = self.rim_height();
+        params.naming = self.naming.clone();
+        Ok(params)
+    }
+
+    /// Check the seat, aperture and rim are printable
+    pub fn validate(&self) -> Result<()> {
+        let (aperture_u, aperture_v) = self.aperture();
+        if self.seat_width <= 0.0 || aperture_u < 1.0 || aperture_v < 1.0 {
+            return Err(anyhow::anyhow!(
+                "Seat width ({:.2}mm) must be positive and leave a clear aperture",
+                self.seat_width
+            ));
+        }
+        if self.wall < 1.0 {
+            return Err(anyhow::anyhow!(
+                "Saddle rim ({:.2}mm) is too thin for reliable 3D printing. Minimum recommended: 1.0mm",
+                self.wall
+            ));
+        }
+        if self.lip < 0.0 || self.clearance < 0.0 {
+            return Err(anyhow::anyhow!("Rim lip and clearance can't be negative"));
+        }
+        let (_, pocket_v) = self.pocket();
+        if self.surface.sag(pocket_v + RIM_RAMP).is_none() {
+            return Err(anyhow::anyhow!(
+                "A {:.2}mm radius, {:.3} conic surface is undefined across a {:.2}mm wide lens",
+                self.surface.radius, self.surface.conic, self.width
+            ));
+        }
+        let (lowest, _) = self.seat_range();
+        if lowest < 1.0 {
+            return Err(anyhow::anyhow!(
+                "The seat drops to {:.2}mm. Raise --height to at least {:.2}mm",
+                lowest, self.height + 1.0 - lowest
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Grid lines from `-half` to `half` through each of `breaks` (and their
+/// mirror images), at most `max_step` apart
+fn grid_lines(half: f32, breaks: &[f32], max_step: f32) -> Vec<f32> {
+    let mut stops: Vec<f32> = breaks.iter().flat_map(|&b| [-b, b]).chain([-half, half]).collect();
+    stops.sort_by(|a, b| a.total_cmp(b));
+    stops.dedup_by(|a, b| (*a - *b).abs() < 1e-5);
+
+    let mut lines = Vec::new();
+    for pair in stops.windows(2) {
+        let steps = ((pair[1] - pair[0]) / max_step).ceil().max(1.0) as usize;
+        lines.extend((0..steps).map(|k| pair[0] + (pair[1] - pair[0]) * k as f32 / steps as f32));
+    }
+    lines.push(half);
+    lines
+}
+
+/// Generate the saddle, flat side down with the seat facing up
+pub fn generate_saddle(params: &SaddleParameters) -> Result<Vec<(String, Vec<Triangle>)>> {
+    params.validate()?;
+    let filename = params.ring_parameters()?.filename();
+
+    let (aperture_u, aperture_v) = params.aperture();
+    let (pocket_u, pocket_v) = params.pocket();
+    let (outer_u, outer_v) = (pocket_u + params.wall, pocket_v + params.wall);
+    let us = grid_lines(outer_u, &[aperture_u, pocket_u, pocket_u + RIM_RAMP], f32::MAX);
+    // Only the seat curves; the rim steps up just outside the pocket
+    let mut vs = vec![-outer_v, -pocket_v - RIM_RAMP];
+    vs.extend(grid_lines(pocket_v, &[aperture_v], CURVE_STEP));
+    vs.extend([pocket_v + RIM_RAMP, outer_v]);
+
+    let rim = params.rim_height();
+    let in_pocket = |u: f32, v: f32| u.abs() <= pocket_u + 1e-5 && v.abs() <= pocket_v + 1e-5;
+    let (sin, cos) = params.axis.to_radians().sin_cos();
+    let mesh = extrude_grid(
+        &us,
+        &vs,
+        |i, j| {
+            let (u, v) = ((us[i] + us[i + 1]) / 2.0, (vs[j] + vs[j + 1]) / 2.0);
+            u.abs() > aperture_u || v.abs() > aperture_v
+        },
+        |u, v, top| {
+            let z = match (top, in_pocket(u, v)) {
+                (false, _) => 0.0,
+                (true, true) => params.seat_height(v),
+                (true, false) => rim,
+            };
+            Point3::new(u * cos - v * sin, u * sin + v * cos, z)
+        },
+    );
+
+    Ok(vec![(filename, mesh.into_triangles())])
+}
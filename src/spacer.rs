@@ -0,0 +1,269 @@
+use anyhow::{Context, Result};
+use nalgebra::Point3;
+use serde::Deserialize;
+use std::path::Path;
+use stl_io::Triangle;
+
+use crate::geometry::{RingParameters, RingType};
+use crate::mesh::{revolve, subdivide, uniform_angles, ProfilePoint, Surface};
+use crate::naming::{part_filename, NameOptions};
+
+/// Angular segments for spacers
+const SPACER_SEGMENTS: usize = 128;
+
+/// Radial steps across a shaped spacer end
+const END_STEPS: usize = 16;
+
+/// Thinnest a spacer may get between the two lens surfaces
+const MIN_SPACER_THICKNESS: f32 = 0.5;
+
+/// One lens of a prescription, in millimeters.
+///
+/// Radii follow the optical sign convention: positive when the center of
+/// curvature lies further along the stack, zero for a flat surface.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Element {
+    pub name: Option<String>,
+    pub diameter: f32,
+    pub center_thickness: f32,
+    #[serde(default)]
+    pub front_radius: f32,
+    #[serde(default)]
+    pub back_radius: f32,
+    /// Air gap on the axis to the next element's front vertex
+    #[serde(default)]
+    pub gap: f32,
+}
+
+impl Element {
+    fn label(&self, index: usize) -> String {
+        self.name.clone().unwrap_or_else(|| format!("L{}", index + 1))
+    }
+
+    fn edge_thickness(&self) -> f32 {
+        let r = self.diameter / 2.0;
+        self.center_thickness + sag(self.back_radius, r) - sag(self.front_radius, r)
+    }
+}
+
+/// An ordered lens prescription, loaded from TOML:
+///
+/// ```toml
+/// [[element]]
+/// diameter = 25.4
+/// center_thickness = 5.0
+/// front_radius = 51.5
+/// back_radius = -51.5
+/// gap = 2.0
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct Prescription {
+    #[serde(rename = "element")]
+    pub elements: Vec<Element>,
+    /// Radial gap between spacer and barrel bore
+    pub clearance: f32,
+    /// How far spacers reach in over the lens edges
+    pub spacer_width: f32,
+}
+
+impl Default for Prescription {
+    fn default() -> Self {
+        Self {
+            elements: Vec::new(),
+            clearance: 0.1,
+            spacer_width: 1.5,
+        }
+    }
+}
+
+impl Prescription {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read prescription {}", path.display()))?;
+        let prescription: Self = toml::from_str(&contents)
+            .with_context(|| format!("Invalid prescription {}", path.display()))?;
+        prescription.validate()?;
+        Ok(prescription)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.elements.len() < 2 {
+            return Err(anyhow::anyhow!("A spacer stack needs at least two elements"));
+        }
+        for (i, element) in self.elements.iter().enumerate() {
+            let label = element.label(i);
+            if element.diameter <= 0.0 || element.center_thickness <= 0.0 {
+                return Err(anyhow::anyhow!("{}: diameter and center thickness must be positive", label));
+            }
+            for radius in [element.front_radius, element.back_radius] {
+                if radius != 0.0 && radius.abs() < element.diameter / 2.0 {
+                    return Err(anyhow::anyhow!(
+                        "{}: radius {:.2}mm is smaller than the semi-diameter {:.2}mm",
+                        label, radius, element.diameter / 2.0
+                    ));
+                }
+            }
+            if element.edge_thickness() <= 0.0 {
+                return Err(anyhow::anyhow!("{}: surfaces meet before the lens edge", label));
+            }
+        }
+        Ok(())
+    }
+
+    /// Axial position of each element's front vertex, starting at 0
+    pub fn vertex_positions(&self) -> Vec<f32> {
+        self.elements.iter()
+            .scan(0.0, |z, element| {
+                let position = *z;
+                *z += element.center_thickness + element.gap;
+                Some(position)
+            })
+            .collect()
+    }
+}
+
+/// Surface height relative to its vertex at radius `r`, along the stack axis
+fn sag(radius: f32, r: f32) -> f32 {
+    if radius == 0.0 {
+        return 0.0;
+    }
+    radius - radius.signum() * (radius.powi(2) - r.powi(2)).max(0.0).sqrt()
+}
+
+/// A spacer between two elements with ends shaped to the lens surfaces
+#[derive(Debug, Clone)]
+pub struct StackSpacer {
+    /// Dimensions and naming; `height` is the overall axial length
+    pub params: RingParameters,
+    pub filename: String,
+    pub after: String,
+    pub before: String,
+    /// Stack position of the spacer's lowest point, from the first front vertex
+    pub position: f32,
+    /// Axial length at the bore and at the outer wall
+    pub inner_length: f32,
+    pub outer_length: f32,
+    /// Cross-section in printing coordinates
+    profile: Vec<ProfilePoint>,
+}
+
+/// Work out the spacer between each pair of adjacent elements
+pub fn design_spacers(prescription: &Prescription, naming: &NameOptions) -> Result<Vec<StackSpacer>> {
+    prescription.validate()?;
+    let positions = prescription.vertex_positions();
+    let mut spacers = Vec::new();
+
+    for (i, pair) in prescription.elements.windows(2).enumerate() {
+        let (back, front) = (&pair[0], &pair[1]);
+        let (back_radius, front_radius) = (back.diameter / 2.0, front.diameter / 2.0);
+        let outer = back_radius.max(front_radius) - prescription.clearance;
+        let inner = back_radius.min(front_radius) - prescription.spacer_width;
+        if inner <= 0.0 {
+            return Err(anyhow::anyhow!("Spacer width leaves no aperture between {} and {}", back.label(i), front.label(i + 1)));
+        }
+
+        // Each end follows its lens surface out to the lens edge, then stays flat
+        let back_vertex = positions[i] + back.center_thickness;
+        let front_vertex = positions[i + 1];
+        let bottom = |r: f32| back_vertex + sag(back.back_radius, r.min(back_radius));
+        let top = |r: f32| front_vertex + sag(front.front_radius, r.min(front_radius));
+        let radii: Vec<f32> = (0..=END_STEPS)
+            .map(|step| inner + (outer - inner) * step as f32 / END_STEPS as f32)
+            .collect();
+
+        let thinnest = radii.iter().map(|&r| top(r) - bottom(r)).fold(f32::MAX, f32::min);
+        if thinnest < MIN_SPACER_THICKNESS {
+            return Err(anyhow::anyhow!(
+                "Air gap between {} and {} leaves only {:.2}mm for the spacer. Minimum: {:.1}mm",
+                back.label(i), front.label(i + 1), thinnest, MIN_SPACER_THICKNESS
+            ));
+        }
+
+        let base = radii.iter().map(|&r| bottom(r)).fold(f32::MAX, f32::min);
+        let height = radii.iter().map(|&r| top(r)).fold(f32::MIN, f32::max) - base;
+
+        // Counter-clockwise: bottom face outward, outer wall, top face inward, bore
+        let mut profile = Vec::new();
+        for (step, &r) in radii.iter().enumerate().take(END_STEPS) {
+            let surface = if step == 0 { Surface::Bore } else { Surface::Base };
+            profile.push(ProfilePoint::new(r, bottom(r) - base, surface));
+        }
+        profile.extend(subdivide(
+            ProfilePoint::new(outer, bottom(outer) - base, Surface::Outer),
+            ProfilePoint::new(outer, top(outer) - base, Surface::Outer),
+            f32::MAX,
+            Surface::Outer,
+        ));
+        for (step, &r) in radii.iter().rev().enumerate().take(END_STEPS) {
+            let surface = if step == 0 { Surface::Outer } else { Surface::Top };
+            profile.push(ProfilePoint::new(r, top(r) - base, surface));
+        }
+        profile.push(ProfilePoint::new(inner, top(inner) - base, Surface::Bore));
+
+        let mut params = RingParameters::new(RingType::Spacer, 2.0 * outer, 2.0 * inner)?;
+        params.height = height;
+        params.naming = NameOptions { sequence: naming.sequence + i as u32, ..naming.clone() };
+
+        spacers.push(StackSpacer {
+            filename: params.filename(),
+            params,
+            after: back.label(i),
+            before: front.label(i + 1),
+            position: base,
+            inner_length: top(inner) - bottom(inner),
+            outer_length: top(outer) - bottom(outer),
+            profile,
+        });
+    }
+
+    // Spacers of the same size would share a name; number them instead
+    let names: Vec<String> = spacers.iter().map(|spacer| spacer.filename.clone()).collect();
+    for (i, spacer) in spacers.iter_mut().enumerate() {
+        if names.iter().filter(|name| **name == names[i]).count() > 1 {
+            spacer.filename = part_filename(&names[i], &(i + 1).to_string());
+        }
+    }
+
+    Ok(spacers)
+}
+
+/// Human-readable table of the stack, one line per element and spacer
+pub fn stack_summary(prescription: &Prescription, spacers: &[StackSpacer]) -> String {
+    let positions = prescription.vertex_positions();
+    let mut lines = vec![format!("{:<14} {:>9} {:>9} {:>9} {:>9}", "Part", "Z (mm)", "Dia (mm)", "Bore", "Length")];
+
+    for (i, element) in prescription.elements.iter().enumerate() {
+        lines.push(format!(
+            "{:<14} {:>9.3} {:>9.2} {:>9} {:>9.3}",
+            element.label(i), positions[i], element.diameter, "-", element.center_thickness
+        ));
+        if let Some(spacer) = spacers.get(i) {
+            lines.push(format!(
+                "{:<14} {:>9.3} {:>9.2} {:>9.2} {:>9.3}",
+                format!("  spacer {}", i + 1), spacer.position, spacer.params.outer_diameter,
+                spacer.params.inner_diameter, spacer.params.height
+            ));
+            lines.push(format!(
+                "{:<14} {} to {}: {:.3}mm long at the bore, {:.3}mm at the wall -> {}",
+                "", spacer.after, spacer.before, spacer.inner_length, spacer.outer_length, spacer.filename
+            ));
+        }
+    }
+
+    let last = prescription.elements.len() - 1;
+    let total = positions[last] + prescription.elements[last].center_thickness;
+    lines.push(format!("Stack length (first to last vertex): {:.3}mm", total));
+    lines.join("\n")
+}
+
+fn revolve_profile(profile: &[ProfilePoint]) -> Vec<Triangle> {
+    revolve(profile, &uniform_angles(SPACER_SEGMENTS), |point, angle| {
+        Point3::new(point.r * angle.cos(), point.r * angle.sin(), point.z)
+    }).into_triangles()
+}
+
+/// Mesh for a stack spacer with shaped ends
+pub fn generate_stack_spacer(spacer: &StackSpacer) -> Vec<Triangle> {
+    revolve_profile(&spacer.profile)
+}
@@ -1,12 +1,128 @@
 use anyhow::Result;
 use std::fs::File;
-use std::path::Path;
-use stl_io::write_stl;
+use std::path::{Path, PathBuf};
+use stl_io::{write_stl, Triangle};
 
-use crate::geometry::{RingParameters, generate_ring_mesh};
+use crate::geometry::{
+    contact_angles, validate_aperture, validate_asphere, validate_bayonet, validate_cone, validate_corner,
+    validate_eccentric, validate_grip, validate_holes, validate_kinematic, validate_tangent, validate_vents,
+    validate_wedge, RingParameters, RingType, generate_ring_mesh,
+};
+use crate::holes::HoleKind;
+use crate::label::RingLabel;
+use crate::thread::ThreadSide;
+
+/// What to do when the output file already exists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CollisionPolicy {
+    /// Ask the user; generation fails with [`OutputExists`] so the caller can prompt
+    #[default]
+    Ask,
+    Overwrite,
+    Skip,
+    /// Write to the next free name, e.g. `CX-25.0-2.stl`
+    Suffix,
+}
+
+/// Returned (via `anyhow`) when the output exists and the policy is [`CollisionPolicy::Ask`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputExists(pub PathBuf);
+
+impl std::fmt::Display for OutputExists {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Output file already exists: {}", self.0.display())
+    }
+}
+
+impl std::error::Error for OutputExists {}
+
+/// Result of writing an output file
+#[derive(Debug, Clone, PartialEq)]
+pub enum OutputStatus {
+    Written(String),
+    /// The file already existed and the policy was [`CollisionPolicy::Skip`]
+    Skipped(String),
+}
+
+/// Path the STL for `filename` is written to before collision handling
+pub fn planned_output_path(filename: &str, output_dir: Option<&str>) -> PathBuf {
+    if let Some(dir) = output_dir {
+        Path::new(dir).join(filename)
+    } else {
+        Path::new(filename).to_path_buf()
+    }
+}
+
+/// Apply the collision policy to `path`. Returns `None` if the file should be skipped.
+pub fn resolve_collision(path: &Path, policy: CollisionPolicy) -> Result<Option<PathBuf>> {
+    if !path.exists() {
+        return Ok(Some(path.to_path_buf()));
+    }
+
+    match policy {
+        CollisionPolicy::Ask => Err(OutputExists(path.to_path_buf()).into()),
+        CollisionPolicy::Overwrite => Ok(Some(path.to_path_buf())),
+        CollisionPolicy::Skip => Ok(None),
+        CollisionPolicy::Suffix => Ok(Some(next_free_path(path))),
+    }
+}
+
+/// First `{stem}-{n}.{ext}` that does not exist yet, starting at 2
+fn next_free_path(path: &Path) -> PathBuf {
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let extension = path.extension().map(|e| e.to_string_lossy().to_string());
+
+    (2..)
+        .map(|n| {
+            let name = match extension {
+                Some(ref ext) => format!("{}-{}.{}", stem, n, ext),
+                None => format!("{}-{}", stem, n),
+            };
+            path.with_file_name(name)
+        })
+        .find(|candidate| !candidate.exists())
+        .expect("unbounded suffix search always finds a free name")
+}
+
+/// Write triangles to `path` via a temporary file and rename, so an
+/// interrupted run never leaves a truncated STL behind
+pub fn write_stl_atomic(path: &Path, triangles: &[Triangle]) -> Result<()> {
+    let file_name = path.file_name()
+        .ok_or_else(|| anyhow::anyhow!("Invalid output path: {}", path.display()))?
+        .to_string_lossy();
+    let temp_path = path.with_file_name(format!(".{}.{}.tmp", file_name, std::process::id()));
+
+    let result = (|| -> Result<()> {
+        let mut file = File::create(&temp_path)?;
+        write_stl(&mut file, triangles.iter())?;
+        file.sync_all()?;
+        std::fs::rename(&temp_path, path)?;
+        Ok(())
+    })();
+
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    result
+}
 
 /// Generate and save an STL file for the given ring parameters
-pub fn generate_stl_file(params: &RingParameters, output_dir: Option<&str>) -> Result<String> {
+pub fn generate_stl_file(
+    params: &RingParameters,
+    output_dir: Option<&str>,
+    policy: CollisionPolicy,
+) -> Result<OutputStatus> {
+    // Determine output path
+    let planned_path = planned_output_path(&params.filename(), output_dir);
+    let output_path = match resolve_collision(&planned_path, policy)? {
+        Some(path) => path,
+        None => {
+            let skipped = planned_path.to_string_lossy().to_string();
+            println!("  Skipped existing file: {}", skipped);
+            return Ok(OutputStatus::Skipped(skipped));
+        }
+    };
+
     println!("Generating {} ring geometry...", params.ring_type);
     println!("  Outer diameter: {:.1}mm", params.outer_diameter);
     println!("  Inner diameter: {:.1}mm", params.inner_diameter);
@@ -16,27 +132,58 @@ pub fn generate_stl_file(params: &RingParameters, output_dir: Option<&str>) -> R
     let triangles = generate_ring_mesh(params)?;
     println!("  Generated {} triangles", triangles.len());
     
-    // Determine output path
-    let filename = params.filename();
-    let output_path = if let Some(dir) = output_dir {
-        Path::new(dir).join(&filename)
-    } else {
-        Path::new(&filename).to_path_buf()
-    };
-    
     // Create output directory if it doesn't exist
     if let Some(parent) = output_path.parent() {
         std::fs::create_dir_all(parent)?;
     }
     
     // Write STL file
-    let mut file = File::create(&output_path)?;
-    write_stl(&mut file, triangles.iter())?;
+    write_stl_atomic(&output_path, &triangles)?;
     
     let output_path_str = output_path.to_string_lossy().to_string();
     println!("  STL file saved: {}", output_path_str);
     
-    Ok(output_path_str)
+    Ok(OutputStatus::Written(output_path_str))
+}
+
+/// Write the parts of a multi-part output, named `(filename, triangles)`.
+///
+/// Collisions are resolved for every part before anything is written, so
+/// [`CollisionPolicy::Ask`] never leaves a half-written set behind.
+pub fn write_part_files(
+    parts: &[(String, Vec<Triangle>)],
+    output_dir: Option<&str>,
+    policy: CollisionPolicy,
+) -> Result<Vec<OutputStatus>> {
+    let mut resolved = Vec::new();
+    for (filename, _) in parts {
+        let planned_path = planned_output_path(filename, output_dir);
+        resolved.push((resolve_collision(&planned_path, policy)?, planned_path));
+    }
+
+    let mut statuses = Vec::new();
+    for ((output_path, planned_path), (_, triangles)) in resolved.into_iter().zip(parts) {
+        let output_path = match output_path {
+            Some(path) => path,
+            None => {
+                let skipped = planned_path.to_string_lossy().to_string();
+                println!("  Skipped existing file: {}", skipped);
+                statuses.push(OutputStatus::Skipped(skipped));
+                continue;
+            }
+        };
+
+        if let Some(parent) = output_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        write_stl_atomic(&output_path, triangles)?;
+
+        let output_path_str = output_path.to_string_lossy().to_string();
+        println!("  STL file saved: {} ({} triangles)", output_path_str, triangles.len());
+        statuses.push(OutputStatus::Written(output_path_str));
+    }
+
+    Ok(statuses)
 }
 
 /// Validate ring parameters for 3D printing
@@ -67,6 +214,61 @@ pub fn validate_for_printing(params: &RingParameters) -> Result<()> {
         ));
     }
     
+    // Two-dimension frames: the Y side must fit too
+    if let Some(ref aperture) = params.aperture {
+        let (_, outer_y) = aperture.size(params.inner_diameter, wall_thickness);
+        if outer_y > 300.0 {
+            return Err(anyhow::anyhow!("Frame Y size ({:.1}mm) exceeds typical 3D printer build volume", outer_y));
+        }
+    }
+    validate_aperture(params)?;
+
+    params.features.validate(params.inner_diameter / 2.0, params.outer_diameter / 2.0)?;
+    if let Some(ref label) = params.label {
+        RingLabel::new(label, params)?;
+    }
+    validate_vents(params)?;
+    validate_holes(params)?;
+    validate_bayonet(params)?;
+    validate_grip(params)?;
+    validate_tangent(params)?;
+    validate_corner(params)?;
+    validate_cone(params)?;
+    validate_asphere(params)?;
+    validate_wedge(params)?;
+    validate_eccentric(params)?;
+    validate_kinematic(params)?;
+    if let Some(ref flexure) = params.flexure {
+        flexure.validate(params)?;
+    }
+
+    // Threaded parts need solid material behind the thread groove
+    if params.ring_type == RingType::RetainingRing && params.thread.is_none() {
+        return Err(anyhow::anyhow!("Retaining rings need a thread specification"));
+    }
+    if let Some(ref thread) = params.thread {
+        let wall_behind_thread = match thread.side {
+            ThreadSide::External => {
+                (thread.minor_diameter() - 2.0 * thread.clearance - params.inner_diameter) / 2.0
+            }
+            ThreadSide::Internal => {
+                (params.outer_diameter - thread.major_diameter - 2.0 * thread.clearance) / 2.0
+            }
+        };
+        if wall_behind_thread < 1.0 {
+            return Err(anyhow::anyhow!(
+                "Wall behind the {} thread ({:.2}mm) is too thin. Minimum recommended: 1.0mm",
+                thread.name, wall_behind_thread
+            ));
+        }
+        if thread.pitch < 0.4 {
+            return Err(anyhow::anyhow!(
+                "Thread pitch ({:.2}mm) is too fine to print reliably. Minimum: 0.4mm",
+                thread.pitch
+            ));
+        }
+    }
+
     println!("✓ Ring parameters validated for 3D printing");
     println!("  Wall thickness: {:.2}mm", wall_thickness);
     
@@ -80,13 +282,18 @@ pub fn print_manufacturing_info(params: &RingParameters) {
     let inner_radius = params.inner_diameter / 2.0;
     
     // Estimate material volume (simplified calculation)
-    let outer_volume = std::f32::consts::PI * outer_radius.powi(2) * params.height;
-    let inner_volume = std::f32::consts::PI * inner_radius.powi(2) * params.height;
-    let material_volume = outer_volume - inner_volume;
+    let (outer_area, inner_area) = match params.aperture {
+        Some(ref aperture) => (aperture.area(inner_radius, wall_thickness), aperture.area(inner_radius, 0.0)),
+        None => (std::f32::consts::PI * outer_radius.powi(2), std::f32::consts::PI * inner_radius.powi(2)),
+    };
+    let material_volume = (outer_area - inner_area) * params.height;
     
     println!("\n3D Printing Information:");
     println!("  Wall thickness: {:.2}mm", wall_thickness);
-    println!("  Estimated material volume: {:.2}cm³", material_volume / 1000.0);
+    // Custom profiles report their exact volume when generated; the annulus says nothing about them
+    if params.ring_type != crate::geometry::RingType::Profile {
+        println!("  Estimated material volume: {:.2}cm³", material_volume / 1000.0);
+    }
     
     // Printing recommendations
     println!("\nRecommended Print Settings:");
@@ -98,14 +305,73 @@ pub fn print_manufacturing_info(params: &RingParameters) {
         },
         crate::geometry::RingType::Concave => {
             println!("  - Layer height: 0.15-0.2mm for smooth curves");
-            println!("  - Support: Light support for overhangs");
+            println!("  - Support: None required, the seat faces up");
             println!("  - Orientation: Place flat on build plate");
         },
         crate::geometry::RingType::ThreePoint => {
+            let angles = contact_angles(params).map(|angle| format!("{:.0}°", angle.to_degrees().rem_euclid(360.0)));
+            println!("  - Contact points at {}", angles.join(", "));
             println!("  - Layer height: 0.2-0.3mm (structural print)");
-            println!("  - Support: Minimal support for contact points");
+            println!("  - Support: None required, the contact pads face up");
             println!("  - Orientation: Place flat on build plate");
+            if params.kinematic.is_some() {
+                println!("  - Ball part: print face up, flat on the build plate; 0.1-0.15mm layers keep the balls round");
+            }
         },
+        crate::geometry::RingType::RetainingRing => {
+            let pitch = params.thread.as_ref().map_or(0.635, |thread| thread.pitch);
+            println!("  - Layer height: {:.2}mm or finer (a quarter of the thread pitch)", pitch / 4.0);
+            println!("  - Support: None required");
+            println!("  - Orientation: Place flat on build plate, thread axis vertical");
+            println!("  - Fine lens-tube threads print best on resin printers or with a 0.25mm nozzle");
+        },
+        crate::geometry::RingType::Spacer => {
+            println!("  - Layer height: 0.1-0.15mm; spacer length sets the air gap");
+            println!("  - Support: None required");
+            println!("  - Orientation: Place flat on build plate, then check the length with calipers");
+        },
+        crate::geometry::RingType::Flexure => {
+            println!("  - Layer height: 0.1-0.15mm so the finger slots stay open");
+            println!("  - Support: None required");
+            println!("  - Orientation: Place flat on build plate; fingers bend in the layer plane");
+            println!("  - Perimeters: enough to print each finger solid, without infill");
+        },
+        crate::geometry::RingType::Tangent => {
+            println!("  - Layer height: 0.1-0.15mm so the toroid prints smooth at the contact");
+            println!("  - Support: None required, the seat faces up");
+            println!("  - Orientation: Place flat on build plate");
+            println!("  - Lightly sand the contact band; layer steps there raise contact stress");
+        },
+        crate::geometry::RingType::SharpCorner => {
+            println!("  - Layer height: 0.1mm or finer so the corner height is accurate");
+            println!("  - Support: None required, the seat faces up");
+            println!("  - Orientation: Place flat on build plate");
+            println!("  - Break the printed edge with fine abrasive only; the corner sets the lens position");
+        },
+        crate::geometry::RingType::Conical => {
+            println!("  - Layer height: 0.1mm or finer; steep cones show layer steps where the lens lands");
+            println!("  - Support: None required, the cone faces up");
+            println!("  - Orientation: Place flat on build plate");
+        },
+        crate::geometry::RingType::Saddle => {
+            println!("  - Layer height: 0.1mm or finer; the cylinder cradle shows layer steps across the lens");
+            println!("  - Support: None required, the cradle faces up");
+            println!("  - Orientation: Place flat on build plate");
+            println!("  - Lightly sand the cradle along the cylinder axis; steps there rock the lens");
+        },
+        crate::geometry::RingType::Profile => {
+            println!("  - Layer height: 0.1-0.2mm; finer where the profile slopes gently");
+            println!("  - Support: needed under any overhang in the profile flatter than 45°");
+            println!("  - Orientation: Place flat on build plate, the profile's base (z = 0) down");
+        },
+    }
+    if let Some(ref holes) = params.holes {
+        if let Ok(diameter) = holes.diameter() {
+            println!("  - Radial holes: {:.2}mm for {} screws; small horizontal holes bridge without support", diameter, holes.screw);
+        }
+        if holes.kind == HoleKind::Insert {
+            println!("  - Press heat-set inserts in from the outside with a soldering iron");
+        }
     }
     
     // Material recommendations
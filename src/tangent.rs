@@ -0,0 +1,102 @@
+use anyhow::Result;
+
+/// Toroid section radius as a fraction of the lens surface radius
+const TOROID_FRACTION: f32 = 0.5;
+
+/// Tangent (toroidal) seat: the top face is a torus section that touches the
+/// lens surface tangentially at the contact diameter, at the ring's height.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TangentSpec {
+    /// Lens surface radius: positive for convex, negative for concave
+    pub lens_radius: f32,
+    /// Diameter where the lens touches the seat; mid-wall when unset
+    pub contact_diameter: Option<f32>,
+}
+
+/// Where the torus section sits, in ring coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ToroidSection {
+    pub radius: f32,
+    /// Center of the section circle: distance from the axis and height above the base
+    pub center_r: f32,
+    pub center_z: f32,
+    pub contact_r: f32,
+    pub contact_z: f32,
+}
+
+impl TangentSpec {
+    /// Cross-section radius of the torus. Half the lens radius keeps contact
+    /// stress low while leaving the section curved enough to span the seat.
+    pub fn toroid_radius(&self) -> f32 {
+        TOROID_FRACTION * self.lens_radius.abs()
+    }
+
+    /// Section for a ring from `inner_radius` to `outer_radius` touching the lens at `height`
+    pub fn section(&self, inner_radius: f32, outer_radius: f32, height: f32) -> ToroidSection {
+        let contact_r = self.contact_diameter.map_or((inner_radius + outer_radius) / 2.0, |d| d / 2.0);
+        let lens = self.lens_radius.abs();
+        let radius = self.toroid_radius();
+
+        // Direction from the contact to the torus center. A convex surface has
+        // its center of curvature above the seat, so the torus sits outside it,
+        // along the surface normal. A concave surface's center is below, and
+        // the torus sits inside it, toward that center.
+        let axial = (lens * lens - contact_r * contact_r).max(0.0).sqrt();
+        let (normal_r, normal_z) = if self.lens_radius > 0.0 {
+            (contact_r / lens, -axial / lens)
+        } else {
+            (-contact_r / lens, -axial / lens)
+        };
+
+        ToroidSection {
+            radius,
+            center_r: contact_r + radius * normal_r,
+            center_z: height + radius * normal_z,
+            contact_r,
+            contact_z: height,
+        }
+    }
+
+    /// Check the contact lies on the seat and the toroid spans it above the base
+    pub fn validate(&self, inner_radius: f32, outer_radius: f32, height: f32) -> Result<()> {
+        if self.lens_radius == 0.0 {
+            return Err(anyhow::anyhow!("Tangent seats need a curved lens surface. Use --lens-radius <MM>"));
+        }
+        let section = self.section(inner_radius, outer_radius, height);
+        if section.contact_r <= inner_radius + 0.5 || section.contact_r >= outer_radius - 0.5 {
+            return Err(anyhow::anyhow!(
+                "Contact diameter {:.2}mm must lie at least 0.5mm inside the {:.2}-{:.2}mm seat",
+                2.0 * section.contact_r, 2.0 * inner_radius, 2.0 * outer_radius
+            ));
+        }
+        if section.contact_r >= self.lens_radius.abs() {
+            return Err(anyhow::anyhow!(
+                "Contact diameter {:.2}mm is too large for a {:.2}mm lens surface radius",
+                2.0 * section.contact_r, self.lens_radius.abs()
+            ));
+        }
+        let reach = (inner_radius - section.center_r).abs().max((outer_radius - section.center_r).abs());
+        if reach > 0.9 * section.radius {
+            return Err(anyhow::anyhow!(
+                "A {:.2}mm toroid for a {:.2}mm lens radius is too tight to span the seat. Use a narrower ring",
+                section.radius, self.lens_radius.abs()
+            ));
+        }
+        let lowest = section.height_at(inner_radius).min(section.height_at(outer_radius));
+        if lowest < 1.0 {
+            return Err(anyhow::anyhow!(
+                "The toroid drops to {:.2}mm at the seat edge. Raise --height to at least {:.2}mm",
+                lowest, height + 1.0 - lowest
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl ToroidSection {
+    /// Height of the seat surface at radius `r`
+    pub fn height_at(&self, r: f32) -> f32 {
+        let offset = r - self.center_r;
+        self.center_z + (self.radius * self.radius - offset * offset).max(0.0).sqrt()
+    }
+}
@@ -0,0 +1,202 @@
+use anyhow::Result;
+use nalgebra::Point3;
+use std::f32::consts::PI;
+use stl_io::Triangle;
+
+use crate::geometry::RingParameters;
+use crate::mesh::{merge_angles, revolve, subdivide, uniform_angles, ProfilePoint, Surface};
+
+/// Default radial clearance added to printed threads, in millimeters
+pub const DEFAULT_THREAD_CLEARANCE: f32 = 0.15;
+
+/// Named thread standards: name, major diameter (mm), pitch (mm)
+pub const THREAD_STANDARDS: [(&str, f32, f32); 6] = [
+    ("SM05", 0.535 * 25.4, 25.4 / 40.0), // 0.535"-40 UNS lens tube
+    ("SM1", 1.035 * 25.4, 25.4 / 40.0),  // 1.035"-40 UNS lens tube
+    ("SM2", 2.035 * 25.4, 25.4 / 40.0),  // 2.035"-40 UNS lens tube
+    ("SM3", 3.035 * 25.4, 25.4 / 40.0),  // 3.035"-40 UNS lens tube
+    ("C-MOUNT", 25.4, 25.4 / 32.0),      // 1"-32 UN
+    ("T-MOUNT", 42.0, 0.75),             // M42x0.75
+];
+
+/// Angular segments for threaded walls; finer than plain rings so the helix stays smooth
+const THREAD_SEGMENTS: usize = 180;
+
+/// Whether the thread is cut on the outer wall or the bore
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThreadSide {
+    External,
+    Internal,
+}
+
+/// A 60° thread (ISO metric / unified profile)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThreadSpec {
+    pub name: String,
+    pub major_diameter: f32,
+    pub pitch: f32,
+    pub side: ThreadSide,
+    /// Radial clearance for printing: external threads shrink and internal threads grow by this much
+    pub clearance: f32,
+}
+
+impl ThreadSpec {
+    pub fn new(major_diameter: f32, pitch: f32, side: ThreadSide) -> Result<Self> {
+        if major_diameter <= 0.0 || pitch <= 0.0 {
+            return Err(anyhow::anyhow!("Thread diameter and pitch must be positive"));
+        }
+        if pitch * 4.0 > major_diameter {
+            return Err(anyhow::anyhow!("Thread pitch {:.2}mm is too coarse for {:.2}mm diameter", pitch, major_diameter));
+        }
+
+        Ok(Self {
+            name: format!("M{}x{}", major_diameter, pitch),
+            major_diameter,
+            pitch,
+            side,
+            clearance: DEFAULT_THREAD_CLEARANCE,
+        })
+    }
+
+    /// Radial depth of the basic profile (5/8 of the fundamental triangle height)
+    pub fn depth(&self) -> f32 {
+        0.625 * 0.866_025_4 * self.pitch
+    }
+
+    pub fn minor_diameter(&self) -> f32 {
+        self.major_diameter - 2.0 * self.depth()
+    }
+}
+
+impl std::fmt::Display for ThreadSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let side = match self.side {
+            ThreadSide::External => "external",
+            ThreadSide::Internal => "internal",
+        };
+        write!(f, "{} ({:.3}mm x {:.3}mm pitch, {})", self.name, self.major_diameter, self.pitch, side)
+    }
+}
+
+impl std::str::FromStr for ThreadSpec {
+    type Err = anyhow::Error;
+
+    /// Parse a standard name (`SM1`, `C-mount`) or metric size (`M25x0.5`),
+    /// optionally followed by `internal` or `external`
+    fn from_str(s: &str) -> Result<Self> {
+        let mut words = s.split_whitespace();
+        let size = words.next()
+            .ok_or_else(|| anyhow::anyhow!("Thread specification is empty"))?
+            .to_uppercase();
+        let side = match words.next().map(|w| w.to_lowercase()) {
+            None => ThreadSide::External,
+            Some(w) if w == "external" || w == "ext" => ThreadSide::External,
+            Some(w) if w == "internal" || w == "int" => ThreadSide::Internal,
+            Some(w) => return Err(anyhow::anyhow!("Invalid thread side: {}. Use internal or external", w)),
+        };
+
+        if let Some((name, major, pitch)) = THREAD_STANDARDS.iter().find(|(name, _, _)| *name == size) {
+            let mut spec = ThreadSpec::new(*major, *pitch, side)?;
+            spec.name = name.to_string();
+            return Ok(spec);
+        }
+
+        let metric = size.strip_prefix('M').and_then(|rest| rest.split_once('X'));
+        match metric {
+            Some((major, pitch)) => {
+                let major: f32 = major.parse()
+                    .map_err(|_| anyhow::anyhow!("Invalid thread diameter in {}", s))?;
+                let pitch: f32 = pitch.parse()
+                    .map_err(|_| anyhow::anyhow!("Invalid thread pitch in {}", s))?;
+                ThreadSpec::new(major, pitch, side)
+            }
+            None => Err(anyhow::anyhow!(
+                "Invalid thread: {}. Use M<diameter>x<pitch> or one of: {}",
+                s,
+                THREAD_STANDARDS.map(|(name, _, _)| name).join(", ")
+            )),
+        }
+    }
+}
+
+/// Depth of the thread groove at axial phase `u` (in turns), as a fraction of full depth.
+///
+/// The basic 60° profile has a crest flat of p/8 and a root flat of p/4, with
+/// straight flanks between them.
+pub fn thread_profile(u: f32) -> f32 {
+    let u = u.rem_euclid(1.0);
+    let s = if u < 0.5 { u } else { 1.0 - u };
+    ((s - 1.0 / 16.0) / (5.0 / 16.0)).clamp(0.0, 1.0)
+}
+
+/// Generate a retaining ring with a helical thread on the outer wall or bore
+pub fn generate_retaining_ring(params: &RingParameters) -> Result<Vec<Triangle>> {
+    let thread = params.thread.as_ref()
+        .ok_or_else(|| anyhow::anyhow!("Retaining rings need a thread specification"))?;
+
+    let height = params.height;
+    let depth = thread.depth();
+    let z_step = thread.pitch / 16.0;
+
+    // Radius of the thread crest surface and the radius the groove is measured from
+    let (inner_radius, outer_radius, groove_reference) = match thread.side {
+        ThreadSide::External => {
+            let crest = thread.major_diameter / 2.0 - thread.clearance;
+            (params.inner_diameter / 2.0, crest, crest)
+        }
+        ThreadSide::Internal => {
+            let reference = thread.major_diameter / 2.0 + thread.clearance;
+            (reference - depth, params.outer_diameter / 2.0, reference)
+        }
+    };
+
+    let (outer_step, bore_step) = match thread.side {
+        ThreadSide::External => (z_step, height),
+        ThreadSide::Internal => (height, z_step),
+    };
+
+    let base_inner = ProfilePoint::new(inner_radius, 0.0, Surface::Bore);
+    let base_outer = ProfilePoint::new(outer_radius, 0.0, Surface::Outer);
+    let top_outer = ProfilePoint::new(outer_radius, height, Surface::Outer);
+    let top_inner = ProfilePoint::new(inner_radius, height, Surface::Bore);
+
+    let mut outer_wall = subdivide(base_outer, top_outer, outer_step, Surface::Outer);
+    let mut angles = uniform_angles(THREAD_SEGMENTS);
+    if let Some(ref grip) = params.grip {
+        let mut heights: Vec<f32> = outer_wall.iter().map(|point| point.z).collect();
+        heights.extend(grip.height_breaks(outer_radius, height).into_iter().filter(|&z| z < height - 1e-4));
+        heights.sort_by(|a, b| a.total_cmp(b));
+        heights.dedup_by(|a, b| (*a - *b).abs() < 1e-4);
+        outer_wall = heights.into_iter().map(|z| ProfilePoint::new(outer_radius, z, Surface::Outer)).collect();
+        angles = merge_angles(angles, grip.angle_breaks(outer_radius, params.features.clocking));
+    }
+
+    let mut profile = Vec::new();
+    profile.extend(subdivide(base_inner, base_outer, f32::MAX, Surface::Base));
+    profile.extend(outer_wall);
+    profile.extend(subdivide(top_outer, top_inner, f32::MAX, Surface::Top));
+    profile.extend(subdivide(top_inner, base_inner, bore_step, Surface::Bore));
+
+    let threaded = match thread.side {
+        ThreadSide::External => Surface::Outer,
+        ThreadSide::Internal => Surface::Bore,
+    };
+
+    let mesh = revolve(&profile, &angles, |point, angle| {
+        let r = if point.surface == threaded {
+            // Right-hand helix: the profile phase advances one pitch per turn
+            let phase = point.z / thread.pitch - angle / (2.0 * PI);
+            match thread.side {
+                ThreadSide::External => groove_reference - depth * thread_profile(phase),
+                ThreadSide::Internal => groove_reference - depth * thread_profile(phase + 0.5),
+            }
+        } else if let Some(ref grip) = params.grip {
+            grip.apply(point, angle, outer_radius, height, params.features.clocking).r
+        } else {
+            point.r
+        };
+        Point3::new(r * angle.cos(), r * angle.sin(), point.z)
+    });
+
+    Ok(mesh.into_triangles())
+}
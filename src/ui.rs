@@ -1,6 +1,9 @@
 use anyhow::Result;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, MouseButton,
+        MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -16,9 +19,30 @@ use ratatui::{
 use std::io;
 use std::path::PathBuf;
 use std::fs;
+use std::time::{Duration, Instant};
 
+use crate::config::Config;
 use crate::geometry::{RingParameters, RingType};
-use crate::stl_output::{generate_stl_file, validate_for_printing};
+use crate::naming::{validate_template, NameOptions, DEFAULT_TEMPLATE};
+use crate::label::{LabelPlacement, LabelSpec, RingLabel};
+use crate::cone::ConeSpec;
+use crate::corner::CornerSpec;
+use crate::tangent::TangentSpec;
+use crate::thread::{ThreadSide, ThreadSpec};
+use crate::stl_output::{
+    generate_stl_file, validate_for_printing, CollisionPolicy, OutputExists, OutputStatus,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BrowserMode {
+    Browse,
+    /// Typing a path to jump to, with tab completion
+    PathInput(String),
+    /// Typing the name of a folder to create in the current directory
+    NewFolder(String),
+    /// Type-ahead search within the current entries
+    Search(String),
+}
 
 #[derive(Debug, Clone)]
 pub struct DirectoryBrowser {
@@ -26,6 +50,11 @@ pub struct DirectoryBrowser {
     pub entries: Vec<PathBuf>,
     pub list_state: ListState,
     pub show_hidden: bool,
+    pub mode: BrowserMode,
+    pub completions: Vec<String>,
+    /// File name the current form would generate, used to flag collisions
+    pub target_filename: Option<String>,
+    pub message: Option<String>,
 }
 
 impl DirectoryBrowser {
@@ -35,6 +64,10 @@ impl DirectoryBrowser {
             entries: Vec::new(),
             list_state: ListState::default(),
             show_hidden: false,
+            mode: BrowserMode::Browse,
+            completions: Vec::new(),
+            target_filename: None,
+            message: None,
         };
         browser.refresh_entries()?;
         Ok(browser)
@@ -113,6 +146,371 @@ impl DirectoryBrowser {
         };
         self.list_state.select(Some(i));
     }
+
+    /// Whether entry `i` is the ".." link to the parent directory
+    pub fn is_parent_entry(&self, i: usize) -> bool {
+        i == 0 && self.current_path.parent().is_some()
+    }
+
+    /// STL files in the current directory
+    pub fn stl_files(&self) -> Vec<&PathBuf> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(i, path)| !self.is_parent_entry(*i) && is_stl_file(path))
+            .map(|(_, path)| path)
+            .collect()
+    }
+
+    /// Existing file that generating into this directory would collide with
+    pub fn collision(&self) -> Option<&PathBuf> {
+        let target = self.target_filename.as_deref()?;
+        self.stl_files()
+            .into_iter()
+            .find(|path| path.file_name().and_then(|n| n.to_str()) == Some(target))
+    }
+
+    /// Select the first entry matching `query`, preferring prefix matches
+    pub fn search(&mut self, query: &str) {
+        if query.is_empty() {
+            return;
+        }
+        let query = query.to_lowercase();
+        let names: Vec<(usize, String)> = self.entries
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !self.is_parent_entry(*i))
+            .map(|(i, path)| (i, entry_name(path).to_lowercase()))
+            .collect();
+
+        let found = names.iter()
+            .find(|(_, name)| name.starts_with(&query))
+            .or_else(|| names.iter().find(|(_, name)| name.contains(&query)));
+
+        if let Some((i, _)) = found {
+            self.list_state.select(Some(*i));
+        }
+    }
+
+    /// Create a folder in the current directory and select it
+    pub fn create_folder(&mut self, name: &str) -> Result<PathBuf> {
+        let name = name.trim();
+        if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+            return Err(anyhow::anyhow!("Invalid folder name: '{}'", name));
+        }
+
+        let path = self.current_path.join(name);
+        fs::create_dir(&path)?;
+        self.refresh_entries()?;
+        if let Some(i) = self.entries.iter().position(|entry| *entry == path) {
+            self.list_state.select(Some(i));
+        }
+        Ok(path)
+    }
+}
+
+fn entry_name(path: &std::path::Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn is_stl_file(path: &std::path::Path) -> bool {
+    path.is_file()
+        && path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("stl"))
+}
+
+/// Expand a leading `~` to the user's home directory
+fn expand_home(input: &str) -> PathBuf {
+    if let Some(rest) = input.strip_prefix('~') {
+        if let Some(home) = dirs::home_dir() {
+            return home.join(rest.trim_start_matches(['/', '\\']));
+        }
+    }
+    PathBuf::from(input)
+}
+
+/// Complete a partially typed directory path.
+///
+/// Returns the completed input and the candidate directory names. A unique
+/// match is completed with a trailing separator; several matches are completed
+/// up to their longest common prefix.
+pub fn complete_path(input: &str) -> (String, Vec<String>) {
+    let ends_with_separator = input.ends_with('/') || input.ends_with(std::path::MAIN_SEPARATOR);
+    let (dir_part, prefix) = if input.is_empty() || ends_with_separator {
+        (input, "")
+    } else {
+        match input.rfind(['/', std::path::MAIN_SEPARATOR]) {
+            Some(i) => (&input[..=i], &input[i + 1..]),
+            None => ("", input),
+        }
+    };
+
+    let dir = if dir_part.is_empty() { PathBuf::from(".") } else { expand_home(dir_part) };
+    let mut candidates: Vec<String> = match fs::read_dir(&dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|name| name.starts_with(prefix) && (prefix.starts_with('.') || !name.starts_with('.')))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    candidates.sort();
+
+    let completed = match candidates.as_slice() {
+        [] => input.to_string(),
+        [only] => format!("{}{}{}", dir_part, only, std::path::MAIN_SEPARATOR),
+        [first, rest @ ..] => {
+            let common = rest.iter().fold(first.clone(), |common, name| {
+                common.chars()
+                    .zip(name.chars())
+                    .take_while(|(a, b)| a == b)
+                    .map(|(a, _)| a)
+                    .collect()
+            });
+            format!("{}{}", dir_part, common)
+        }
+    };
+
+    (completed, candidates)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobStatus {
+    Pending,
+    Done(String),
+    /// The output already existed and was kept
+    Skipped(String),
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct QueueItem {
+    pub params: RingParameters,
+    pub output_dir: String,
+    pub status: JobStatus,
+}
+
+impl QueueItem {
+    pub fn label(&self) -> String {
+        format!(
+            "{} {:.1}/{:.1}mm",
+            self.params.ring_type, self.params.outer_diameter, self.params.inner_diameter
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct JobQueue {
+    pub items: Vec<QueueItem>,
+    pub list_state: ListState,
+}
+
+impl JobQueue {
+    pub fn push(&mut self, params: RingParameters, output_dir: String) {
+        self.items.push(QueueItem {
+            params,
+            output_dir,
+            status: JobStatus::Pending,
+        });
+        self.list_state.select(Some(self.items.len() - 1));
+    }
+
+    pub fn remove_selected(&mut self) {
+        if let Some(i) = self.list_state.selected() {
+            if i < self.items.len() {
+                self.items.remove(i);
+            }
+            if self.items.is_empty() {
+                self.list_state.select(None);
+            } else {
+                self.list_state.select(Some(i.min(self.items.len() - 1)));
+            }
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
+        let i = match self.list_state.selected() {
+            Some(i) => if i == 0 { self.items.len() - 1 } else { i - 1 },
+            None => 0,
+        };
+        self.list_state.select(Some(i));
+    }
+
+    pub fn move_down(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
+        let i = match self.list_state.selected() {
+            Some(i) => if i >= self.items.len() - 1 { 0 } else { i + 1 },
+            None => 0,
+        };
+        self.list_state.select(Some(i));
+    }
+
+    /// Generate every item that has not completed yet. Failed items are retried.
+    ///
+    /// Stops at the first item whose output exists under [`CollisionPolicy::Ask`],
+    /// returning its index and the existing path so the caller can ask the user.
+    pub fn generate_all(&mut self, policy: CollisionPolicy) -> Option<(usize, PathBuf)> {
+        for i in 0..self.items.len() {
+            if matches!(self.items[i].status, JobStatus::Pending | JobStatus::Failed(_)) {
+                if let Some(existing) = self.generate_item(i, policy) {
+                    return Some((i, existing));
+                }
+            }
+        }
+        None
+    }
+
+    /// Retry the currently selected item, regardless of its previous status
+    pub fn retry_selected(&mut self, policy: CollisionPolicy) -> Option<(usize, PathBuf)> {
+        let i = self.list_state.selected().filter(|i| *i < self.items.len())?;
+        self.items[i].status = JobStatus::Pending;
+        self.generate_item(i, policy).map(|existing| (i, existing))
+    }
+
+    /// Generate one item. Returns the existing output path if the user must be asked.
+    pub fn generate_item(&mut self, index: usize, policy: CollisionPolicy) -> Option<PathBuf> {
+        let item = &mut self.items[index];
+        let output_dir = if item.output_dir.trim().is_empty() {
+            None
+        } else {
+            Some(item.output_dir.trim())
+        };
+
+        let result = validate_for_printing(&item.params)
+            .and_then(|_| generate_stl_file(&item.params, output_dir, policy));
+
+        let (status, existing) = match result {
+            Ok(OutputStatus::Written(path)) => (JobStatus::Done(path), None),
+            Ok(OutputStatus::Skipped(path)) => (JobStatus::Skipped(path), None),
+            Err(e) => match e.downcast_ref::<OutputExists>() {
+                // Stays pending until the user decides
+                Some(existing) => (JobStatus::Pending, Some(existing.0.clone())),
+                None => (JobStatus::Failed(e.to_string()), None),
+            },
+        };
+        item.status = status;
+        existing
+    }
+
+    pub fn count(&self, status: fn(&JobStatus) -> bool) -> usize {
+        self.items.iter().filter(|item| status(&item.status)).count()
+    }
+}
+
+/// Ring types in the order they appear in the TUI list
+const RING_TYPE_OPTIONS: [RingType; 9] = [
+    RingType::Convex,
+    RingType::Concave,
+    RingType::ThreePoint,
+    RingType::RetainingRing,
+    RingType::Spacer,
+    RingType::Flexure,
+    RingType::Tangent,
+    RingType::SharpCorner,
+    RingType::Conical,
+];
+
+fn ring_type_label(ring_type: RingType) -> &'static str {
+    match ring_type {
+        RingType::Convex => "🔲 Convex (CX) - Curves inward toward lens",
+        RingType::Concave => "🔳 Concave (CC) - Curves outward from lens",
+        RingType::ThreePoint => "⚡ Three-Point (3P) - Minimal contact points",
+        RingType::RetainingRing => "🔩 Retaining Ring (RR) - Threaded lens retainer",
+        RingType::Spacer => "📏 Spacer (SP) - Flat spacer between elements",
+        RingType::Flexure => "🌀 Flexure (FX) - Lens held by compliant fingers",
+        RingType::Tangent => "⭕ Tangent (TG) - Toroidal seat touching the lens surface",
+        RingType::SharpCorner => "📐 Sharp Corner (SC) - Lens rests on the bore edge",
+        RingType::Conical => "🔻 Conical (CN) - Chamfered seat that self-centers the lens",
+        RingType::Saddle => "🛏 Saddle (SD) - Rectangular cradle for a cylindrical lens",
+        RingType::Profile => "✏ Custom Profile (PR) - Revolved from a user-defined cross-section",
+    }
+}
+
+/// Two clicks on the same cell within this window count as a double-click
+const DOUBLE_CLICK_WINDOW: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Button {
+    Generate,
+    AddToQueue,
+    RunQueue,
+    Browse,
+    Help,
+}
+
+impl Button {
+    const ALL: [Button; 5] = [
+        Button::Generate,
+        Button::AddToQueue,
+        Button::RunQueue,
+        Button::Browse,
+        Button::Help,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Button::Generate => "Generate",
+            Button::AddToQueue => "Queue F4",
+            Button::RunQueue => "Run F5",
+            Button::Browse => "Browse F3",
+            Button::Help => "Help F1",
+        }
+    }
+}
+
+/// Screen areas recorded during the last draw, used to hit-test mouse events
+#[derive(Debug, Clone, Default)]
+pub struct ClickAreas {
+    pub fields: Vec<(InputField, Rect)>,
+    pub buttons: Vec<(Button, Rect)>,
+    pub directory_list: Rect,
+    /// Choices in the overwrite dialog; `None` cancels
+    pub collision_choices: Vec<(Option<CollisionPolicy>, Rect)>,
+}
+
+fn contains(area: Rect, column: u16, row: u16) -> bool {
+    column >= area.x && column < area.x + area.width && row >= area.y && row < area.y + area.height
+}
+
+/// Index of the list item under `row`, for a bordered list drawn in `area`
+fn list_index_at(area: Rect, offset: usize, row: u16) -> Option<usize> {
+    if row <= area.y || row + 1 >= area.y + area.height {
+        return None;
+    }
+    Some(offset + (row - area.y - 1) as usize)
+}
+
+/// Generation waiting for the user to decide what to do with an existing file
+#[derive(Debug, Clone, PartialEq)]
+pub enum CollisionTarget {
+    Form,
+    Queue(usize),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingCollision {
+    pub existing: PathBuf,
+    pub target: CollisionTarget,
+}
+
+impl ClickAreas {
+    pub fn field_at(&self, column: u16, row: u16) -> Option<(InputField, Rect)> {
+        self.fields.iter().find(|(_, area)| contains(*area, column, row)).cloned()
+    }
+
+    pub fn set_field(&mut self, field: InputField, area: Rect) {
+        self.fields.retain(|(f, _)| *f != field);
+        self.fields.push((field, area));
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -120,7 +518,53 @@ pub enum InputField {
     RingType,
     OuterDiameter,
     InnerDiameter,
+    /// Thread specification, shown for retaining rings
+    Thread,
+    /// Ring height, shown for spacers, flexure mounts and lens-contact seats
+    Height,
+    /// Label text, empty for none
+    Label,
+    /// Lens surface radius, shown for tangent, sharp-corner and conical rings
+    LensRadius,
+    /// Contact diameter, shown for tangent and conical rings
+    ContactDiameter,
+    /// Cone half-angle, shown for conical rings
+    ConeAngle,
     OutputDir,
+    FilenameTemplate,
+    Queue,
+}
+
+impl InputField {
+    pub fn title(&self) -> &'static str {
+        match self {
+            InputField::RingType => "Ring Type",
+            InputField::OuterDiameter => "Outer Diameter (mm)",
+            InputField::InnerDiameter => "Inner Diameter (mm)",
+            InputField::Thread => "Thread (SM1, M25x0.5, add 'internal' for bore)",
+            InputField::Height => "Height (mm, empty for automatic)",
+            InputField::Label => "Label (e.g. {type} {inner}, empty for none)",
+            InputField::LensRadius => "Lens Surface Radius (mm, negative for concave)",
+            InputField::ContactDiameter => "Contact Diameter (mm, empty for mid-wall)",
+            InputField::ConeAngle => "Cone Half-Angle (degrees)",
+            InputField::OutputDir => "Output Directory",
+            InputField::FilenameTemplate => "Filename Template",
+            InputField::Queue => "Job Queue",
+        }
+    }
+
+    /// Whether the field only accepts numbers
+    fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            InputField::OuterDiameter
+                | InputField::InnerDiameter
+                | InputField::Height
+                | InputField::LensRadius
+                | InputField::ContactDiameter
+                | InputField::ConeAngle
+        )
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -129,6 +573,15 @@ pub struct AppState {
     pub outer_diameter: String,
     pub inner_diameter: String,
     pub output_dir: String,
+    pub thread: String,
+    pub height: String,
+    pub label: String,
+    pub lens_radius: String,
+    pub contact_diameter: String,
+    pub cone_angle: String,
+    pub filename_template: String,
+    /// Material, preset and sequence used when naming output files
+    pub naming: NameOptions,
     pub current_field: InputField,
     pub ring_type_list_state: ListState,
     pub show_help: bool,
@@ -139,6 +592,13 @@ pub struct AppState {
     pub generation_progress: Option<u16>,
     pub generation_complete: bool,
     pub generated_file: Option<String>,
+    pub queue: JobQueue,
+    pub status_message: Option<String>,
+    pub click_areas: ClickAreas,
+    pub last_click: Option<(Instant, u16, u16)>,
+    pub config: Config,
+    pub collision_policy: CollisionPolicy,
+    pub pending_collision: Option<PendingCollision>,
 }
 
 impl Default for AppState {
@@ -151,6 +611,14 @@ impl Default for AppState {
             outer_diameter: String::new(),
             inner_diameter: String::new(),
             output_dir: String::from("./"),
+            thread: String::from("SM1"),
+            height: String::new(),
+            label: String::new(),
+            lens_radius: String::new(),
+            contact_diameter: String::new(),
+            cone_angle: String::from("45"),
+            filename_template: DEFAULT_TEMPLATE.to_string(),
+            naming: NameOptions::default(),
             current_field: InputField::RingType,
             ring_type_list_state,
             show_help: false,
@@ -161,55 +629,125 @@ impl Default for AppState {
             generation_progress: None,
             generation_complete: false,
             generated_file: None,
+            queue: JobQueue::default(),
+            status_message: None,
+            click_areas: ClickAreas::default(),
+            last_click: None,
+            config: Config::default(),
+            collision_policy: CollisionPolicy::default(),
+            pending_collision: None,
         }
     }
 }
 
 impl AppState {
+    /// Fields shown in the form, in tab order. Type-specific fields only
+    /// appear once that ring type is chosen.
+    pub fn visible_fields(&self) -> Vec<InputField> {
+        let mut fields = vec![InputField::RingType, InputField::OuterDiameter, InputField::InnerDiameter];
+        if self.ring_type == Some(RingType::RetainingRing) {
+            fields.push(InputField::Thread);
+        }
+        if matches!(
+            self.ring_type,
+            Some(RingType::Spacer | RingType::Flexure | RingType::Tangent | RingType::SharpCorner | RingType::Conical)
+        ) {
+            fields.push(InputField::Height);
+        }
+        if self.ring_type == Some(RingType::Tangent) {
+            fields.extend([InputField::LensRadius, InputField::ContactDiameter]);
+        }
+        if self.ring_type == Some(RingType::SharpCorner) {
+            fields.push(InputField::LensRadius);
+        }
+        if self.ring_type == Some(RingType::Conical) {
+            fields.extend([InputField::ConeAngle, InputField::ContactDiameter, InputField::LensRadius]);
+        }
+        if !matches!(self.ring_type, Some(RingType::RetainingRing | RingType::Flexure)) {
+            fields.push(InputField::Label);
+        }
+        fields.extend([InputField::OutputDir, InputField::FilenameTemplate, InputField::Queue]);
+        fields
+    }
+
     pub fn next_field(&mut self) {
-        self.current_field = match self.current_field {
-            InputField::RingType => InputField::OuterDiameter,
-            InputField::OuterDiameter => InputField::InnerDiameter,
-            InputField::InnerDiameter => InputField::OutputDir,
-            InputField::OutputDir => InputField::RingType,
-        };
+        let fields = self.visible_fields();
+        let i = fields.iter().position(|f| *f == self.current_field).unwrap_or(0);
+        self.current_field = fields[(i + 1) % fields.len()].clone();
         self.validation_message = None;
+        self.status_message = None;
     }
 
     pub fn previous_field(&mut self) {
-        self.current_field = match self.current_field {
-            InputField::RingType => InputField::OutputDir,
-            InputField::OuterDiameter => InputField::RingType,
-            InputField::InnerDiameter => InputField::OuterDiameter,
-            InputField::OutputDir => InputField::InnerDiameter,
-        };
+        let fields = self.visible_fields();
+        let i = fields.iter().position(|f| *f == self.current_field).unwrap_or(0);
+        self.current_field = fields[(i + fields.len() - 1) % fields.len()].clone();
         self.validation_message = None;
+        self.status_message = None;
+    }
+
+    /// Whether the focused field accepts arbitrary text
+    pub fn is_text_field(&self) -> bool {
+        matches!(
+            self.current_field,
+            InputField::Thread | InputField::Label | InputField::OutputDir | InputField::FilenameTemplate
+        )
+    }
+
+    /// Text of an input field; `None` for list fields
+    pub fn field_text(&self, field: &InputField) -> Option<&str> {
+        match field {
+            InputField::OuterDiameter => Some(&self.outer_diameter),
+            InputField::InnerDiameter => Some(&self.inner_diameter),
+            InputField::Thread => Some(&self.thread),
+            InputField::Height => Some(&self.height),
+            InputField::Label => Some(&self.label),
+            InputField::LensRadius => Some(&self.lens_radius),
+            InputField::ContactDiameter => Some(&self.contact_diameter),
+            InputField::ConeAngle => Some(&self.cone_angle),
+            InputField::OutputDir => Some(&self.output_dir),
+            InputField::FilenameTemplate => Some(&self.filename_template),
+            InputField::RingType | InputField::Queue => None,
+        }
+    }
+
+    fn field_text_mut(&mut self, field: &InputField) -> Option<&mut String> {
+        match field {
+            InputField::OuterDiameter => Some(&mut self.outer_diameter),
+            InputField::InnerDiameter => Some(&mut self.inner_diameter),
+            InputField::Thread => Some(&mut self.thread),
+            InputField::Height => Some(&mut self.height),
+            InputField::Label => Some(&mut self.label),
+            InputField::LensRadius => Some(&mut self.lens_radius),
+            InputField::ContactDiameter => Some(&mut self.contact_diameter),
+            InputField::ConeAngle => Some(&mut self.cone_angle),
+            InputField::OutputDir => Some(&mut self.output_dir),
+            InputField::FilenameTemplate => Some(&mut self.filename_template),
+            InputField::RingType | InputField::Queue => None,
+        }
     }
 
     pub fn handle_ring_type_input(&mut self, key: KeyCode) {
         match key {
             KeyCode::Up => {
+                let last = RING_TYPE_OPTIONS.len() - 1;
                 let i = match self.ring_type_list_state.selected() {
-                    Some(i) => if i == 0 { 2 } else { i - 1 },
+                    Some(i) => if i == 0 { last } else { i - 1 },
                     None => 0,
                 };
                 self.ring_type_list_state.select(Some(i));
             }
             KeyCode::Down => {
+                let last = RING_TYPE_OPTIONS.len() - 1;
                 let i = match self.ring_type_list_state.selected() {
-                    Some(i) => if i >= 2 { 0 } else { i + 1 },
+                    Some(i) => if i >= last { 0 } else { i + 1 },
                     None => 0,
                 };
                 self.ring_type_list_state.select(Some(i));
             }
             KeyCode::Enter => {
                 if let Some(i) = self.ring_type_list_state.selected() {
-                    self.ring_type = Some(match i {
-                        0 => RingType::Convex,
-                        1 => RingType::Concave,
-                        2 => RingType::ThreePoint,
-                        _ => RingType::Convex,
-                    });
+                    self.ring_type = Some(RING_TYPE_OPTIONS.get(i).copied().unwrap_or(RingType::Convex));
                     self.next_field();
                 }
             }
@@ -218,74 +756,156 @@ impl AppState {
     }
 
     pub fn handle_text_input(&mut self, c: char) {
-        match self.current_field {
-            InputField::OuterDiameter => {
-                if c.is_ascii_digit() || c == '.' {
-                    self.outer_diameter.push(c);
-                }
-            }
-            InputField::InnerDiameter => {
-                if c.is_ascii_digit() || c == '.' {
-                    self.inner_diameter.push(c);
-                }
-            }
-            InputField::OutputDir => {
-                self.output_dir.push(c);
-            }
-            _ => {}
+        let field = self.current_field.clone();
+        let sign = c == '-' && field == InputField::LensRadius;
+        if field.is_numeric() && !(c.is_ascii_digit() || c == '.' || sign) {
+            return;
+        }
+        if let Some(text) = self.field_text_mut(&field) {
+            text.push(c);
         }
         self.validation_message = None;
     }
 
     pub fn handle_backspace(&mut self) {
-        match self.current_field {
-            InputField::OuterDiameter => {
-                self.outer_diameter.pop();
-            }
-            InputField::InnerDiameter => {
-                self.inner_diameter.pop();
-            }
-            InputField::OutputDir => {
-                self.output_dir.pop();
-            }
-            _ => {}
+        let field = self.current_field.clone();
+        if let Some(text) = self.field_text_mut(&field) {
+            text.pop();
         }
         self.validation_message = None;
     }
 
-    pub fn validate_and_generate(&mut self) -> Result<()> {
-        // Validate inputs
-        if self.ring_type.is_none() {
-            self.validation_message = Some("Please select a ring type".to_string());
-            self.current_field = InputField::RingType;
-            return Ok(());
+    /// Build ring parameters from the form, reporting the first invalid field
+    pub fn build_parameters(&mut self) -> Option<RingParameters> {
+        match self.form_parameters() {
+            Ok(params) => Some(params),
+            Err((message, field)) => {
+                self.validation_message = Some(message);
+                if let Some(field) = field {
+                    self.current_field = field;
+                }
+                None
+            }
         }
+    }
 
-        let outer_diameter: f32 = match self.outer_diameter.parse() {
-            Ok(val) => val,
-            Err(_) => {
-                self.validation_message = Some("Invalid outer diameter".to_string());
-                self.current_field = InputField::OuterDiameter;
-                return Ok(());
-            }
+    /// Ring parameters described by the form, or the error and the field to fix
+    fn form_parameters(&self) -> std::result::Result<RingParameters, (String, Option<InputField>)> {
+        let ring_type = self.ring_type
+            .ok_or_else(|| ("Please select a ring type".to_string(), Some(InputField::RingType)))?;
+
+        let thread: Option<ThreadSpec> = if ring_type == RingType::RetainingRing {
+            let thread = self.thread.parse()
+                .map_err(|e: anyhow::Error| (e.to_string(), Some(InputField::Thread)))?;
+            Some(thread)
+        } else {
+            None
         };
 
-        let inner_diameter: f32 = match self.inner_diameter.parse() {
-            Ok(val) => val,
-            Err(_) => {
-                self.validation_message = Some("Invalid inner diameter".to_string());
-                self.current_field = InputField::InnerDiameter;
-                return Ok(());
+        // The threaded wall takes its diameter from the thread, so it may be left empty
+        let parse_diameter = |text: &str, threaded: Option<f32>, field: InputField, name: &str| {
+            match (text.trim().parse::<f32>(), threaded) {
+                (Ok(value), _) => Ok(value),
+                (Err(_), Some(value)) if text.trim().is_empty() => Ok(value),
+                (Err(_), _) => Err((format!("Invalid {} diameter", name), Some(field))),
             }
         };
+        let threaded_outer = thread.as_ref().filter(|t| t.side == ThreadSide::External).map(|t| t.major_diameter);
+        let threaded_inner = thread.as_ref().filter(|t| t.side == ThreadSide::Internal).map(|t| t.minor_diameter());
+        let outer_diameter = parse_diameter(&self.outer_diameter, threaded_outer, InputField::OuterDiameter, "outer")?;
+        let inner_diameter = parse_diameter(&self.inner_diameter, threaded_inner, InputField::InnerDiameter, "inner")?;
+
+        validate_template(&self.filename_template)
+            .map_err(|e| (e.to_string(), Some(InputField::FilenameTemplate)))?;
+
+        let mut params = RingParameters::new(ring_type, outer_diameter, inner_diameter)
+            .map_err(|e| (format!("Validation error: {}", e), None))?;
+        if let Some(thread) = thread {
+            params = params.with_thread(thread)
+                .map_err(|e| (format!("Validation error: {}", e), Some(InputField::Thread)))?;
+        }
+        let sets_height = matches!(
+            ring_type,
+            RingType::Spacer | RingType::Flexure | RingType::Tangent | RingType::SharpCorner | RingType::Conical
+        );
+        if sets_height && !self.height.trim().is_empty() {
+            params.height = self.height.trim().parse::<f32>().ok()
+                .filter(|height| *height > 0.0)
+                .ok_or_else(|| ("Invalid height".to_string(), Some(InputField::Height)))?;
+        }
+        if ring_type == RingType::Tangent {
+            let lens_radius = self.parse_lens_radius()?;
+            let tangent = TangentSpec { lens_radius, contact_diameter: self.parse_contact_diameter()? };
+            tangent.validate(inner_diameter / 2.0, outer_diameter / 2.0, params.height)
+                .map_err(|e| (e.to_string(), Some(InputField::ContactDiameter)))?;
+            params.tangent = Some(tangent);
+        }
+        if ring_type == RingType::SharpCorner {
+            let corner = CornerSpec { lens_radius: self.parse_lens_radius()?, relief: 0.0 };
+            corner.validate(inner_diameter / 2.0, outer_diameter / 2.0, params.height)
+                .map_err(|e| (e.to_string(), Some(InputField::LensRadius)))?;
+            params.corner = Some(corner);
+        }
+        if ring_type == RingType::Conical {
+            let half_angle = self.cone_angle.trim().parse::<f32>()
+                .map_err(|_| ("Invalid cone half-angle".to_string(), Some(InputField::ConeAngle)))?;
+            let lens_radius = match self.lens_radius.trim() {
+                "" => None,
+                _ => Some(self.parse_lens_radius()?),
+            };
+            let cone = ConeSpec { half_angle, contact_diameter: self.parse_contact_diameter()?, lens_radius };
+            cone.validate(inner_diameter / 2.0, outer_diameter / 2.0, params.height)
+                .map_err(|e| (e.to_string(), Some(InputField::ConeAngle)))?;
+            params.cone = Some(cone);
+        }
+        params.naming = self.naming_options();
+        if !matches!(ring_type, RingType::RetainingRing | RingType::Flexure) && !self.label.trim().is_empty() {
+            let label = LabelSpec {
+                template: self.label.trim().to_string(),
+                placement: LabelPlacement::for_ring(ring_type),
+                ..LabelSpec::default()
+            };
+            RingLabel::new(&label, &params).map_err(|e| (e.to_string(), Some(InputField::Label)))?;
+            params.label = Some(label);
+        }
+        Ok(params)
+    }
 
-        // Create ring parameters
-        let params = match RingParameters::new(self.ring_type.unwrap(), outer_diameter, inner_diameter) {
-            Ok(params) => params,
-            Err(e) => {
-                self.validation_message = Some(format!("Validation error: {}", e));
-                return Ok(());
-            }
+    /// Lens surface radius field, for tangent and sharp-corner rings
+    fn parse_lens_radius(&self) -> std::result::Result<f32, (String, Option<InputField>)> {
+        self.lens_radius.trim().parse::<f32>().ok()
+            .filter(|radius| *radius != 0.0)
+            .ok_or_else(|| ("Invalid lens surface radius".to_string(), Some(InputField::LensRadius)))
+    }
+
+    /// Contact diameter field, empty for mid-wall
+    fn parse_contact_diameter(&self) -> std::result::Result<Option<f32>, (String, Option<InputField>)> {
+        match self.contact_diameter.trim() {
+            "" => Ok(None),
+            text => text.parse::<f32>().ok()
+                .filter(|diameter| *diameter > 0.0)
+                .map(Some)
+                .ok_or_else(|| ("Invalid contact diameter".to_string(), Some(InputField::ContactDiameter))),
+        }
+    }
+
+    /// Naming options for the next part; queued parts are numbered in order
+    fn naming_options(&self) -> NameOptions {
+        NameOptions {
+            template: self.filename_template.clone(),
+            sequence: self.naming.sequence + self.queue.items.len() as u32,
+            ..self.naming.clone()
+        }
+    }
+
+    pub fn validate_and_generate(&mut self) -> Result<()> {
+        self.generate_form(self.collision_policy)
+    }
+
+    fn generate_form(&mut self, policy: CollisionPolicy) -> Result<()> {
+        let params = match self.build_parameters() {
+            Some(params) => params,
+            None => return Ok(()),
         };
 
         // Validate for 3D printing
@@ -311,21 +931,261 @@ impl AppState {
             Some(self.output_dir.trim())
         };
 
-        match generate_stl_file(&params, output_dir) {
-            Ok(file_path) => {
+        self.generation_progress = None;
+        match generate_stl_file(&params, output_dir, policy) {
+            Ok(OutputStatus::Written(file_path)) => {
                 self.generated_file = Some(file_path);
                 self.generation_complete = true;
-                self.generation_progress = None;
             }
-            Err(e) => {
-                self.validation_message = Some(format!("Generation error: {}", e));
-                self.generation_progress = None;
+            Ok(OutputStatus::Skipped(file_path)) => {
+                self.status_message = Some(format!("Skipped, file already exists: {}", file_path));
             }
+            Err(e) => match e.downcast_ref::<OutputExists>() {
+                Some(existing) => {
+                    self.pending_collision = Some(PendingCollision {
+                        existing: existing.0.clone(),
+                        target: CollisionTarget::Form,
+                    });
+                }
+                None => {
+                    self.validation_message = Some(format!("Generation error: {}", e));
+                }
+            },
         }
 
         Ok(())
     }
 
+    /// Answer the overwrite dialog. `None` cancels the pending generation.
+    pub fn resolve_collision(&mut self, choice: Option<CollisionPolicy>) -> Result<()> {
+        let pending = match self.pending_collision.take() {
+            Some(pending) => pending,
+            None => return Ok(()),
+        };
+        let policy = match choice {
+            Some(policy) => policy,
+            None => {
+                self.status_message = Some("Generation cancelled".to_string());
+                return Ok(());
+            }
+        };
+
+        match pending.target {
+            CollisionTarget::Form => self.generate_form(policy)?,
+            CollisionTarget::Queue(index) => {
+                if let Some(existing) = self.queue.generate_item(index, policy) {
+                    self.pending_collision = Some(PendingCollision {
+                        existing,
+                        target: CollisionTarget::Queue(index),
+                    });
+                } else {
+                    self.continue_queue();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn handle_collision_input(&mut self, key: KeyCode) -> Result<()> {
+        match key {
+            KeyCode::Char('o') => self.resolve_collision(Some(CollisionPolicy::Overwrite)),
+            KeyCode::Char('s') => self.resolve_collision(Some(CollisionPolicy::Skip)),
+            KeyCode::Char('n') => self.resolve_collision(Some(CollisionPolicy::Suffix)),
+            KeyCode::Esc | KeyCode::Char('c') => self.resolve_collision(None),
+            _ => Ok(()),
+        }
+    }
+
+    /// Add the current form to the job queue, keeping the form for the next ring
+    pub fn add_to_queue(&mut self) {
+        if let Some(params) = self.build_parameters() {
+            let label = params.filename();
+            self.queue.push(params, self.output_dir.trim().to_string());
+            self.status_message = Some(format!(
+                "Queued {} ({} item(s) in queue)",
+                label,
+                self.queue.items.len()
+            ));
+        }
+    }
+
+    pub fn generate_queue(&mut self) {
+        if self.queue.items.is_empty() {
+            self.validation_message = Some("Queue is empty. Press F4 to add the current ring".to_string());
+            return;
+        }
+
+        self.continue_queue();
+    }
+
+    /// Run the remaining queue items, pausing to ask about existing files
+    fn continue_queue(&mut self) {
+        if let Some((index, existing)) = self.queue.generate_all(self.collision_policy) {
+            self.pending_collision = Some(PendingCollision {
+                existing,
+                target: CollisionTarget::Queue(index),
+            });
+            return;
+        }
+
+        self.status_message = Some(format!(
+            "Queue finished: {} done, {} skipped, {} failed",
+            self.queue.count(|s| matches!(s, JobStatus::Done(_))),
+            self.queue.count(|s| matches!(s, JobStatus::Skipped(_))),
+            self.queue.count(|s| matches!(s, JobStatus::Failed(_))),
+        ));
+    }
+
+    pub fn handle_queue_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Up => self.queue.move_up(),
+            KeyCode::Down => self.queue.move_down(),
+            KeyCode::Enter => self.generate_queue(),
+            KeyCode::Delete | KeyCode::Backspace | KeyCode::Char('d') => self.queue.remove_selected(),
+            KeyCode::Char('r') => {
+                if let Some((index, existing)) = self.queue.retry_selected(self.collision_policy) {
+                    self.pending_collision = Some(PendingCollision {
+                        existing,
+                        target: CollisionTarget::Queue(index),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn press_button(&mut self, button: Button) -> Result<()> {
+        match button {
+            Button::Generate => {
+                if self.generation_complete {
+                    self.reset();
+                } else {
+                    self.validate_and_generate()?;
+                }
+            }
+            Button::AddToQueue => self.add_to_queue(),
+            Button::RunQueue => self.generate_queue(),
+            Button::Browse => {
+                self.current_field = InputField::OutputDir;
+                self.open_directory_browser()?;
+            }
+            Button::Help => self.show_help = !self.show_help,
+        }
+        Ok(())
+    }
+
+    /// Record a left click and report whether it completes a double-click
+    fn register_click(&mut self, column: u16, row: u16) -> bool {
+        let now = Instant::now();
+        let is_double = matches!(
+            self.last_click,
+            Some((at, c, r)) if c == column && r == row && now.duration_since(at) <= DOUBLE_CLICK_WINDOW
+        );
+        // A double-click consumes the pending click so a third click starts over
+        self.last_click = if is_double { None } else { Some((now, column, row)) };
+        is_double
+    }
+
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) -> Result<()> {
+        let (column, row) = (mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let is_double = self.register_click(column, row);
+                if self.pending_collision.is_some() {
+                    let choice = self.click_areas.collision_choices
+                        .iter()
+                        .find(|(_, area)| contains(*area, column, row))
+                        .map(|(choice, _)| *choice);
+                    if let Some(choice) = choice {
+                        self.resolve_collision(choice)?;
+                    }
+                } else if self.show_directory_browser {
+                    self.handle_directory_browser_click(column, row, is_double)?;
+                } else if self.show_help {
+                    self.show_help = false;
+                } else {
+                    self.handle_form_click(column, row)?;
+                }
+            }
+            MouseEventKind::ScrollUp => self.handle_scroll(column, row, KeyCode::Up)?,
+            MouseEventKind::ScrollDown => self.handle_scroll(column, row, KeyCode::Down)?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_form_click(&mut self, column: u16, row: u16) -> Result<()> {
+        let areas = self.click_areas.clone();
+
+        if let Some((button, _)) = areas.buttons.iter().find(|(_, area)| contains(*area, column, row)) {
+            return self.press_button(*button);
+        }
+
+        let (field, area) = match areas.field_at(column, row) {
+            Some(hit) => hit,
+            None => return Ok(()),
+        };
+
+        if self.current_field != field {
+            self.current_field = field.clone();
+            self.validation_message = None;
+            self.status_message = None;
+        }
+
+        match field {
+            InputField::RingType => {
+                let offset = self.ring_type_list_state.offset();
+                if let Some(i) = list_index_at(area, offset, row) {
+                    if let Some(ring_type) = RING_TYPE_OPTIONS.get(i) {
+                        self.ring_type_list_state.select(Some(i));
+                        self.ring_type = Some(*ring_type);
+                    }
+                }
+            }
+            InputField::Queue => {
+                let offset = self.queue.list_state.offset();
+                if let Some(i) = list_index_at(area, offset, row) {
+                    if i < self.queue.items.len() {
+                        self.queue.list_state.select(Some(i));
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_scroll(&mut self, column: u16, row: u16, key: KeyCode) -> Result<()> {
+        if self.show_directory_browser {
+            return self.handle_directory_browser_input(key);
+        }
+        match self.click_areas.field_at(column, row) {
+            Some((InputField::RingType, _)) => self.handle_ring_type_input(key),
+            Some((InputField::Queue, _)) => self.handle_queue_input(key),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_directory_browser_click(&mut self, column: u16, row: u16, is_double: bool) -> Result<()> {
+        let list_area = self.click_areas.directory_list;
+        if let Some(ref mut browser) = self.directory_browser {
+            if !contains(list_area, column, row) {
+                return Ok(());
+            }
+            let offset = browser.list_state.offset();
+            if let Some(i) = list_index_at(list_area, offset, row) {
+                if i < browser.entries.len() {
+                    browser.list_state.select(Some(i));
+                    if is_double {
+                        return self.handle_directory_browser_input(KeyCode::Enter);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn open_directory_browser(&mut self) -> Result<()> {
         let current_dir = if self.output_dir.is_empty() || self.output_dir == "./" {
             std::env::current_dir()?
@@ -333,18 +1193,40 @@ impl AppState {
             PathBuf::from(&self.output_dir)
         };
         
-        self.directory_browser = Some(DirectoryBrowser::new(current_dir)?);
+        let mut browser = DirectoryBrowser::new(current_dir)?;
+        browser.target_filename = self.planned_filename();
+        self.directory_browser = Some(browser);
         self.show_directory_browser = true;
         Ok(())
     }
 
+    /// File name the current form would generate, if the form is complete and valid
+    pub fn planned_filename(&self) -> Option<String> {
+        self.form_parameters().ok().map(|params| params.filename())
+    }
+
     pub fn close_directory_browser(&mut self) {
         self.show_directory_browser = false;
         self.directory_browser = None;
     }
 
     pub fn handle_directory_browser_input(&mut self, key: KeyCode) -> Result<()> {
+        let mode = match self.directory_browser {
+            Some(ref browser) => browser.mode.clone(),
+            None => return Ok(()),
+        };
+
+        match mode {
+            BrowserMode::Browse => self.handle_browse_input(key),
+            BrowserMode::PathInput(input) => self.handle_path_input(key, input),
+            BrowserMode::NewFolder(name) => self.handle_new_folder_input(key, name),
+            BrowserMode::Search(query) => self.handle_search_input(key, query),
+        }
+    }
+
+    fn handle_browse_input(&mut self, key: KeyCode) -> Result<()> {
         if let Some(ref mut browser) = self.directory_browser {
+            browser.message = None;
             match key {
                 KeyCode::Up => browser.move_up(),
                 KeyCode::Down => browser.move_down(),
@@ -367,6 +1249,33 @@ impl AppState {
                     browser.show_hidden = !browser.show_hidden;
                     browser.refresh_entries()?;
                 }
+                KeyCode::Char('g') => {
+                    let mut input = browser.current_path.to_string_lossy().to_string();
+                    if !input.ends_with(std::path::MAIN_SEPARATOR) {
+                        input.push(std::path::MAIN_SEPARATOR);
+                    }
+                    browser.mode = BrowserMode::PathInput(input);
+                }
+                KeyCode::Char('n') => browser.mode = BrowserMode::NewFolder(String::new()),
+                KeyCode::Char('/') => browser.mode = BrowserMode::Search(String::new()),
+                KeyCode::Char('b') => {
+                    let bookmarked = self.config.toggle_bookmark(&browser.current_path);
+                    browser.message = Some(match self.config.save() {
+                        Ok(()) if bookmarked => "Bookmark added".to_string(),
+                        Ok(()) => "Bookmark removed".to_string(),
+                        Err(e) => format!("Could not save bookmarks: {}", e),
+                    });
+                }
+                KeyCode::Char(c @ '1'..='9') => {
+                    let index = c as usize - '1' as usize;
+                    if let Some(bookmark) = self.config.bookmarks.get(index) {
+                        if bookmark.is_dir() {
+                            browser.navigate_to(bookmark.clone())?;
+                        } else {
+                            browser.message = Some(format!("Bookmark no longer exists: {}", bookmark.display()));
+                        }
+                    }
+                }
                 KeyCode::Esc => {
                     self.close_directory_browser();
                 }
@@ -376,12 +1285,110 @@ impl AppState {
         Ok(())
     }
 
+    fn handle_path_input(&mut self, key: KeyCode, mut input: String) -> Result<()> {
+        if let Some(ref mut browser) = self.directory_browser {
+            match key {
+                KeyCode::Char(c) => input.push(c),
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Tab => {
+                    let (completed, candidates) = complete_path(&input);
+                    input = completed;
+                    browser.completions = candidates;
+                }
+                KeyCode::Enter => {
+                    let path = expand_home(input.trim());
+                    browser.completions.clear();
+                    if path.is_dir() {
+                        browser.mode = BrowserMode::Browse;
+                        browser.navigate_to(path)?;
+                    } else {
+                        browser.message = Some(format!("Not a directory: {}", path.display()));
+                    }
+                    return Ok(());
+                }
+                KeyCode::Esc => {
+                    browser.completions.clear();
+                    browser.mode = BrowserMode::Browse;
+                    return Ok(());
+                }
+                _ => {}
+            }
+            browser.mode = BrowserMode::PathInput(input);
+        }
+        Ok(())
+    }
+
+    fn handle_new_folder_input(&mut self, key: KeyCode, mut name: String) -> Result<()> {
+        if let Some(ref mut browser) = self.directory_browser {
+            match key {
+                KeyCode::Char(c) => name.push(c),
+                KeyCode::Backspace => {
+                    name.pop();
+                }
+                KeyCode::Enter => {
+                    browser.message = Some(match browser.create_folder(&name) {
+                        Ok(path) => format!("Created {}", path.display()),
+                        Err(e) => format!("Could not create folder: {}", e),
+                    });
+                    browser.mode = BrowserMode::Browse;
+                    return Ok(());
+                }
+                KeyCode::Esc => {
+                    browser.mode = BrowserMode::Browse;
+                    return Ok(());
+                }
+                _ => {}
+            }
+            browser.mode = BrowserMode::NewFolder(name);
+        }
+        Ok(())
+    }
+
+    fn handle_search_input(&mut self, key: KeyCode, mut query: String) -> Result<()> {
+        if let Some(ref mut browser) = self.directory_browser {
+            match key {
+                KeyCode::Char(c) => {
+                    query.push(c);
+                    browser.search(&query);
+                }
+                KeyCode::Backspace => {
+                    query.pop();
+                    browser.search(&query);
+                }
+                KeyCode::Up => browser.move_up(),
+                KeyCode::Down => browser.move_down(),
+                KeyCode::Enter | KeyCode::Esc => {
+                    browser.mode = BrowserMode::Browse;
+                    if key == KeyCode::Enter {
+                        return self.handle_browse_input(KeyCode::Enter);
+                    }
+                    return Ok(());
+                }
+                _ => {}
+            }
+            browser.mode = BrowserMode::Search(query);
+        }
+        Ok(())
+    }
+
+    /// Reset the form, keeping the job queue and settings
     pub fn reset(&mut self) {
-        *self = AppState::default();
+        let queue = std::mem::take(&mut self.queue);
+        let config = std::mem::take(&mut self.config);
+        *self = AppState {
+            queue,
+            config,
+            collision_policy: self.collision_policy,
+            filename_template: self.filename_template.clone(),
+            naming: self.naming.clone(),
+            ..AppState::default()
+        };
     }
 }
 
-pub fn run_ui() -> Result<()> {
+pub fn run_ui(collision_policy: CollisionPolicy, naming: NameOptions) -> Result<()> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -390,34 +1397,48 @@ pub fn run_ui() -> Result<()> {
     let mut terminal = Terminal::new(backend)?;
 
     // Create app state
-    let mut app_state = AppState::default();
+    let mut app_state = AppState {
+        config: Config::load(),
+        collision_policy,
+        filename_template: naming.template.clone(),
+        naming,
+        ..AppState::default()
+    };
     let mut should_quit = false;
 
     // Main loop
     while !should_quit {
         terminal.draw(|f| ui(f, &mut app_state))?;
 
-        if let Event::Key(key) = event::read()? {
-            if key.kind == KeyEventKind::Press {
-                // Handle directory browser input first if it's open
-                if app_state.show_directory_browser {
+        match event::read()? {
+            Event::Mouse(mouse) => {
+                let _ = app_state.handle_mouse(mouse);
+            }
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                // The overwrite dialog takes priority, then the directory browser
+                if app_state.pending_collision.is_some() {
+                    let _ = app_state.handle_collision_input(key.code);
+                } else if app_state.show_directory_browser {
                     let _ = app_state.handle_directory_browser_input(key.code);
                 } else {
                     match key.code {
+                        // Letters are typed into free-text fields rather than used as shortcuts
+                        KeyCode::Char(c) if app_state.is_text_field() => app_state.handle_text_input(c),
                         KeyCode::Char('q') => should_quit = true,
                         KeyCode::Char('h') => app_state.show_help = !app_state.show_help,
                         KeyCode::Char('p') => app_state.show_preview = !app_state.show_preview,
                         KeyCode::F(1) => app_state.show_help = !app_state.show_help,
-                        KeyCode::F(3) => {
-                            if app_state.current_field == InputField::OutputDir {
-                                let _ = app_state.open_directory_browser();
-                            }
+                        KeyCode::F(3) if app_state.current_field == InputField::OutputDir => {
+                            let _ = app_state.open_directory_browser();
                         }
+                        KeyCode::F(4) => app_state.add_to_queue(),
+                        KeyCode::F(5) => app_state.generate_queue(),
                         KeyCode::Tab => app_state.next_field(),
                         KeyCode::BackTab => app_state.previous_field(),
                         KeyCode::Enter => {
                             match app_state.current_field {
                                 InputField::RingType => app_state.handle_ring_type_input(key.code),
+                                InputField::Queue => app_state.handle_queue_input(key.code),
                                 _ => {
                                     if app_state.generation_complete {
                                         app_state.reset();
@@ -439,21 +1460,30 @@ pub fn run_ui() -> Result<()> {
                                 should_quit = true;
                             }
                         }
+                        KeyCode::Backspace | KeyCode::Delete
+                            if app_state.current_field == InputField::Queue => {
+                            app_state.handle_queue_input(key.code);
+                        }
                         KeyCode::Backspace => app_state.handle_backspace(),
-                        KeyCode::Up | KeyCode::Down => {
-                            if app_state.current_field == InputField::RingType {
-                                app_state.handle_ring_type_input(key.code);
-                            }
+                        KeyCode::Up | KeyCode::Down
+                            if app_state.current_field == InputField::RingType => {
+                            app_state.handle_ring_type_input(key.code);
                         }
-                        KeyCode::Char(c) => {
-                            if app_state.current_field != InputField::RingType {
-                                app_state.handle_text_input(c);
-                            }
+                        KeyCode::Up | KeyCode::Down
+                            if app_state.current_field == InputField::Queue => {
+                            app_state.handle_queue_input(key.code);
+                        }
+                        KeyCode::Char(c) if app_state.current_field == InputField::Queue => {
+                            app_state.handle_queue_input(KeyCode::Char(c));
+                        }
+                        KeyCode::Char(c) if app_state.current_field != InputField::RingType => {
+                            app_state.handle_text_input(c);
                         }
                         _ => {}
                     }
                 }
             }
+            _ => {}
         }
     }
 
@@ -498,8 +1528,14 @@ fn ui(f: &mut Frame, app: &mut AppState) {
     // Left panel - Input form
     render_input_form(f, app, main_chunks[0]);
 
-    // Right panel - Preview/Info
-    render_info_panel(f, app, main_chunks[1]);
+    // Right panel - Preview/Info above the job queue
+    let right_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(8), Constraint::Length(10)])
+        .split(main_chunks[1]);
+
+    render_info_panel(f, app, right_chunks[0]);
+    render_queue_panel(f, app, right_chunks[1]);
 
     // Status bar
     render_status_bar(f, app, chunks[2]);
@@ -516,9 +1552,64 @@ fn ui(f: &mut Frame, app: &mut AppState) {
     if app.show_directory_browser {
         render_directory_browser(f, app, size);
     }
+
+    if app.pending_collision.is_some() {
+        render_collision_dialog(f, app, size);
+    }
 }
 
-fn render_input_form(f: &mut Frame, app: &AppState, area: Rect) {
+fn render_collision_dialog(f: &mut Frame, app: &mut AppState, area: Rect) {
+    let pending = match app.pending_collision {
+        Some(ref pending) => pending.clone(),
+        None => return,
+    };
+
+    let popup_area = centered_rect(60, 30, area);
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title("File Already Exists")
+        .borders(Borders::ALL)
+        .style(Style::default().fg(Color::Yellow));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(2), Constraint::Length(3)])
+        .split(inner);
+
+    let message = Paragraph::new(format!(
+        "{}\nalready exists. What should happen to it?",
+        pending.existing.display()
+    ))
+    .style(Style::default().fg(Color::White))
+    .alignment(Alignment::Center)
+    .wrap(Wrap { trim: true });
+    f.render_widget(message, chunks[0]);
+
+    let choices: [(Option<CollisionPolicy>, &str); 4] = [
+        (Some(CollisionPolicy::Overwrite), "o: Overwrite"),
+        (Some(CollisionPolicy::Skip), "s: Skip"),
+        (Some(CollisionPolicy::Suffix), "n: New name"),
+        (None, "Esc: Cancel"),
+    ];
+    let choice_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(choices.iter().map(|_| Constraint::Ratio(1, choices.len() as u32)))
+        .split(chunks[1]);
+
+    app.click_areas.collision_choices.clear();
+    for ((choice, label), chunk) in choices.iter().zip(choice_chunks.iter()) {
+        let button = Paragraph::new(*label)
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::ALL));
+        f.render_widget(button, *chunk);
+        app.click_areas.collision_choices.push((*choice, *chunk));
+    }
+}
+
+fn render_input_form(f: &mut Frame, app: &mut AppState, area: Rect) {
     let block = Block::default()
         .title("Configuration")
         .borders(Borders::ALL)
@@ -527,84 +1618,85 @@ fn render_input_form(f: &mut Frame, app: &AppState, area: Rect) {
     let inner = block.inner(area);
     f.render_widget(block, area);
 
+    // The queue is drawn in the right panel, every other field here
+    let fields: Vec<InputField> = app.visible_fields()
+        .into_iter()
+        .filter(|field| *field != InputField::Queue)
+        .collect();
+
+    let mut constraints: Vec<Constraint> = fields.iter().map(|field| match field {
+        InputField::RingType => Constraint::Length(RING_TYPE_OPTIONS.len() as u16 + 2),
+        _ => Constraint::Length(3),
+    }).collect();
+    constraints.push(Constraint::Length(3)); // Buttons
+    constraints.push(Constraint::Min(1));    // Spacing
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(6),  // Ring type
-            Constraint::Length(3),  // Outer diameter
-            Constraint::Length(3),  // Inner diameter
-            Constraint::Length(3),  // Output directory
-            Constraint::Min(1),     // Spacing
-        ])
+        .constraints(constraints)
         .split(inner);
 
-    // Ring Type Selection
-    let ring_types = vec![
-        ListItem::new("🔲 Convex (CX) - Curves inward toward lens"),
-        ListItem::new("🔳 Concave (CC) - Curves outward from lens"),
-        ListItem::new("⚡ Three-Point (3P) - Minimal contact points"),
-    ];
+    app.click_areas.fields.retain(|(field, _)| *field == InputField::Queue);
 
-    let ring_type_style = if app.current_field == InputField::RingType {
-        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default()
-    };
+    for (field, chunk) in fields.iter().zip(chunks.iter()) {
+        app.click_areas.set_field(field.clone(), *chunk);
 
-    let ring_type_list = List::new(ring_types)
-        .block(Block::default()
-            .title("Ring Type")
-            .borders(Borders::ALL)
-            .style(ring_type_style))
-        .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
-        .highlight_symbol("→ ");
+        let field_style = if app.current_field == *field {
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
 
-    f.render_stateful_widget(ring_type_list, chunks[0], &mut app.ring_type_list_state.clone());
+        if *field == InputField::RingType {
+            // Ring Type Selection
+            let ring_types: Vec<ListItem> = RING_TYPE_OPTIONS.iter()
+                .map(|ring_type| ListItem::new(ring_type_label(*ring_type)))
+                .collect();
 
-    // Outer Diameter Input
-    let outer_diameter_style = if app.current_field == InputField::OuterDiameter {
-        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default()
-    };
+            let ring_type_list = List::new(ring_types)
+                .block(Block::default()
+                    .title(field.title())
+                    .borders(Borders::ALL)
+                    .style(field_style))
+                .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
+                .highlight_symbol("→ ");
 
-    let outer_diameter_input = Paragraph::new(app.outer_diameter.as_str())
-        .style(Style::default().fg(Color::White))
-        .block(Block::default()
-            .title("Outer Diameter (mm)")
-            .borders(Borders::ALL)
-            .style(outer_diameter_style));
-    f.render_widget(outer_diameter_input, chunks[1]);
+            f.render_stateful_widget(ring_type_list, *chunk, &mut app.ring_type_list_state);
+        } else {
+            let input = Paragraph::new(app.field_text(field).unwrap_or_default().to_string())
+                .style(Style::default().fg(Color::White))
+                .block(Block::default()
+                    .title(field.title())
+                    .borders(Borders::ALL)
+                    .style(field_style));
+            f.render_widget(input, *chunk);
+        }
+    }
 
-    // Inner Diameter Input
-    let inner_diameter_style = if app.current_field == InputField::InnerDiameter {
-        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default()
-    };
+    render_buttons(f, app, chunks[fields.len()]);
+}
 
-    let inner_diameter_input = Paragraph::new(app.inner_diameter.as_str())
-        .style(Style::default().fg(Color::White))
-        .block(Block::default()
-            .title("Inner Diameter (mm)")
-            .borders(Borders::ALL)
-            .style(inner_diameter_style));
-    f.render_widget(inner_diameter_input, chunks[2]);
+fn render_buttons(f: &mut Frame, app: &mut AppState, area: Rect) {
+    let button_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(Button::ALL.iter().map(|_| Constraint::Ratio(1, Button::ALL.len() as u32)))
+        .split(area);
 
-    // Output Directory Input
-    let output_dir_style = if app.current_field == InputField::OutputDir {
-        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default()
-    };
+    app.click_areas.buttons.clear();
+    for (button, chunk) in Button::ALL.iter().zip(button_chunks.iter()) {
+        let style = if *button == Button::Generate {
+            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
 
-    let output_dir_input = Paragraph::new(app.output_dir.as_str())
-        .style(Style::default().fg(Color::White))
-        .block(Block::default()
-            .title("Output Directory")
-            .borders(Borders::ALL)
-            .style(output_dir_style));
-    f.render_widget(output_dir_input, chunks[3]);
+        let widget = Paragraph::new(button.label())
+            .style(style)
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::ALL).style(style));
+        f.render_widget(widget, *chunk);
+        app.click_areas.buttons.push((*button, *chunk));
+    }
 }
 
 fn render_info_panel(f: &mut Frame, app: &AppState, area: Rect) {
@@ -653,11 +1745,7 @@ fn render_preview_panel(f: &mut Frame, app: &AppState, area: Rect) {
     let mut preview_text = String::from("📋 Preview\n\n");
     
     if let Some(ring_type) = app.ring_type {
-        preview_text.push_str(&format!("Type: {} ({})\n", ring_type, match ring_type {
-            RingType::Convex => "Convex",
-            RingType::Concave => "Concave",
-            RingType::ThreePoint => "Three-point",
-        }));
+        preview_text.push_str(&format!("Type: {} ({})\n", ring_type, ring_type.name()));
     } else {
         preview_text.push_str("Type: Not selected\n");
     }
@@ -680,8 +1768,7 @@ fn render_preview_panel(f: &mut Frame, app: &AppState, area: Rect) {
             let wall_thickness = (outer - inner) / 2.0;
             preview_text.push_str(&format!("Wall thickness: {:.1}mm\n", wall_thickness));
             
-            if let Some(ring_type) = app.ring_type {
-                let filename = format!("{}-{}.stl", ring_type, inner);
+            if let Some(filename) = app.planned_filename() {
                 preview_text.push_str(&format!("\nOutput file: {}", filename));
             }
         }
@@ -700,11 +1787,51 @@ fn render_preview_panel(f: &mut Frame, app: &AppState, area: Rect) {
     f.render_widget(preview_panel, area);
 }
 
+fn render_queue_panel(f: &mut Frame, app: &mut AppState, area: Rect) {
+    app.click_areas.set_field(InputField::Queue, area);
+
+    let queue_style = if app.current_field == InputField::Queue {
+        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::Magenta)
+    };
+
+    let items: Vec<ListItem> = if app.queue.items.is_empty() {
+        vec![ListItem::new("Empty - press F4 to add the current ring")]
+    } else {
+        app.queue.items.iter().map(|item| {
+            let (text, color) = match &item.status {
+                JobStatus::Pending => (format!("⏳ {}", item.label()), Color::White),
+                JobStatus::Done(path) => (format!("✅ {} → {}", item.label(), path), Color::Green),
+                JobStatus::Skipped(path) => (format!("⏭ {} - kept existing {}", item.label(), path), Color::Gray),
+                JobStatus::Failed(err) => (format!("❌ {} - {}", item.label(), err), Color::Red),
+            };
+            ListItem::new(text).style(Style::default().fg(color))
+        }).collect()
+    };
+
+    let queue_list = List::new(items)
+        .block(Block::default()
+            .title(format!("Job Queue ({})", app.queue.items.len()))
+            .borders(Borders::ALL)
+            .style(queue_style))
+        .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
+        .highlight_symbol("→ ");
+
+    f.render_stateful_widget(queue_list, area, &mut app.queue.list_state);
+}
+
 fn render_status_bar(f: &mut Frame, app: &AppState, area: Rect) {
-    let status_text = if app.current_field == InputField::OutputDir {
-        "Tab: Next field | F3: Browse directory | Enter: Generate | F1/h: Help | q: Quit"
+    let status_text = if let Some(ref msg) = app.status_message {
+        msg.as_str()
+    } else if app.current_field == InputField::OutputDir {
+        "Tab: Next field | F3: Browse directory | F4: Queue ring | Enter: Generate | F1: Help | Esc: Quit"
+    } else if app.current_field == InputField::FilenameTemplate {
+        "{type} {inner} {outer} {height} {material} {date} {preset} {seq} | {inner:.2} {seq:03} | F1: Help"
+    } else if app.current_field == InputField::Queue {
+        "↑/↓: Select | Enter/F5: Generate queue | r: Retry selected | d/Del: Remove | q: Quit"
     } else {
-        "Tab: Next field | Shift+Tab: Previous | Enter: Select/Generate | F1/h: Help | q: Quit"
+        "Tab: Next field | Shift+Tab: Previous | Enter: Select/Generate | F4: Queue | F5: Run queue | F1/h: Help"
     };
     
     let status_bar = Paragraph::new(status_text)
@@ -716,7 +1843,7 @@ fn render_status_bar(f: &mut Frame, app: &AppState, area: Rect) {
 }
 
 fn render_help_popup(f: &mut Frame, area: Rect) {
-    let popup_area = centered_rect(80, 60, area);
+    let popup_area = centered_rect(80, 80, area);
     
     let help_text = "🔬 Optics Ring Generator - Help\n\n\
         NAVIGATION:\n\
@@ -725,18 +1852,44 @@ fn render_help_popup(f: &mut Frame, area: Rect) {
         • Enter - Select option or generate STL\n\
         • Backspace - Delete characters\n\
         • F3 - Open directory browser (when in Output Directory field)\n\
+        • Filename Template - placeholders {type} {inner} {outer} {height}\n\
+          {material} {date} {preset} {seq}, e.g. {inner:.2} or {seq:03}\n\
+        • Label - text engraved on the top face, same placeholders,\n\
+          e.g. {type} {inner} or SN {seq:03}; leave empty for none\n\
+        • F4 - Add the current ring to the job queue\n\
+        • F5 - Generate all queued rings\n\
         • Esc - Close dialogs or quit\n\
         • q - Quit application\n\n\
+        MOUSE:\n\
+        • Click a field or ring type to select it\n\
+        • Click the buttons below the form to run actions\n\
+        • Scroll wheel - Move through lists\n\
+        • Double-click a folder in the browser to open it\n\n\
+        JOB QUEUE (Tab to the queue panel):\n\
+        • Arrow keys - Select a queued ring\n\
+        • Enter - Generate all pending and failed rings\n\
+        • r - Retry the selected ring\n\
+        • d / Delete - Remove the selected ring\n\n\
         DIRECTORY BROWSER:\n\
         • Arrow keys - Navigate file/folder list\n\
         • Enter - Enter selected directory\n\
         • Space - Select current directory\n\
         • h - Toggle hidden files\n\
+        • g - Type a path to go to (Tab completes)\n\
+        • / - Search the folder by name\n\
+        • n - Create a new folder here\n\
+        • b - Bookmark this folder, 1-9 to open a bookmark\n\
         • Esc - Cancel and close browser\n\n\
         RING TYPES:\n\
         • Convex (CX) - Curves inward, gentle contact\n\
         • Concave (CC) - Curves outward, secure cradle\n\
-        • Three-Point (3P) - Minimal contact points\n\n\
+        • Three-Point (3P) - Minimal contact points\n\
+        • Retaining Ring (RR) - Threaded retainer, set the thread field\n\
+        • Spacer (SP) - Flat spacer, set the height field\n\
+        • Flexure (FX) - Compliant fingers, inner diameter is the lens\n\
+        • Tangent (TG) - Toroidal seat, set the lens radius and contact diameter\n\
+        • Sharp Corner (SC) - Lens rests on the bore edge, set the lens radius\n\
+        • Conical (CN) - Chamfered seat, set the cone angle and contact diameter\n\n\
         REQUIREMENTS:\n\
         • Outer diameter > Inner diameter\n\
         • Minimum wall thickness: 1.0mm\n\
@@ -799,8 +1952,8 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
-fn render_directory_browser(f: &mut Frame, app: &AppState, area: Rect) {
-    if let Some(ref browser) = app.directory_browser {
+fn render_directory_browser(f: &mut Frame, app: &mut AppState, area: Rect) {
+    if let Some(ref mut browser) = app.directory_browser {
         // Create popup area (80% of screen)
         let popup_area = centered_rect(80, 80, area);
         
@@ -816,32 +1969,74 @@ fn render_directory_browser(f: &mut Frame, app: &AppState, area: Rect) {
         let inner_area = block.inner(popup_area);
         f.render_widget(block, popup_area);
         
-        // Split into directory list and help text
+        // Split into input line, directory list with side panels, and help text
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Min(10),  // Directory list
-                Constraint::Length(4), // Help text
+                Constraint::Length(3), // Path / search input
+                Constraint::Min(10),   // Directory list and side panels
+                Constraint::Length(5), // Help text
             ])
             .split(inner_area);
+
+        let body_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(chunks[1]);
+
+        let side_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(body_chunks[1]);
+
+        // Input line
+        let (input_title, input_text) = match &browser.mode {
+            BrowserMode::Browse => (
+                "Path".to_string(),
+                browser.message.clone().unwrap_or_else(|| browser.current_path.display().to_string()),
+            ),
+            BrowserMode::PathInput(input) => {
+                let title = if browser.completions.is_empty() {
+                    "Go to path (Tab to complete)".to_string()
+                } else {
+                    format!("Go to path - matches: {}", browser.completions.join(", "))
+                };
+                (title, format!("{}█", input))
+            }
+            BrowserMode::NewFolder(name) => ("New folder name".to_string(), format!("{}█", name)),
+            BrowserMode::Search(query) => ("Search".to_string(), format!("{}█", query)),
+        };
+        let input_style = if browser.mode == BrowserMode::Browse {
+            Style::default().fg(Color::Gray)
+        } else {
+            Style::default().fg(Color::White).add_modifier(Modifier::BOLD)
+        };
+        let input = Paragraph::new(input_text)
+            .style(input_style)
+            .block(Block::default().borders(Borders::ALL).title(input_title));
+        f.render_widget(input, chunks[0]);
         
         // Directory list
+        let collision = browser.collision().cloned();
         let items: Vec<ListItem> = browser.entries.iter().enumerate().map(|(i, path)| {
-            let display_name = if i == 0 && path.parent().is_some() {
-                ".. (parent directory)".to_string()
+            if browser.is_parent_entry(i) {
+                return ListItem::new(".. (parent directory)");
+            }
+
+            let name = path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("<invalid>")
+                .to_string();
+            if path.is_dir() {
+                ListItem::new(format!("📁 {}/", name))
+            } else if collision.as_ref() == Some(path) {
+                ListItem::new(format!("⚠️  {} (same name as planned output)", name))
+                    .style(Style::default().fg(Color::Red))
+            } else if is_stl_file(path) {
+                ListItem::new(format!("🧊 {}", name)).style(Style::default().fg(Color::Cyan))
             } else {
-                let name = path.file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("<invalid>")
-                    .to_string();
-                if path.is_dir() {
-                    format!("📁 {}/", name)
-                } else {
-                    format!("📄 {}", name)
-                }
-            };
-            
-            ListItem::new(display_name)
+                ListItem::new(format!("📄 {}", name))
+            }
         }).collect();
         
         let list = List::new(items)
@@ -849,13 +2044,47 @@ fn render_directory_browser(f: &mut Frame, app: &AppState, area: Rect) {
             .highlight_style(Style::default().fg(Color::Black).bg(Color::Yellow))
             .highlight_symbol("▶ ");
         
-        let mut list_state = browser.list_state.clone();
-        f.render_stateful_widget(list, chunks[0], &mut list_state);
+        f.render_stateful_widget(list, body_chunks[0], &mut browser.list_state);
+        app.click_areas.directory_list = body_chunks[0];
+
+        // Bookmarks
+        let bookmarks: Vec<ListItem> = if app.config.bookmarks.is_empty() {
+            vec![ListItem::new("None - press b to bookmark this folder")]
+        } else {
+            app.config.bookmarks.iter().enumerate().map(|(i, bookmark)| {
+                let marker = if *bookmark == browser.current_path { "★" } else { " " };
+                let label = if i < 9 { format!("{}", i + 1) } else { " ".to_string() };
+                ListItem::new(format!("{} {} {}", label, marker, bookmark.display()))
+            }).collect()
+        };
+        let bookmark_list = List::new(bookmarks)
+            .block(Block::default().borders(Borders::ALL).title("Bookmarks"))
+            .style(Style::default().fg(Color::White));
+        f.render_widget(bookmark_list, side_chunks[0]);
+
+        // Existing STL files, so naming collisions are visible before generating
+        let stl_files = browser.stl_files();
+        let mut stl_items: Vec<ListItem> = Vec::new();
+        if let Some(ref target) = browser.target_filename {
+            stl_items.push(if collision.is_some() {
+                ListItem::new(format!("⚠️  {} already exists", target)).style(Style::default().fg(Color::Red))
+            } else {
+                ListItem::new(format!("✓ {} is free", target)).style(Style::default().fg(Color::Green))
+            });
+        }
+        if stl_files.is_empty() {
+            stl_items.push(ListItem::new("No STL files here"));
+        }
+        stl_items.extend(stl_files.iter().map(|path| ListItem::new(entry_name(path))));
+        let stl_list = List::new(stl_items)
+            .block(Block::default().borders(Borders::ALL).title(format!("STL files ({})", stl_files.len())))
+            .style(Style::default().fg(Color::Cyan));
+        f.render_widget(stl_list, side_chunks[1]);
         
         // Help text
         let help_text = format!(
-            "Navigation: ↑/↓ to select, Enter to enter directory, Space to select current directory\n\
-             Options: h to toggle hidden files, Esc to cancel\n\
+            "Navigation: ↑/↓ or scroll to select, Enter or double-click to enter directory, Space to select current directory\n\
+             Options: g go to path, / search, n new folder, b bookmark folder, 1-9 open bookmark, h hidden files, Esc cancel\n\
              Current selection will be saved to: {}", 
             browser.current_path.display()
         );
@@ -865,6 +2094,6 @@ fn render_directory_browser(f: &mut Frame, app: &AppState, area: Rect) {
             .style(Style::default().fg(Color::Gray))
             .wrap(Wrap { trim: true });
         
-        f.render_widget(help, chunks[1]);
+        f.render_widget(help, chunks[2]);
     }
 }
@@ -0,0 +1,152 @@
+use anyhow::Result;
+use std::f32::consts::PI;
+
+use crate::features::angle_between;
+use crate::mesh::{ProfilePoint, Surface, EDGE_RAMP};
+
+/// Largest share of the seat circumference radial slots may remove
+const MAX_SLOT_COVERAGE: f32 = 0.25;
+
+/// Which vents to cut
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VentStyle {
+    /// Slots across the seat face, from bore to outer wall
+    Radial,
+    /// Grooves along the bore, through the full height
+    Axial,
+    Both,
+}
+
+/// Air-escape channels so the cavity behind a seated lens can breathe
+#[derive(Debug, Clone, PartialEq)]
+pub struct VentSpec {
+    pub style: VentStyle,
+    pub count: usize,
+    pub width: f32,
+    pub depth: f32,
+    /// Angle of the first vent in degrees. When unset the vents sit half a
+    /// spacing past the clocking angle, which keeps every vent between the
+    /// three-point contacts when `count` is a multiple of three
+    pub angle: Option<f32>,
+}
+
+impl VentSpec {
+    fn radial(&self) -> bool {
+        matches!(self.style, VentStyle::Radial | VentStyle::Both)
+    }
+
+    /// Whether the vents include grooves along the bore
+    pub fn axial(&self) -> bool {
+        matches!(self.style, VentStyle::Axial | VentStyle::Both)
+    }
+
+    /// Center angle of each vent in radians, given the ring's clocking in degrees
+    pub fn angles(&self, clocking: f32) -> Vec<f32> {
+        let first = self.angle.unwrap_or(clocking + 180.0 / self.count.max(1) as f32).to_radians();
+        (0..self.count).map(|i| first + 2.0 * PI * i as f32 / self.count as f32).collect()
+    }
+
+    /// Check that the vents leave the ring printable and the contact annulus intact.
+    ///
+    /// `contact_radius` is where the lens bears on the seat and `pads` are the
+    /// three-point contact windows as (center, half width) in radians.
+    pub fn validate(
+        &self,
+        inner_radius: f32,
+        outer_radius: f32,
+        seat_height: f32,
+        contact_radius: f32,
+        pads: &[(f32, f32)],
+        clocking: f32,
+    ) -> Result<()> {
+        if self.count == 0 || self.width <= 0.0 || self.depth <= 0.0 {
+            return Err(anyhow::anyhow!("Vent count, width and depth must be positive"));
+        }
+
+        if self.radial() {
+            if seat_height - self.depth < 1.0 {
+                return Err(anyhow::anyhow!(
+                    "Vent slots {:.2}mm deep leave less than 1.0mm under the {:.2}mm seat",
+                    self.depth, seat_height
+                ));
+            }
+            let coverage = self.count as f32 * self.width / (2.0 * PI * contact_radius);
+            if coverage > MAX_SLOT_COVERAGE {
+                return Err(anyhow::anyhow!(
+                    "{} vent slots {:.2}mm wide remove {:.0}% of the contact annulus. Maximum: {:.0}%",
+                    self.count, self.width, coverage * 100.0, MAX_SLOT_COVERAGE * 100.0
+                ));
+            }
+            for vent in self.angles(clocking) {
+                let half = self.width / 2.0 / contact_radius;
+                if pads.iter().any(|&(pad, pad_half)| angle_between(vent, pad).abs() < half + pad_half) {
+                    return Err(anyhow::anyhow!(
+                        "Vent slot at {:.0}° cuts through a three-point contact. Use a multiple of 3 vents, or --vent-angle to move them",
+                        vent.to_degrees().rem_euclid(360.0)
+                    ));
+                }
+            }
+        }
+
+        if self.axial() {
+            if inner_radius + self.depth > contact_radius - 0.5 {
+                return Err(anyhow::anyhow!(
+                    "Vent grooves {:.2}mm deep reach the contact annulus at {:.2}mm diameter",
+                    self.depth, 2.0 * contact_radius
+                ));
+            }
+            if outer_radius - inner_radius - self.depth < 1.0 {
+                return Err(anyhow::anyhow!("Vent grooves leave less than 1.0mm of wall"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Extra angles at the vent edges, so the channels have crisp sides
+    pub fn angle_breaks(&self, inner_radius: f32, outer_radius: f32, clocking: f32) -> Vec<f32> {
+        let mut halves = Vec::new();
+        if self.radial() {
+            halves.push(self.slot_half_angle(inner_radius, outer_radius));
+        }
+        if self.axial() {
+            halves.push(self.width / 2.0 / inner_radius);
+        }
+
+        self.angles(clocking).into_iter()
+            .flat_map(|vent| halves.iter().flat_map(move |half| {
+                [vent - half - EDGE_RAMP, vent - half, vent + half, vent + half + EDGE_RAMP]
+            }))
+            .collect()
+    }
+
+    /// Slots keep the same angular width across the seat, matching `width` at mid-wall
+    fn slot_half_angle(&self, inner_radius: f32, outer_radius: f32) -> f32 {
+        self.width / (inner_radius + outer_radius)
+    }
+
+    /// Cut the vents into a profile point. `seat_base` is the height of the
+    /// top corners; corner points move with the slot so it stays open at both ends.
+    pub fn apply(
+        &self,
+        point: &ProfilePoint,
+        angle: f32,
+        inner_radius: f32,
+        outer_radius: f32,
+        seat_base: f32,
+        clocking: f32,
+    ) -> ProfilePoint {
+        let mut point = *point;
+        let offset = self.angles(clocking).into_iter()
+            .map(|vent| angle_between(angle, vent).abs())
+            .fold(PI, f32::min);
+
+        let on_top = point.surface == Surface::Top || point.z >= seat_base - 1e-5;
+        if self.radial() && on_top && offset <= self.slot_half_angle(inner_radius, outer_radius) + 1e-5 {
+            point.z -= self.depth;
+        }
+        if self.axial() && point.surface == Surface::Bore && offset <= self.width / 2.0 / inner_radius + 1e-5 {
+            point.r += self.depth;
+        }
+        point
+    }
+}
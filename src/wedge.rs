@@ -0,0 +1,51 @@
+use anyhow::Result;
+
+/// Steepest wedge the seat may be tilted by, in degrees
+const MAX_WEDGE: f32 = 30.0;
+
+/// Fixed-point iterations when intersecting a tilted seat with a vertical line
+const TILT_ITERATIONS: usize = 6;
+
+/// Tilt of the seat relative to the ring base, for cells with known tilt,
+/// prisms and wedged windows
+#[derive(Debug, Clone, PartialEq)]
+pub struct WedgeSpec {
+    /// Tilt of the seat's axis from the ring axis, in degrees
+    pub angle: f32,
+    /// Direction the seat rises toward, in degrees counter-clockwise from +X
+    pub orientation: f32,
+}
+
+impl WedgeSpec {
+    /// Height of the tilted seat above base point (`x`, `y`).
+    ///
+    /// `seat` gives the untilted seat height at a distance from the axis. The
+    /// whole seat is rotated about the horizontal line through the axis at
+    /// `pivot`, so curved seats keep their profile square to the tilted axis.
+    pub fn tilted_height(&self, x: f32, y: f32, pivot: f32, seat: impl Fn(f32) -> f32) -> f32 {
+        let (sin, cos) = self.angle.to_radians().sin_cos();
+        let direction = self.orientation.to_radians();
+        let along = x * direction.cos() + y * direction.sin();
+        let across = -x * direction.sin() + y * direction.cos();
+
+        // Find the point of the tilted seat above (x, y): its coordinates
+        // along the tilt and up the axis, in the seat's own frame
+        let mut up = 0.0;
+        let mut tilted_along = along / cos;
+        for _ in 0..TILT_ITERATIONS {
+            up = seat((tilted_along * tilted_along + across * across).sqrt()) - pivot;
+            tilted_along = (along + up * sin) / cos;
+        }
+        pivot + tilted_along * sin + up * cos
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if !(0.0..=MAX_WEDGE).contains(&self.angle) {
+            return Err(anyhow::anyhow!(
+                "Wedge angle {:.2}° is out of range. Use 0-{:.0}°",
+                self.angle, MAX_WEDGE
+            ));
+        }
+        Ok(())
+    }
+}